@@ -0,0 +1,71 @@
+//! What solving one part of a puzzle gives you
+
+use std::fmt::Display;
+
+/// One part's answer, in whatever shape the puzzle asks for
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Answer {
+    Num(u64),
+    /// A single line of text
+    Text(String),
+    /// Letters drawn as a picture over several lines, like day 13's folded paper
+    Grid(String),
+}
+
+impl Answer {
+    /// Whether this takes more than one line to show
+    pub fn is_multiline(&self) -> bool {
+        match self {
+            Self::Num(_) => false,
+            Self::Text(text) | Self::Grid(text) => text.contains('\n'),
+        }
+    }
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Num(n) => write!(f, "{}", n),
+            Self::Text(text) | Self::Grid(text) => f.write_str(text),
+        }
+    }
+}
+
+impl From<u64> for Answer {
+    fn from(n: u64) -> Self {
+        Self::Num(n)
+    }
+}
+
+impl From<u32> for Answer {
+    fn from(n: u32) -> Self {
+        Self::Num(n.into())
+    }
+}
+
+impl From<usize> for Answer {
+    fn from(n: usize) -> Self {
+        Self::Num(n as u64)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(text: String) -> Self {
+        Self::Text(text)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        assert_eq!(Answer::from(893u64).to_string(), "893");
+        assert_eq!(Answer::from(String::from("CQRK")).to_string(), "CQRK");
+        let grid = Answer::Grid(String::from("#..\n#.."));
+        assert_eq!(grid.to_string(), "#..\n#..");
+        assert!(grid.is_multiline());
+        assert!(!Answer::from(7usize).is_multiline());
+    }
+}
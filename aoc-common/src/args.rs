@@ -0,0 +1,163 @@
+use crate::{input::InputSource, logging, timing::Timer};
+
+const USAGE: &str = "[--input <path>] [--time] [--visualize] [--dot] [-v | -vv]
+
+  --input <path>  solve this file instead of the bundled input (`-` reads stdin)
+  --time          report how long each phase took and how much it allocated
+  --visualize     animate the solution in the terminal, on days that can
+  --dot           print the input as a Graphviz graph instead of solving it, on days that can
+  -v, --verbose   log diagnostics and phase timings to stderr, twice for every step";
+
+/// Command line options shared by every day's binary
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Args {
+    pub input: InputSource,
+    pub time: bool,
+    pub visualize: bool,
+    pub dot: bool,
+    /// How many times `-v` was given
+    pub verbosity: u8,
+}
+
+impl Args {
+    /// Parses command line arguments (without the program name).
+    ///
+    /// Accepts `--input <path>` (or `--input=<path>`), `--time`, `--visualize`, `--dot` and any
+    /// number of `-v`/`--verbose` (also written `-vv`), with `--input` at most once.
+    pub fn from_args<I, S>(args: I) -> Result<Self, String>
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let mut args = args.into_iter().map(Into::into);
+        let mut parsed = Self::default();
+        let mut seen_input = false;
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--time" => {
+                    parsed.time = true;
+                    continue;
+                }
+                "--visualize" => {
+                    parsed.visualize = true;
+                    continue;
+                }
+                "--dot" => {
+                    parsed.dot = true;
+                    continue;
+                }
+                "--verbose" => {
+                    parsed.verbosity = parsed.verbosity.saturating_add(1);
+                    continue;
+                }
+                flags
+                    if flags.len() > 1
+                        && flags.starts_with('-')
+                        && flags[1..].bytes().all(|b| b == b'v') =>
+                {
+                    let count = u8::try_from(flags.len() - 1).unwrap_or(u8::MAX);
+                    parsed.verbosity = parsed.verbosity.saturating_add(count);
+                    continue;
+                }
+                _ => {}
+            }
+            let path = match arg.strip_prefix("--input") {
+                Some("") => args
+                    .next()
+                    .ok_or_else(|| String::from("`--input` needs a path, or `-` for stdin"))?,
+                Some(path) if path.starts_with('=') => path[1..].to_string(),
+                _ => return Err(format!("unexpected argument `{}`", arg)),
+            };
+            if seen_input {
+                return Err(String::from("`--input` given more than once"));
+            }
+            seen_input = true;
+            parsed.input = InputSource::from_path(path);
+        }
+        Ok(parsed)
+    }
+
+    /// Parses this process's arguments, printing usage and exiting if they don't make sense,
+    /// then starts logging at the requested verbosity
+    pub fn from_env() -> Self {
+        let mut args = std::env::args();
+        let program = args.next().unwrap_or_default();
+        match Self::from_args(args) {
+            Ok(args) => {
+                logging::init(args.verbosity);
+                args
+            }
+            Err(e) => {
+                eprintln!("{}\n\nusage: {} {}", e, program, USAGE);
+                std::process::exit(2);
+            }
+        }
+    }
+
+    /// Reads the chosen input, falling back to `bundled`. Exits if it can't be read.
+    pub fn read_input(&self, bundled: &str) -> String {
+        match self.input.read(bundled) {
+            Ok(input) => input,
+            Err(e) => {
+                eprintln!("couldn't read input: {}", e);
+                std::process::exit(1);
+            }
+        }
+    }
+
+    /// A timer that only records anything if `--time` was passed
+    pub fn timer(&self) -> Timer {
+        Timer::new(self.time)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::path::PathBuf;
+
+    use super::*;
+
+    #[test]
+    fn test_from_args() {
+        let none: [&str; 0] = [];
+        assert_eq!(Args::from_args(none), Ok(Args::default()));
+        assert_eq!(
+            Args::from_args(["--input", "mine.txt"]),
+            Ok(Args {
+                input: InputSource::File(PathBuf::from("mine.txt")),
+                time: false,
+                visualize: false,
+                dot: false,
+                verbosity: 0,
+            })
+        );
+        assert_eq!(
+            Args::from_args(["--time", "--input=mine.txt"]),
+            Ok(Args {
+                input: InputSource::File(PathBuf::from("mine.txt")),
+                time: true,
+                visualize: false,
+                dot: false,
+                verbosity: 0,
+            })
+        );
+        assert_eq!(
+            Args::from_args(["--visualize"]).map(|args| args.visualize),
+            Ok(true)
+        );
+        assert_eq!(Args::from_args(["--dot"]).map(|args| args.dot), Ok(true));
+        assert_eq!(
+            Args::from_args(["--input", "-"]).map(|args| args.input),
+            Ok(InputSource::Stdin)
+        );
+        assert_eq!(
+            Args::from_args(["-v", "--time", "-vv", "--verbose"]).map(|args| args.verbosity),
+            Ok(4)
+        );
+        assert!(Args::from_args(["-vx"]).is_err());
+        assert!(Args::from_args(["-"]).is_err());
+        assert!(Args::from_args(["--input"]).is_err());
+        assert!(Args::from_args(["--inputs", "x"]).is_err());
+        assert!(Args::from_args(["--input", "a", "--input", "b"]).is_err());
+    }
+}
@@ -0,0 +1,89 @@
+use thiserror::Error;
+
+/// Everything that can go wrong turning a puzzle input into something to solve
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum AocError {
+    /// Part of the input isn't in the puzzle's format. `line` and `column` are 1-indexed.
+    #[error("line {line}, column {column}: {message}")]
+    Parse {
+        line: usize,
+        column: usize,
+        message: String,
+    },
+    /// The input stopped before something the puzzle needs
+    #[error("unexpected end of input, expected {0}")]
+    UnexpectedEnd(String),
+}
+
+impl AocError {
+    pub fn parse(line: usize, column: usize, message: impl Into<String>) -> Self {
+        Self::Parse {
+            line,
+            column,
+            message: message.into(),
+        }
+    }
+
+    /// A parse error pointing at `token`, which must be a slice of `line`
+    pub fn at_token(line_no: usize, line: &str, token: &str, message: impl Into<String>) -> Self {
+        Self::parse(line_no, column_of(line, token), message)
+    }
+
+    /// Moves an error found by parsing one line on its own (as line 1) onto line `line` of the
+    /// whole input
+    pub fn on_line(self, line: usize) -> Self {
+        match self {
+            Self::Parse {
+                column, message, ..
+            } => Self::Parse {
+                line,
+                column,
+                message,
+            },
+            other => other,
+        }
+    }
+}
+
+/// The 1-indexed column where `token` starts in `line`.
+///
+/// # Panics
+///
+/// Panics if `token` isn't a slice of `line`, like the pieces `split_whitespace` hands out.
+pub fn column_of(line: &str, token: &str) -> usize {
+    let offset = (token.as_ptr() as usize)
+        .checked_sub(line.as_ptr() as usize)
+        .filter(|&offset| offset <= line.len())
+        .expect("token must be a slice of line");
+    line[..offset].chars().count() + 1
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_column_of() {
+        let line = "forward  12 x";
+        let tokens: Vec<&str> = line.split_whitespace().collect();
+        assert_eq!(column_of(line, tokens[0]), 1);
+        assert_eq!(column_of(line, tokens[1]), 10);
+        assert_eq!(column_of(line, tokens[2]), 13);
+        assert_eq!(column_of(line, &line[line.len()..]), line.len() + 1);
+    }
+
+    #[test]
+    fn test_display() {
+        let line = "a -> bc";
+        let err = AocError::at_token(3, line, &line[5..], "expected one character");
+        assert_eq!(err.to_string(), "line 3, column 6: expected one character");
+        assert_eq!(
+            AocError::parse(1, 2, "oops").on_line(7),
+            AocError::parse(7, 2, "oops")
+        );
+        assert_eq!(
+            AocError::UnexpectedEnd(String::from("a board")).to_string(),
+            "unexpected end of input, expected a board"
+        );
+    }
+}
@@ -0,0 +1,59 @@
+use std::{
+    fs,
+    io::{self, Read},
+    path::PathBuf,
+};
+
+/// Where a day's puzzle input is read from
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum InputSource {
+    /// The `input.txt` compiled into the binary
+    #[default]
+    Bundled,
+    File(PathBuf),
+    Stdin,
+}
+
+impl InputSource {
+    /// Treats `-` as stdin and anything else as a file path
+    pub fn from_path(path: impl Into<PathBuf>) -> Self {
+        let path = path.into();
+        if path.as_os_str() == "-" {
+            Self::Stdin
+        } else {
+            Self::File(path)
+        }
+    }
+
+    /// Reads the whole input, returning `bundled` for [`InputSource::Bundled`]
+    pub fn read(&self, bundled: &str) -> io::Result<String> {
+        match self {
+            Self::Bundled => Ok(bundled.to_string()),
+            Self::File(path) => fs::read_to_string(path),
+            Self::Stdin => {
+                let mut input = String::new();
+                io::stdin().read_to_string(&mut input)?;
+                Ok(input)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_from_path() {
+        assert_eq!(InputSource::from_path("-"), InputSource::Stdin);
+        assert_eq!(
+            InputSource::from_path("mine.txt"),
+            InputSource::File(PathBuf::from("mine.txt"))
+        );
+    }
+
+    #[test]
+    fn test_read_bundled() {
+        assert_eq!(InputSource::Bundled.read("1\n2\n").unwrap(), "1\n2\n");
+    }
+}
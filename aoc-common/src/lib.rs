@@ -0,0 +1,58 @@
+use std::collections::VecDeque;
+
+pub mod answer;
+pub mod args;
+pub mod error;
+pub mod input;
+pub mod logging;
+pub mod progress;
+pub mod registry;
+#[cfg(feature = "proptest")]
+pub mod strategies;
+pub mod timing;
+
+/// Counts how many values are larger than the value `lag` places before them.
+///
+/// This is the same as counting how often the sum of a sliding window of width `lag` increases:
+/// neighbouring windows `a[i..i + lag]` and `a[i + 1..i + lag + 1]` share every value except
+/// `a[i]` and `a[i + lag]`, so comparing their sums is the same as comparing those two values.
+/// Only the last `lag` values are kept around, so this runs in constant memory and never has to
+/// compute (or overflow) a sum.
+///
+/// # Panics
+///
+/// Panics if `lag` is 0.
+pub fn increases_with_lag<I, T>(iter: I, lag: usize) -> usize
+where
+    I: IntoIterator<Item = T>,
+    T: PartialOrd,
+{
+    assert!(lag > 0, "lag must be at least 1");
+    let mut buffer = VecDeque::with_capacity(lag);
+    let mut count = 0;
+    for value in iter {
+        if buffer.len() == lag {
+            let oldest = buffer.pop_front().expect("buffer is full");
+            if value > oldest {
+                count += 1;
+            }
+        }
+        buffer.push_back(value);
+    }
+    count
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_increases_with_lag() {
+        let values = [199, 200, 208, 210, 200, 207, 240, 269, 260, 263];
+        assert_eq!(increases_with_lag(values, 1), 7);
+        assert_eq!(increases_with_lag(values, 3), 5);
+        assert_eq!(increases_with_lag(values, 10), 0);
+        assert_eq!(increases_with_lag([1.0, 0.5, 2.0], 2), 1);
+        assert_eq!(increases_with_lag(Vec::<i32>::new(), 1), 0);
+    }
+}
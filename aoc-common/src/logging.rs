@@ -0,0 +1,46 @@
+//! Diagnostics on stderr through `tracing`, so they never mix with the answers on stdout
+
+use std::io::{self, IsTerminal};
+
+use tracing::Level;
+use tracing_subscriber::fmt::format::FmtSpan;
+
+/// The most detailed events shown at a given number of `-v` flags: warnings only by default,
+/// `-v` for parse diagnostics, totals and how long each phase took, `-vv` for every step
+pub fn level(verbosity: u8) -> Level {
+    match verbosity {
+        0 => Level::WARN,
+        1 => Level::DEBUG,
+        _ => Level::TRACE,
+    }
+}
+
+/// Installs a subscriber that prints events at `verbosity` and up to stderr. Does nothing if
+/// one is already installed.
+pub fn init(verbosity: u8) {
+    let span_events = if verbosity > 0 {
+        FmtSpan::CLOSE
+    } else {
+        FmtSpan::NONE
+    };
+    let _ = tracing_subscriber::fmt()
+        .with_max_level(level(verbosity))
+        .with_span_events(span_events)
+        .with_target(false)
+        .with_ansi(io::stderr().is_terminal())
+        .with_writer(io::stderr)
+        .try_init();
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_level() {
+        assert_eq!(level(0), Level::WARN);
+        assert_eq!(level(1), Level::DEBUG);
+        assert_eq!(level(2), Level::TRACE);
+        assert_eq!(level(5), Level::TRACE);
+    }
+}
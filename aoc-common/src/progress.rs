@@ -0,0 +1,113 @@
+//! Lets slow solvers say how far along they are, for a runner to show
+//!
+//! A solver calls [`start`] and [`advance`] as it goes. They go to whatever [`Progress`] the
+//! caller installed with [`with_progress`] on the same thread, or nowhere if it didn't, so
+//! solvers keep their signatures and cost next to nothing when nobody's watching.
+
+use std::{cell::RefCell, sync::mpsc::Sender};
+
+/// Somewhere to report progress to
+pub trait Progress {
+    /// Work is starting, with `total` steps if the solver knows how many there'll be
+    fn start(&self, total: Option<u64>);
+
+    /// `steps` more steps are done
+    fn advance(&self, steps: u64);
+}
+
+/// What a solver reported, for sending down a channel
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Event {
+    Start(Option<u64>),
+    Advance(u64),
+}
+
+/// Sends every report down the channel, ignoring a receiver that's gone away
+impl Progress for Sender<Event> {
+    fn start(&self, total: Option<u64>) {
+        let _ = self.send(Event::Start(total));
+    }
+
+    fn advance(&self, steps: u64) {
+        let _ = self.send(Event::Advance(steps));
+    }
+}
+
+thread_local! {
+    static CURRENT: RefCell<Option<Box<dyn Progress>>> = const { RefCell::new(None) };
+}
+
+/// Runs `f` with its progress reported to `progress`, then puts back whatever was installed
+/// before
+pub fn with_progress<T>(progress: impl Progress + 'static, f: impl FnOnce() -> T) -> T {
+    /// Puts the previous hook back even if `f` panics
+    struct Restore(Option<Box<dyn Progress>>);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            let previous = self.0.take();
+            CURRENT.with(|current| *current.borrow_mut() = previous);
+        }
+    }
+
+    let previous = CURRENT.with(|current| current.replace(Some(Box::new(progress))));
+    let _restore = Restore(previous);
+    f()
+}
+
+fn report(f: impl FnOnce(&dyn Progress)) {
+    CURRENT.with(|current| {
+        if let Some(progress) = current.borrow().as_deref() {
+            f(progress)
+        }
+    })
+}
+
+/// Work is starting, with `total` steps if the solver knows how many there'll be
+pub fn start(total: Option<u64>) {
+    report(|progress| progress.start(total))
+}
+
+/// `steps` more steps are done
+pub fn advance(steps: u64) {
+    report(|progress| progress.advance(steps))
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::mpsc;
+
+    use super::*;
+
+    #[test]
+    fn test_reports_to_installed_hook() {
+        let (outer, outer_events) = mpsc::channel();
+        let (inner, inner_events) = mpsc::channel();
+        advance(1);
+        with_progress(outer, || {
+            start(Some(3));
+            with_progress(inner, || advance(2));
+            advance(1);
+        });
+        advance(1);
+        assert_eq!(
+            outer_events.try_iter().collect::<Vec<_>>(),
+            vec![Event::Start(Some(3)), Event::Advance(1)]
+        );
+        assert_eq!(
+            inner_events.try_iter().collect::<Vec<_>>(),
+            vec![Event::Advance(2)]
+        );
+    }
+
+    #[test]
+    fn test_restores_after_panic() {
+        let (sender, events) = mpsc::channel();
+        let result = std::panic::catch_unwind(|| {
+            with_progress(sender, || panic!("solver failed"));
+        });
+        assert!(result.is_err());
+        advance(1);
+        assert_eq!(events.try_iter().count(), 0);
+    }
+}
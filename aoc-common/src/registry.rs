@@ -0,0 +1,89 @@
+//! Every day's solvers, collected at startup from the day crates that are linked in
+//!
+//! A day registers itself by calling [`register_day!`](crate::register_day) next to its
+//! `INPUT`, `part1` and `part2`, so a runner only has to depend on the day crate (and name it
+//! with `use dayN as _;`) to be able to solve it.
+
+#[doc(hidden)]
+pub use inventory;
+
+use crate::answer::Answer;
+
+/// Solves one part of a puzzle from the text of its input
+pub type Solve = fn(&str) -> Result<Answer, String>;
+
+/// A day's bundled puzzle input and its two solvers
+#[derive(Debug)]
+pub struct Solver {
+    pub day: u8,
+    pub input: &'static str,
+    pub parts: [Solve; 2],
+}
+
+inventory::collect!(Solver);
+
+/// Registers the calling crate's `INPUT`, `part1` and `part2` as the solution to `day`
+///
+/// ```ignore
+/// aoc_common::register_day!(16);
+/// ```
+#[macro_export]
+macro_rules! register_day {
+    ($day:literal) => {
+        $crate::registry::inventory::submit! {
+            $crate::registry::Solver {
+                day: $day,
+                input: INPUT,
+                parts: [part1, part2],
+            }
+        }
+    };
+}
+
+/// Every registered day, in order
+///
+/// # Panics
+///
+/// Panics if two crates registered the same day.
+pub fn solvers() -> Vec<&'static Solver> {
+    let mut solvers: Vec<&'static Solver> = inventory::iter::<Solver>.into_iter().collect();
+    solvers.sort_by_key(|solver| solver.day);
+    if let Some(pair) = solvers.windows(2).find(|pair| pair[0].day == pair[1].day) {
+        panic!("day {} is registered more than once", pair[0].day);
+    }
+    solvers
+}
+
+/// The solver registered for `day`, if there is one
+pub fn solver(day: u8) -> Option<&'static Solver> {
+    inventory::iter::<Solver>
+        .into_iter()
+        .find(|solver| solver.day == day)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const INPUT: &str = "1\n2\n3";
+
+    fn part1(input: &str) -> Result<Answer, String> {
+        Ok(input.lines().count().into())
+    }
+
+    fn part2(input: &str) -> Result<Answer, String> {
+        Err(format!("can't solve {:?}", input))
+    }
+
+    crate::register_day!(25);
+
+    #[test]
+    fn test_registered() {
+        let day25 = solver(25).expect("day 25 is registered above");
+        assert_eq!(day25.input, INPUT);
+        assert_eq!((day25.parts[0])(INPUT), Ok(Answer::Num(3)));
+        assert!((day25.parts[1])(INPUT).is_err());
+        assert!(solver(24).is_none());
+        assert_eq!(solvers().last().map(|solver| solver.day), Some(25));
+    }
+}
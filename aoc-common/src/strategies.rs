@@ -0,0 +1,60 @@
+//! Proptest strategies for the shapes that turn up in more than one day's input
+
+use std::fmt::{Debug, Display};
+
+use proptest::prelude::*;
+
+/// Fewer cases than proptest's default, since the whole workspace's tests run in debug builds
+pub fn config() -> ProptestConfig {
+    ProptestConfig {
+        cases: 128,
+        ..ProptestConfig::default()
+    }
+}
+
+/// `(x, y)` with both coordinates up to `max`
+pub fn point(max: u64) -> impl Strategy<Value = (u64, u64)> {
+    (0..=max, 0..=max)
+}
+
+/// Trees a few levels deep, built from `leaf`s by `branch`, which gets a strategy for the
+/// children of each node
+pub fn tree<T, L, B, S>(leaf: L, branch: B) -> impl Strategy<Value = T>
+where
+    T: Debug + Clone + 'static,
+    L: Strategy<Value = T> + 'static,
+    B: Fn(BoxedStrategy<T>) -> S + 'static,
+    S: Strategy<Value = T> + 'static,
+{
+    leaf.prop_recursive(4, 32, 3, branch)
+}
+
+/// Some values and the input they'd appear in, one per line
+pub fn lines_of<T>(item: impl Strategy<Value = T>) -> impl Strategy<Value = (Vec<T>, String)>
+where
+    T: Display + Debug,
+{
+    prop::collection::vec(item, 1..20).prop_map(|items| {
+        let text = items
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join("\n");
+        (items, text)
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    proptest! {
+        #![proptest_config(config())]
+
+        #[test]
+        fn lines_of_puts_one_value_per_line((values, text) in lines_of(0..100u8)) {
+            let parsed: Vec<u8> = text.lines().map(|line| line.parse().unwrap()).collect();
+            prop_assert_eq!(parsed, values);
+        }
+    }
+}
@@ -0,0 +1,186 @@
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    fmt::Display,
+    sync::atomic::{AtomicBool, AtomicUsize, Ordering},
+    time::{Duration, Instant},
+};
+
+static INSTALLED: AtomicBool = AtomicBool::new(false);
+static CURRENT: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+/// The system allocator, plus bookkeeping of how many bytes are live and the most there have
+/// been. Install it in a binary with
+///
+/// ```ignore
+/// #[global_allocator]
+/// static ALLOC: aoc_common::timing::PeakAlloc = aoc_common::timing::PeakAlloc;
+/// ```
+pub struct PeakAlloc;
+
+impl PeakAlloc {
+    fn grow(size: usize) {
+        let current = CURRENT.fetch_add(size, Ordering::Relaxed) + size;
+        PEAK.fetch_max(current, Ordering::Relaxed);
+    }
+
+    fn shrink(size: usize) {
+        CURRENT.fetch_sub(size, Ordering::Relaxed);
+    }
+}
+
+unsafe impl GlobalAlloc for PeakAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        INSTALLED.store(true, Ordering::Relaxed);
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            Self::grow(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        INSTALLED.store(true, Ordering::Relaxed);
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            Self::grow(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        Self::shrink(layout.size());
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            Self::shrink(layout.size());
+            Self::grow(new_size);
+        }
+        new_ptr
+    }
+}
+
+/// How long one phase of a solution took, and how far it pushed the heap past where it started
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Phase {
+    pub name: &'static str,
+    pub elapsed: Duration,
+    /// `None` unless [`PeakAlloc`] is the global allocator
+    pub peak_bytes: Option<usize>,
+}
+
+/// Records [`Phase`]s as a solution runs
+#[derive(Debug, Default)]
+pub struct Timer {
+    enabled: bool,
+    phases: Vec<Phase>,
+}
+
+impl Timer {
+    pub fn new(enabled: bool) -> Self {
+        Self {
+            enabled,
+            phases: Vec::new(),
+        }
+    }
+
+    /// Runs `f` inside a `phase` span, recording it as a phase called `name` if the timer is
+    /// enabled
+    pub fn measure<T>(&mut self, name: &'static str, f: impl FnOnce() -> T) -> T {
+        let _span = tracing::info_span!("phase", name).entered();
+        if !self.enabled {
+            return f();
+        }
+        let baseline = CURRENT.load(Ordering::Relaxed);
+        PEAK.store(baseline, Ordering::Relaxed);
+        let start = Instant::now();
+        let result = f();
+        let elapsed = start.elapsed();
+        let peak_bytes = INSTALLED
+            .load(Ordering::Relaxed)
+            .then(|| PEAK.load(Ordering::Relaxed).saturating_sub(baseline));
+        self.phases.push(Phase {
+            name,
+            elapsed,
+            peak_bytes,
+        });
+        result
+    }
+
+    pub fn phases(&self) -> &[Phase] {
+        &self.phases
+    }
+
+    /// Prints the recorded phases, if the timer is enabled
+    pub fn report(&self) {
+        if self.enabled {
+            print!("{}", self);
+        }
+    }
+}
+
+impl Display for Timer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "{:<8} {:>12} {:>12}", "phase", "time", "peak alloc")?;
+        for phase in &self.phases {
+            let peak = match phase.peak_bytes {
+                Some(bytes) => format_bytes(bytes),
+                None => String::from("-"),
+            };
+            writeln!(
+                f,
+                "{:<8} {:>12} {:>12}",
+                phase.name,
+                format!("{:.3?}", phase.elapsed),
+                peak
+            )?;
+        }
+        Ok(())
+    }
+}
+
+fn format_bytes(bytes: usize) -> String {
+    const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+    let mut size = bytes as f64;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} B", bytes)
+    } else {
+        format!("{:.1} {}", size, UNITS[unit])
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_measure() {
+        let mut timer = Timer::new(true);
+        assert_eq!(timer.measure("part1", || 40 + 2), 42);
+        let phases = timer.phases();
+        assert_eq!(phases.len(), 1);
+        assert_eq!(phases[0].name, "part1");
+        // the test harness doesn't install PeakAlloc
+        assert_eq!(phases[0].peak_bytes, None);
+
+        let mut disabled = Timer::new(false);
+        assert_eq!(disabled.measure("part1", || 7), 7);
+        assert!(disabled.phases().is_empty());
+    }
+
+    #[test]
+    fn test_format_bytes() {
+        assert_eq!(format_bytes(0), "0 B");
+        assert_eq!(format_bytes(1023), "1023 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024), "3.0 MiB");
+    }
+}
@@ -0,0 +1,15 @@
+//! A square cave of risk levels
+
+use rand::Rng;
+
+/// A `size` by `size` grid of digits from 1 to 9
+pub fn input(rng: &mut impl Rng, size: usize) -> String {
+    let mut text = String::with_capacity(size * (size + 1));
+    for _ in 0..size {
+        for _ in 0..size {
+            text.push(char::from(b'0' + rng.random_range(1..=9)));
+        }
+        text.push('\n');
+    }
+    text
+}
@@ -0,0 +1,116 @@
+//! A BITS transmission holding one deeply nested packet
+
+use rand::Rng;
+
+use crate::pick;
+
+const SUM: u64 = 0;
+const PRODUCT: u64 = 1;
+const MINIMUM: u64 = 2;
+const MAXIMUM: u64 = 3;
+const LITERAL: u64 = 4;
+const GREATER_THAN: u64 = 5;
+const LESS_THAN: u64 = 6;
+const EQUAL_TO: u64 = 7;
+
+/// The largest total length a packet can give for its subpackets, in 15 bits
+const MAX_TOTAL_LENGTH: usize = (1 << 15) - 1;
+
+fn push_bits(bits: &mut Vec<bool>, value: u64, count: u32) {
+    bits.extend((0..count).rev().map(|shift| (value >> shift) & 1 == 1));
+}
+
+fn header(rng: &mut impl Rng, type_id: u64) -> Vec<bool> {
+    let mut bits = Vec::new();
+    push_bits(&mut bits, rng.random_range(0..8), 3);
+    push_bits(&mut bits, type_id, 3);
+    bits
+}
+
+pub(crate) fn literal(rng: &mut impl Rng, value: u64) -> Vec<bool> {
+    let mut bits = header(rng, LITERAL);
+    let groups = (64 - value.leading_zeros()).div_ceil(4).max(1);
+    for group in (0..groups).rev() {
+        bits.push(group > 0);
+        push_bits(&mut bits, value >> (group * 4), 4);
+    }
+    bits
+}
+
+fn operator(rng: &mut impl Rng, type_id: u64, subpackets: Vec<Vec<bool>>) -> Vec<bool> {
+    let mut bits = header(rng, type_id);
+    let total_length: usize = subpackets.iter().map(Vec::len).sum();
+    if total_length <= MAX_TOTAL_LENGTH && rng.random_bool(0.5) {
+        bits.push(false);
+        push_bits(&mut bits, total_length as u64, 15);
+    } else {
+        bits.push(true);
+        push_bits(&mut bits, subpackets.len() as u64, 11);
+    }
+    bits.extend(subpackets.into_iter().flatten());
+    bits
+}
+
+/// A packet `depth` operators deep. Only one subpacket of each operator nests any further, so
+/// the transmission grows with the depth rather than exponentially, and products only multiply
+/// a literal by a comparison so the value can't overflow.
+fn packet(rng: &mut impl Rng, depth: usize) -> Vec<bool> {
+    if depth == 0 {
+        let value = rng.random_range(0..1000);
+        return literal(rng, value);
+    }
+    let type_id = pick(
+        rng,
+        &[
+            SUM,
+            PRODUCT,
+            MINIMUM,
+            MAXIMUM,
+            GREATER_THAN,
+            LESS_THAN,
+            EQUAL_TO,
+        ],
+    );
+    let mut subpackets = match type_id {
+        PRODUCT => {
+            let value = rng.random_range(0..1000);
+            let comparison = pick(rng, &[GREATER_THAN, LESS_THAN, EQUAL_TO]);
+            let nested = vec![packet(rng, depth - 1), literal(rng, value)];
+            let factor = rng.random_range(0..1000);
+            vec![operator(rng, comparison, nested), literal(rng, factor)]
+        }
+        GREATER_THAN | LESS_THAN | EQUAL_TO => {
+            let value = rng.random_range(0..1000);
+            vec![packet(rng, depth - 1), literal(rng, value)]
+        }
+        _ => {
+            let mut subpackets = vec![packet(rng, depth - 1)];
+            for _ in 0..rng.random_range(0..3) {
+                let value = rng.random_range(0..1000);
+                subpackets.push(literal(rng, value));
+            }
+            subpackets
+        }
+    };
+    if subpackets.len() > 1 && rng.random_bool(0.5) {
+        subpackets.swap(0, 1);
+    }
+    operator(rng, type_id, subpackets)
+}
+
+/// One packet `depth` operators deep, as hex
+pub fn input(rng: &mut impl Rng, depth: usize) -> String {
+    let mut bits = packet(rng, depth);
+    bits.resize(bits.len().div_ceil(4) * 4, false);
+    let mut hex: String = bits
+        .chunks(4)
+        .map(|nibble| {
+            let value = nibble.iter().fold(0, |acc, &bit| acc << 1 | u32::from(bit));
+            char::from_digit(value, 16)
+                .expect("nibbles are below 16")
+                .to_ascii_uppercase()
+        })
+        .collect();
+    hex.push('\n');
+    hex
+}
@@ -0,0 +1,36 @@
+//! Hydrothermal vent lines on a 1000 by 1000 sea floor, like the real input's
+
+use rand::Rng;
+
+use crate::pick;
+
+const FLOOR: i32 = 1000;
+
+/// `lines` vents, each horizontal, vertical or at 45 degrees
+pub fn input(rng: &mut impl Rng, lines: usize) -> String {
+    let mut text = String::new();
+    for _ in 0..lines {
+        let (x1, y1) = (rng.random_range(0..FLOOR), rng.random_range(0..FLOOR));
+        let length = rng.random_range(1..FLOOR / 2);
+        let (dx, dy) = pick(
+            rng,
+            &[
+                (1, 0),
+                (-1, 0),
+                (0, 1),
+                (0, -1),
+                (1, 1),
+                (1, -1),
+                (-1, 1),
+                (-1, -1),
+            ],
+        );
+        // Shorten the line until it stays on the floor
+        let fits =
+            |n: i32| (0..FLOOR).contains(&(x1 + dx * n)) && (0..FLOOR).contains(&(y1 + dy * n));
+        let length = (0..=length).rev().find(|&n| fits(n)).unwrap_or(0);
+        let (x2, y2) = (x1 + dx * length, y1 + dy * length);
+        text.push_str(&format!("{},{} -> {},{}\n", x1, y1, x2, y2));
+    }
+    text
+}
@@ -0,0 +1,118 @@
+//! Large random puzzle inputs that are still valid, for benchmarking and scalability testing
+//!
+//! Every generator takes a seeded [`Rng`] so the same seed always gives the same input.
+
+pub mod day15;
+pub mod day16;
+pub mod day5;
+
+use rand::Rng;
+
+/// A generator for one day's input, and how big to make it when nothing else is asked for
+#[derive(Debug, Clone, Copy)]
+pub struct Generator {
+    pub day: u8,
+    /// What `size` counts, for `--help`
+    pub size_means: &'static str,
+    pub default_size: usize,
+    pub generate: fn(&mut dyn rand::RngCore, usize) -> String,
+}
+
+pub const GENERATORS: [Generator; 3] = [
+    Generator {
+        day: 5,
+        size_means: "vent lines",
+        default_size: 10_000,
+        generate: |mut rng, size| day5::input(&mut rng, size),
+    },
+    Generator {
+        day: 15,
+        size_means: "width and height of the cave",
+        // Part 2 makes the cave 25 times bigger, so 1000 is a real stress test
+        default_size: 200,
+        generate: |mut rng, size| day15::input(&mut rng, size),
+    },
+    Generator {
+        day: 16,
+        size_means: "depth of the outermost packet",
+        default_size: 200,
+        generate: |mut rng, size| day16::input(&mut rng, size),
+    },
+];
+
+/// The generator for `day`, if there is one
+pub fn generator(day: u8) -> Option<&'static Generator> {
+    GENERATORS.iter().find(|generator| generator.day == day)
+}
+
+/// Picks one of `choices` evenly
+fn pick<T: Copy>(rng: &mut impl Rng, choices: &[T]) -> T {
+    choices[rng.random_range(0..choices.len())]
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::*;
+
+    fn generate(day: u8, size: usize, seed: u64) -> String {
+        let generator = generator(day).expect("every day in these tests has a generator");
+        (generator.generate)(&mut StdRng::seed_from_u64(seed), size)
+    }
+
+    #[test]
+    fn same_seed_same_input() {
+        for generator in &GENERATORS {
+            let day = generator.day;
+            assert_eq!(generate(day, 20, 7), generate(day, 20, 7));
+            assert_ne!(generate(day, 20, 7), generate(day, 20, 8));
+        }
+        assert!(super::generator(1).is_none());
+    }
+
+    #[test]
+    fn generated_inputs_solve() {
+        for seed in 0..20 {
+            let vents = generate(5, 300, seed);
+            assert_eq!(vents.lines().count(), 300);
+            assert!(::day5::part1(&vents).is_ok());
+            assert!(::day5::part2(&vents).is_ok());
+
+            let cave = generate(15, 12, seed);
+            assert_eq!(cave.lines().count(), 12);
+            assert!(cave.lines().all(|line| line.len() == 12));
+            assert!(::day15::part1(&cave).is_ok());
+            assert!(::day15::part2(&cave).is_ok());
+
+            let transmission = generate(16, 60, seed);
+            assert!(::day16::part1(&transmission).is_ok(), "{}", transmission);
+            assert!(::day16::part2(&transmission).is_ok(), "{}", transmission);
+        }
+    }
+
+    #[test]
+    fn vents_stay_on_the_floor() {
+        let vents = generate(5, 2000, 1);
+        for line in vents.lines() {
+            let (start, end) = line.split_once(" -> ").unwrap();
+            for point in [start, end] {
+                let (x, y) = point.split_once(',').unwrap();
+                for coordinate in [x, y] {
+                    assert!((0..1000).contains(&coordinate.parse::<i32>().unwrap()));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn literal_groups() {
+        // 2021 is 0111 1110 0101 in three groups, as in the puzzle's example
+        let bits = day16::literal(&mut StdRng::seed_from_u64(0), 2021);
+        let groups: String = bits[6..]
+            .iter()
+            .map(|&bit| if bit { '1' } else { '0' })
+            .collect();
+        assert_eq!(groups, "101111111000101");
+    }
+}
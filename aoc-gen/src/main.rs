@@ -0,0 +1,55 @@
+use std::process::ExitCode;
+
+use aoc_gen::{generator, GENERATORS};
+use clap::Parser;
+use rand::{rngs::StdRng, SeedableRng};
+
+/// Writes a large random puzzle input to stdout
+#[derive(Parser)]
+#[command(after_help = sizes())]
+struct Cli {
+    /// The day to make an input for
+    day: u8,
+    /// How big to make it, which means something different for each day
+    #[arg(long)]
+    size: Option<usize>,
+    /// The same seed always makes the same input
+    #[arg(long, default_value_t = 2021)]
+    seed: u64,
+}
+
+/// What `--size` means for each day
+fn sizes() -> String {
+    let mut help = String::from("Sizes:\n");
+    for generator in &GENERATORS {
+        help.push_str(&format!(
+            "  day{:<3} {} (default {})\n",
+            generator.day, generator.size_means, generator.default_size
+        ));
+    }
+    help
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let Some(generator) = generator(cli.day) else {
+        eprintln!("there's no generator for day {}\n\n{}", cli.day, sizes());
+        return ExitCode::FAILURE;
+    };
+    let mut rng = StdRng::seed_from_u64(cli.seed);
+    let size = cli.size.unwrap_or(generator.default_size);
+    print!("{}", (generator.generate)(&mut rng, size));
+    ExitCode::SUCCESS
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::CommandFactory;
+
+    use super::*;
+
+    #[test]
+    fn cli_parses() {
+        Cli::command().debug_assert();
+    }
+}
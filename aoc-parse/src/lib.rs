@@ -0,0 +1,362 @@
+//! nom combinators for the shapes puzzle inputs come in: numbers, `x,y` coordinates,
+//! comma-separated lists, labeled values like `x=20`, lines, and blocks separated by blank
+//! lines.
+//!
+//! Parse a whole input with [`finish`], which turns a failure into an [`AocError`] pointing at
+//! the line and column where the input stopped making sense, and what was expected there:
+//!
+//! ```
+//! use aoc_parse::{coordinate, finish, lines};
+//!
+//! let points: Vec<(u32, u32)> = finish("6,10\n0,14\n", lines(coordinate)).unwrap();
+//! assert_eq!(points, vec![(6, 10), (0, 14)]);
+//!
+//! let err = finish::<Vec<(u32, u32)>>("6,10\n0;14", lines(coordinate)).unwrap_err();
+//! assert_eq!(err.to_string(), "line 2, column 2: expected `,`, found `;`");
+//! ```
+
+use std::{borrow::Cow, fmt::Display, str::FromStr};
+
+use aoc_common::error::AocError;
+use nom::{
+    character::complete::{char, digit1, line_ending},
+    combinator::{eof, map_res, not, opt, peek, recognize},
+    error::{ContextError, ErrorKind, FromExternalError, ParseError},
+    multi::many1_count,
+    sequence::{pair, terminated},
+    Parser,
+};
+
+pub use nom;
+
+/// What the parsers here return
+pub type IResult<'a, T> = nom::IResult<&'a str, T, Error<'a>>;
+
+/// Where parsing stopped, and what it was hoping to find there
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Error<'a> {
+    /// The rest of the input from where parsing stopped
+    at: &'a str,
+    expected: Cow<'static, str>,
+    /// Whether [`expected`] or [`literal`] named what was wanted, rather than it being a default
+    named: bool,
+    /// Why something that looked right still didn't parse, like a number being too big
+    problem: Option<String>,
+}
+
+impl<'a> Error<'a> {
+    fn new(at: &'a str, expected: impl Into<Cow<'static, str>>) -> Self {
+        Self {
+            at,
+            expected: expected.into(),
+            named: false,
+            problem: None,
+        }
+    }
+
+    /// Points at the line and column of `input` where this went wrong
+    fn into_aoc(self, input: &str) -> AocError {
+        let at = self.at.trim_start_matches([' ', '\t']);
+        if at.is_empty() {
+            return AocError::UnexpectedEnd(self.expected.into_owned());
+        }
+        let before = &input[..input.len() - at.len()];
+        let line = before.matches('\n').count() + 1;
+        let column = before.rsplit('\n').next().unwrap_or("").chars().count() + 1;
+        let found = if at.starts_with(['\r', '\n']) {
+            String::from("the end of the line")
+        } else {
+            format!("`{}`", token(at))
+        };
+        let mut message = format!("expected {}, found {}", self.expected, found);
+        if let Some(problem) = self.problem {
+            message.push_str(&format!(" ({})", problem));
+        }
+        AocError::parse(line, column, message)
+    }
+}
+
+/// The word or number at the start of `at`, or just its first character if it's punctuation
+fn token(at: &str) -> &str {
+    let end = at
+        .char_indices()
+        .find(|&(idx, ch)| !(ch.is_alphanumeric() || (idx == 0 && ch == '-')))
+        .map_or(at.len(), |(idx, _)| idx);
+    match end {
+        0 => &at[..at.chars().next().map_or(0, char::len_utf8)],
+        end => &at[..end],
+    }
+}
+
+impl<'a> ParseError<&'a str> for Error<'a> {
+    fn from_error_kind(input: &'a str, kind: ErrorKind) -> Self {
+        let expected: Cow<'static, str> = match kind {
+            ErrorKind::Digit => "a number".into(),
+            ErrorKind::CrLf => "a line break".into(),
+            ErrorKind::Eof => "the end of the input".into(),
+            kind => kind.description().to_lowercase().into(),
+        };
+        Self::new(input, expected)
+    }
+
+    fn append(_: &'a str, _: ErrorKind, other: Self) -> Self {
+        other
+    }
+
+    /// Of two alternatives that failed, reports the one that got further
+    fn or(self, other: Self) -> Self {
+        if other.at.len() < self.at.len() {
+            other
+        } else {
+            self
+        }
+    }
+}
+
+/// An outer name replaces an inner one only if nothing more specific was named, or if the
+/// outer thing couldn't even start; `a drawn number` says more than `a number` at the same
+/// spot, but less than whatever a board row was missing halfway along
+impl<'a> ContextError<&'a str> for Error<'a> {
+    fn add_context(input: &'a str, context: &'static str, mut other: Self) -> Self {
+        if !other.named || other.at == input {
+            other.expected = context.into();
+            other.named = true;
+        }
+        other
+    }
+}
+
+impl<'a, E: Display> FromExternalError<&'a str, E> for Error<'a> {
+    fn from_external_error(input: &'a str, kind: ErrorKind, e: E) -> Self {
+        Self {
+            problem: Some(e.to_string()),
+            ..Self::from_error_kind(input, kind)
+        }
+    }
+}
+
+/// Turns a recoverable error into one that stops alternatives from being tried
+fn failure(e: nom::Err<Error<'_>>) -> nom::Err<Error<'_>> {
+    match e {
+        nom::Err::Error(e) => nom::Err::Failure(e),
+        e => e,
+    }
+}
+
+/// Parses all of `input` with `parser`, ignoring whitespace at the end
+pub fn finish<'a, T>(
+    input: &'a str,
+    mut parser: impl Parser<&'a str, T, Error<'a>>,
+) -> Result<T, AocError> {
+    let input = input.trim_end();
+    match parser.parse(input) {
+        Ok(("", value)) => Ok(value),
+        Ok((rest, _)) => Err(Error::new(rest, "the end of the input").into_aoc(input)),
+        Err(nom::Err::Error(e) | nom::Err::Failure(e)) => Err(e.into_aoc(input)),
+        Err(nom::Err::Incomplete(_)) => unreachable!("complete parsers never ask for more input"),
+    }
+}
+
+/// Names what `parser` was looking for, for the error if it fails
+pub fn expected<'a, T>(
+    what: &'static str,
+    parser: impl Parser<&'a str, T, Error<'a>>,
+) -> impl FnMut(&'a str) -> IResult<'a, T> {
+    nom::error::context(what, parser)
+}
+
+/// Exactly `text`
+pub fn literal<'a>(text: &'static str) -> impl Fn(&'a str) -> IResult<'a, &'a str> {
+    move |input: &'a str| match input.strip_prefix(text) {
+        Some(rest) => Ok((rest, &input[..text.len()])),
+        None => Err(nom::Err::Error(Error {
+            named: true,
+            ..Error::new(input, format!("`{}`", text))
+        })),
+    }
+}
+
+/// A decimal number, with a `-` in front if it's negative
+pub fn number<'a, T>(input: &'a str) -> IResult<'a, T>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    expected(
+        "a number",
+        map_res(recognize(pair(opt(char('-')), digit1)), str::parse),
+    )(input)
+}
+
+/// One or more of `item` with `separator` between them. Once a separator's been seen, an item
+/// has to follow, so a bad item is reported rather than quietly ending the list before it.
+pub fn separated<'a, T, S>(
+    mut separator: impl Parser<&'a str, S, Error<'a>>,
+    mut item: impl Parser<&'a str, T, Error<'a>>,
+) -> impl FnMut(&'a str) -> IResult<'a, Vec<T>> {
+    move |input| {
+        let (mut input, first) = item.parse(input)?;
+        let mut items = vec![first];
+        loop {
+            match separator.parse(input) {
+                Ok((rest, _)) => {
+                    let (rest, next) = item.parse(rest).map_err(failure)?;
+                    items.push(next);
+                    input = rest;
+                }
+                Err(nom::Err::Error(_)) => return Ok((input, items)),
+                Err(e) => return Err(e),
+            }
+        }
+    }
+}
+
+/// Items separated by commas, like `7,4,9,5`
+pub fn comma_separated<'a, T>(
+    item: impl Parser<&'a str, T, Error<'a>>,
+) -> impl FnMut(&'a str) -> IResult<'a, Vec<T>> {
+    separated(char(','), item)
+}
+
+/// An `x,y` pair of numbers
+pub fn coordinate<'a, T>(input: &'a str) -> IResult<'a, (T, T)>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    let (input, x) = number(input)?;
+    let (input, _) = literal(",")(input)?;
+    let (input, y) = number(input).map_err(failure)?;
+    Ok((input, (x, y)))
+}
+
+/// `value` after `label`, like the `20` in `x=20`. Once the label matches, the value has to.
+pub fn labeled<'a, T>(
+    label: &'static str,
+    mut value: impl Parser<&'a str, T, Error<'a>>,
+) -> impl FnMut(&'a str) -> IResult<'a, T> {
+    move |input| {
+        let (input, _) = literal(label)(input)?;
+        value.parse(input).map_err(failure)
+    }
+}
+
+/// The end of a line, without using it up
+fn end_of_line(input: &str) -> IResult<'_, ()> {
+    let (input, _) = expected("the end of the line", peek(line_ending.or(eof)))(input)?;
+    Ok((input, ()))
+}
+
+/// A line break that carries on to another line of the same block
+fn line_break(input: &str) -> IResult<'_, &str> {
+    terminated(line_ending, not(line_ending))(input)
+}
+
+/// One `item` per line, each taking up the whole line. Stops at a blank line, so the lines can
+/// make up one of several [`blocks`].
+pub fn lines<'a, T>(
+    item: impl Parser<&'a str, T, Error<'a>>,
+) -> impl FnMut(&'a str) -> IResult<'a, Vec<T>> {
+    separated(line_break, terminated(item, end_of_line))
+}
+
+/// The break between two blocks: the end of one line, then at least one empty line. Wrapping
+/// it in [`expected`] says what the blank line is for.
+pub fn blank_line(input: &str) -> IResult<'_, &str> {
+    recognize(pair(line_ending, many1_count(line_ending)))(input)
+        .map_err(|e| e.map(|e: Error| Error::new(e.at, "a blank line")))
+}
+
+/// One or more of `block`, separated by blank lines
+pub fn blocks<'a, T>(
+    block: impl Parser<&'a str, T, Error<'a>>,
+) -> impl FnMut(&'a str) -> IResult<'a, Vec<T>> {
+    separated(blank_line, block)
+}
+
+#[cfg(test)]
+mod test {
+    use nom::sequence::separated_pair;
+
+    use super::*;
+
+    #[test]
+    fn test_numbers() {
+        assert_eq!(finish("-12", number::<i32>), Ok(-12));
+        assert_eq!(
+            finish("7,4,9\n", comma_separated(number::<u8>)),
+            Ok(vec![7, 4, 9])
+        );
+        assert_eq!(
+            finish(
+                "7,x,9",
+                comma_separated(expected("a drawn number", number::<u8>))
+            ),
+            Err(AocError::parse(1, 3, "expected a drawn number, found `x`"))
+        );
+        assert_eq!(
+            finish("7,300", comma_separated(number::<u8>)),
+            Err(AocError::parse(
+                1,
+                3,
+                "expected a number, found `300` (number too large to fit in target type)"
+            ))
+        );
+        assert_eq!(
+            finish("7,", comma_separated(number::<u8>)),
+            Err(AocError::UnexpectedEnd(String::from("a number")))
+        );
+    }
+
+    #[test]
+    fn test_labeled() {
+        let range = |label| labeled(label, separated_pair(number, literal(".."), number));
+        let mut area = separated_pair(range("x="), literal(", "), range("y="));
+        assert_eq!(area("x=20..30, y=-10..-5"), Ok(("", ((20, 30), (-10, -5)))));
+        assert_eq!(
+            finish("x=20..30, y=-10.-5", area),
+            Err(AocError::parse(1, 16, "expected `..`, found `.`"))
+        );
+    }
+
+    #[test]
+    fn test_lines_and_blocks() {
+        let mut sections =
+            separated_pair(lines(coordinate::<u32>), blank_line, lines(number::<u32>));
+        assert_eq!(
+            sections("1,2\n3,4\n\n5\n6"),
+            Ok(("", (vec![(1, 2), (3, 4)], vec![5, 6])))
+        );
+        assert_eq!(
+            finish("1,2 3,4\n\n5", sections),
+            Err(AocError::parse(
+                1,
+                5,
+                "expected the end of the line, found `3`"
+            ))
+        );
+        assert_eq!(
+            finish("1\n2\n\n\n3\n", blocks(lines(number::<u32>))),
+            Ok(vec![vec![1, 2], vec![3]])
+        );
+        assert_eq!(
+            finish("1\n2\nthree", blocks(lines(number::<u32>))),
+            Err(AocError::parse(3, 1, "expected a number, found `three`"))
+        );
+        assert_eq!(
+            finish(
+                "1\n2\n3",
+                separated_pair(number::<u32>, blank_line, number::<u32>)
+            ),
+            Err(AocError::parse(2, 1, "expected a blank line, found `2`"))
+        );
+        assert_eq!(
+            finish("1\n\n2\n", number::<u32>),
+            Err(AocError::parse(
+                1,
+                2,
+                "expected the end of the input, found the end of the line"
+            ))
+        );
+    }
+}
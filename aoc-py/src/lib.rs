@@ -0,0 +1,260 @@
+//! The solvers as a Python module, so the parsers can be picked up from a notebook. Build it
+//! into the current virtualenv with
+//!
+//! ```text
+//! maturin develop -m aoc-py/Cargo.toml
+//! ```
+//!
+//! then `import aoc2021`.
+
+use aoc_common::registry;
+use day16::{MessageType, Packet, Transmission};
+use day18::SnailFish;
+use pyo3::{exceptions::PyValueError, prelude::*};
+
+// Naming the days links them in, which registers their solvers
+use day1 as _;
+use day10 as _;
+use day11 as _;
+use day12 as _;
+use day13 as _;
+use day14 as _;
+use day15 as _;
+use day17 as _;
+use day19 as _;
+use day2 as _;
+use day3 as _;
+use day4 as _;
+use day5 as _;
+use day6 as _;
+use day7 as _;
+use day8 as _;
+use day9 as _;
+
+/// The days there are solutions for, in order
+#[pyfunction]
+fn days() -> Vec<u32> {
+    // Widened so Python gets a list of ints rather than `bytes`
+    registry::solvers()
+        .iter()
+        .map(|solver| u32::from(solver.day))
+        .collect()
+}
+
+/// Solves one part of a day, raising `ValueError` if the day doesn't exist or the input is bad
+#[pyfunction]
+fn solve(day: u8, part: u8, input: &str) -> PyResult<String> {
+    let solver = registry::solver(day)
+        .ok_or_else(|| PyValueError::new_err(format!("there's no solution for day {}", day)))?;
+    let solve = match part {
+        1 | 2 => solver.parts[usize::from(part) - 1],
+        _ => {
+            return Err(PyValueError::new_err(format!(
+                "day {} has no part {}",
+                day, part
+            )))
+        }
+    };
+    solve(input)
+        .map(|answer| answer.to_string())
+        .map_err(PyValueError::new_err)
+}
+
+/// The bundled puzzle input for a day
+#[pyfunction]
+fn puzzle_input(day: u8) -> PyResult<&'static str> {
+    registry::solver(day)
+        .map(|solver| solver.input)
+        .ok_or_else(|| PyValueError::new_err(format!("there's no input for day {}", day)))
+}
+
+/// A day 16 BITS packet
+#[pyclass(name = "Packet", module = "aoc2021", frozen, eq)]
+#[derive(Clone, PartialEq)]
+struct PyPacket(Packet);
+
+#[pymethods]
+impl PyPacket {
+    /// Decodes a hex transmission like `"8A004A801A8002F478"`
+    #[staticmethod]
+    fn from_hex(hex: &str) -> PyResult<Self> {
+        let transmission =
+            Transmission::from_hex_str(hex).map_err(|e| PyValueError::new_err(e.to_string()))?;
+        match transmission.packets().next() {
+            Some(Ok(packet)) => Ok(Self(packet)),
+            Some(Err(e)) => Err(PyValueError::new_err(e.to_string())),
+            None => Err(PyValueError::new_err("no packets in the transmission")),
+        }
+    }
+
+    /// Decodes a transmission from its raw bytes, e.g. `b"\xd2\xfe\x28"`
+    #[staticmethod]
+    fn from_bytes(bytes: &[u8]) -> PyResult<Self> {
+        Packet::from_bytes(bytes)
+            .map(Self)
+            .ok_or_else(|| PyValueError::new_err("not the bytes of a whole packet"))
+    }
+
+    #[getter]
+    fn version(&self) -> u8 {
+        self.0.version.into()
+    }
+
+    /// The number a literal packet holds, or `None` for an operator
+    #[getter]
+    fn literal(&self) -> Option<u128> {
+        match self.0.message_type {
+            MessageType::Literal(value) => Some(value),
+            MessageType::Operator(..) => None,
+        }
+    }
+
+    /// An operator's symbol, like `"+"` or `"min"`, or `None` for a literal
+    #[getter]
+    fn operator(&self) -> Option<String> {
+        match self.0.message_type {
+            MessageType::Literal(_) => None,
+            MessageType::Operator(_, op_type) => Some(op_type.to_string()),
+        }
+    }
+
+    #[getter]
+    fn subpackets(&self) -> Vec<Self> {
+        self.0.subpackets().cloned().map(Self).collect()
+    }
+
+    /// Evaluates the expression, raising `ValueError` if it can't be
+    fn value(&self) -> PyResult<u64> {
+        self.0
+            .try_value()
+            .map_err(|e| PyValueError::new_err(e.to_string()))
+    }
+
+    fn version_sum(&self) -> u64 {
+        self.0.version_sum()
+    }
+
+    fn __str__(&self) -> String {
+        self.0.to_string()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("<Packet {}>", self.0)
+    }
+}
+
+/// A day 18 snailfish number
+#[pyclass(name = "SnailFish", module = "aoc2021", frozen, eq)]
+#[derive(Clone, PartialEq)]
+struct PySnailFish(SnailFish);
+
+#[pymethods]
+impl PySnailFish {
+    /// Parses a number written like the puzzle input, e.g. `"[[1,2],3]"`
+    #[new]
+    fn new(number: &str) -> PyResult<Self> {
+        number
+            .parse()
+            .map(Self)
+            .map_err(|e: day18::ParseError| PyValueError::new_err(e.to_string()))
+    }
+
+    /// The reduced sum
+    fn __add__(&self, other: &Self) -> Self {
+        Self(self.0.clone() + other.0.clone())
+    }
+
+    fn magnitude(&self) -> u64 {
+        self.0.magnitude()
+    }
+
+    fn __str__(&self) -> String {
+        self.0.to_string()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("SnailFish('{}')", self.0)
+    }
+}
+
+/// Largest magnitude of any sum of two different numbers, or `None` if there are fewer than two
+#[pyfunction]
+fn max_pair_magnitude(numbers: Vec<PySnailFish>) -> Option<u64> {
+    let numbers: Vec<SnailFish> = numbers.into_iter().map(|number| number.0).collect();
+    day18::max_pair_magnitude_pruned(&numbers)
+}
+
+#[pymodule]
+fn aoc2021(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add_function(wrap_pyfunction!(days, module)?)?;
+    module.add_function(wrap_pyfunction!(solve, module)?)?;
+    module.add_function(wrap_pyfunction!(puzzle_input, module)?)?;
+    module.add_function(wrap_pyfunction!(max_pair_magnitude, module)?)?;
+    module.add_class::<PyPacket>()?;
+    module.add_class::<PySnailFish>()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Runs `code` with the module imported as `aoc`
+    fn run(code: &str) {
+        Python::with_gil(|py| {
+            let aoc = PyModule::new(py, "aoc2021").unwrap();
+            aoc2021(&aoc).unwrap();
+            pyo3::py_run!(py, aoc, code);
+        })
+    }
+
+    #[test]
+    fn solves_from_python() {
+        run(r#"
+assert aoc.solve(16, 1, "8A004A801A8002F478") == "16"
+assert aoc.days()[:2] == [1, 2]
+assert len(aoc.puzzle_input(18).splitlines()) == 100
+try:
+    aoc.solve(16, 3, "")
+except ValueError as e:
+    assert "no part 3" in str(e)
+else:
+    raise AssertionError("part 3 should raise")
+"#);
+    }
+
+    #[test]
+    fn decodes_packets() {
+        run(r#"
+packet = aoc.Packet.from_hex("9C0141080250320F1802104A08")
+assert str(packet) == "(= (+ 1 3) (* 2 2))"
+assert packet.value() == 1
+assert packet.operator == "=" and packet.literal is None
+assert [p.operator for p in packet.subpackets] == ["+", "*"]
+assert packet.subpackets[0].subpackets[1].literal == 3
+assert aoc.Packet.from_hex("8A004A801A8002F478").version_sum() == 16
+assert aoc.Packet.from_hex("D2FE28").version == 6
+assert aoc.Packet.from_bytes(bytes.fromhex("D2FE28")).literal == 2021
+try:
+    aoc.Packet.from_hex("zz")
+except ValueError:
+    pass
+else:
+    raise AssertionError("bad hex should raise")
+"#);
+    }
+
+    #[test]
+    fn adds_snailfish() {
+        run(r#"
+a = aoc.SnailFish("[[[[4,3],4],4],[7,[[8,4],9]]]")
+total = a + aoc.SnailFish("[1,1]")
+assert str(total) == "[[[[0,7],4],[[7,8],[6,0]]],[8,1]]"
+assert total == aoc.SnailFish("[[[[0,7],4],[[7,8],[6,0]]],[8,1]]")
+assert repr(aoc.SnailFish("[9,1]")) == "SnailFish('[9,1]')"
+assert aoc.SnailFish("[9,1]").magnitude() == 29
+assert aoc.max_pair_magnitude([aoc.SnailFish("[1,2]"), aoc.SnailFish("[3,4]")]) is not None
+assert aoc.max_pair_magnitude([]) is None
+"#);
+    }
+}
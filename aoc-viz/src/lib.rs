@@ -0,0 +1,195 @@
+//! Animates solutions in the terminal, one [`Frame`] at a time
+
+use std::{
+    fmt::Display,
+    io::{self, Write},
+    time::Duration,
+};
+
+use crossterm::{
+    cursor,
+    event::{self, Event, KeyCode, KeyEvent, KeyModifiers},
+    execute, queue,
+    style::{self, Stylize},
+    terminal,
+};
+use grid2d::Grid;
+
+pub use crossterm::style::Color;
+
+/// One character on screen
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cell {
+    pub ch: char,
+    /// `None` draws in the terminal's default colour
+    pub color: Option<Color>,
+}
+
+impl Default for Cell {
+    fn default() -> Self {
+        Self {
+            ch: ' ',
+            color: None,
+        }
+    }
+}
+
+/// A picture to draw, with a line of text underneath
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Frame {
+    cells: Grid<Cell>,
+    caption: String,
+}
+
+impl Frame {
+    /// A blank frame
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            cells: Grid::from_fn(width, height, |_, _| Cell::default()),
+            caption: String::new(),
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.cells.width()
+    }
+
+    pub fn height(&self) -> usize {
+        self.cells.height()
+    }
+
+    /// Draws `ch` at `(x, y)`, ignoring positions outside the frame
+    pub fn set(&mut self, x: usize, y: usize, ch: char, color: Option<Color>) {
+        if let Some(cell) = self.cells.get_mut(x, y) {
+            *cell = Cell { ch, color };
+        }
+    }
+
+    pub fn with_caption(mut self, caption: impl Into<String>) -> Self {
+        self.caption = caption.into();
+        self
+    }
+
+    /// Queues the frame onto `out`, cut down to `columns` by `rows` so it doesn't scroll
+    fn draw(&self, out: &mut impl Write, columns: u16, rows: u16) -> io::Result<()> {
+        queue!(out, terminal::Clear(terminal::ClearType::All))?;
+        let visible_rows = usize::from(rows.saturating_sub(1));
+        for (y, row) in self.cells.rows().take(visible_rows).enumerate() {
+            queue!(out, cursor::MoveTo(0, y as u16))?;
+            for cell in row.iter().take(usize::from(columns)) {
+                match cell.color {
+                    Some(color) => queue!(out, style::PrintStyledContent(cell.ch.with(color)))?,
+                    None => queue!(out, style::Print(cell.ch))?,
+                }
+            }
+        }
+        let caption_row = self.height().min(visible_rows) as u16;
+        queue!(
+            out,
+            cursor::MoveTo(0, caption_row),
+            style::Print(&self.caption)
+        )?;
+        out.flush()
+    }
+}
+
+/// The characters without colour, then the caption
+impl Display for Frame {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in self.cells.rows() {
+            let line: String = row.iter().map(|cell| cell.ch).collect();
+            writeln!(f, "{}", line)?;
+        }
+        write!(f, "{}", self.caption)
+    }
+}
+
+/// Puts the terminal back how it was, even if drawing fails part way through
+struct Screen;
+
+impl Screen {
+    fn enter() -> io::Result<Self> {
+        terminal::enable_raw_mode()?;
+        execute!(io::stdout(), terminal::EnterAlternateScreen, cursor::Hide)?;
+        Ok(Self)
+    }
+}
+
+impl Drop for Screen {
+    fn drop(&mut self) {
+        let _ = execute!(io::stdout(), cursor::Show, terminal::LeaveAlternateScreen);
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+fn is_quit(key: KeyEvent) -> bool {
+    matches!(key.code, KeyCode::Char('q') | KeyCode::Esc)
+        || (key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL))
+}
+
+/// Shows frames one after another on the alternate screen
+#[derive(Debug, Clone, Copy)]
+pub struct Player {
+    delay: Duration,
+}
+
+impl Player {
+    /// Waits `delay` between frames
+    pub fn new(delay: Duration) -> Self {
+        Self { delay }
+    }
+
+    /// Plays `frames`, then holds the last one until a key is pressed. Pressing `q`, Escape
+    /// or Ctrl-C stops early.
+    pub fn play(&self, frames: impl IntoIterator<Item = Frame>) -> io::Result<()> {
+        let _screen = Screen::enter()?;
+        let mut out = io::stdout();
+        for frame in frames {
+            let (columns, rows) = terminal::size()?;
+            frame.draw(&mut out, columns, rows)?;
+            if event::poll(self.delay)? {
+                if let Event::Key(key) = event::read()? {
+                    if is_quit(key) {
+                        return Ok(());
+                    }
+                }
+            }
+        }
+        loop {
+            if let Event::Key(_) = event::read()? {
+                return Ok(());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_frame() {
+        let mut frame = Frame::new(3, 2).with_caption("step 1");
+        frame.set(0, 0, '#', Some(Color::Yellow));
+        frame.set(2, 1, '9', None);
+        frame.set(3, 0, 'x', None);
+        assert_eq!(frame.to_string(), "#  \n  9\nstep 1");
+        assert_eq!((frame.width(), frame.height()), (3, 2));
+    }
+
+    #[test]
+    fn test_draw_clips_to_terminal() {
+        let mut frame = Frame::new(4, 3).with_caption("done");
+        frame.set(3, 0, 'x', None);
+        frame.set(0, 2, 'y', None);
+        let mut out = Vec::new();
+        frame.draw(&mut out, 3, 3).unwrap();
+        let drawn = String::from_utf8(out).unwrap();
+        assert!(!drawn.contains('x'), "column 4 is off screen");
+        assert!(
+            !drawn.contains('y'),
+            "row 3 would push the caption off screen"
+        );
+        assert!(drawn.ends_with("done"));
+    }
+}
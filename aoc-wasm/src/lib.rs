@@ -0,0 +1,88 @@
+//! The solvers as a WebAssembly module, so they can run in a browser. Build it with
+//!
+//! ```text
+//! wasm-pack build aoc-wasm --target web
+//! ```
+//!
+//! then serve `aoc-wasm/` and open `www/index.html`.
+
+use aoc_common::registry;
+use wasm_bindgen::prelude::*;
+
+// Naming the days links them in, which registers their solvers
+use day1 as _;
+use day10 as _;
+use day11 as _;
+use day12 as _;
+use day13 as _;
+use day14 as _;
+use day15 as _;
+use day16 as _;
+use day17 as _;
+use day18 as _;
+use day19 as _;
+use day2 as _;
+use day3 as _;
+use day4 as _;
+use day5 as _;
+use day6 as _;
+use day7 as _;
+use day8 as _;
+use day9 as _;
+
+#[cfg(target_family = "wasm")]
+extern "C" {
+    fn __wasm_call_ctors();
+}
+
+/// Registers the days as soon as the module is instantiated. The linker doesn't always call
+/// constructors by itself in a library module, and calling them again is harmless.
+#[cfg(target_family = "wasm")]
+#[wasm_bindgen(start)]
+fn start() {
+    // SAFETY: the only constructors are `inventory`'s, which are safe to run more than once
+    unsafe { __wasm_call_ctors() }
+}
+
+/// The days there are solutions for, in order
+#[wasm_bindgen]
+pub fn days() -> Vec<u8> {
+    registry::solvers()
+        .iter()
+        .map(|solver| solver.day)
+        .collect()
+}
+
+/// Solves one part of a day, throwing a string if the day doesn't exist or the input is bad
+#[wasm_bindgen]
+pub fn solve(day: u8, part: u8, input: &str) -> Result<String, String> {
+    let solver =
+        registry::solver(day).ok_or_else(|| format!("there's no solution for day {}", day))?;
+    let solve = match part {
+        1 | 2 => solver.parts[usize::from(part) - 1],
+        _ => return Err(format!("day {} has no part {}", day, part)),
+    };
+    solve(input).map(|answer| answer.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn solves_pasted_input() {
+        assert_eq!(
+            solve(1, 1, "199\n200\n208\n210\n200\n207\n240\n269\n260\n263"),
+            Ok(String::from("7"))
+        );
+        assert_eq!(solve(16, 1, "8A004A801A8002F478"), Ok(String::from("16")));
+        assert!(solve(16, 1, "not hex").is_err());
+    }
+
+    #[test]
+    fn rejects_missing_days_and_parts() {
+        assert!(solve(0, 1, "").is_err());
+        assert!(solve(days().last().unwrap() + 1, 1, "").is_err());
+        assert!(solve(1, 3, "").is_err());
+    }
+}
@@ -0,0 +1,210 @@
+//! Solving a day in a child process of the runner, so that one that runs over its timeout can
+//! be killed
+//!
+//! The runner starts itself again with the hidden `solve-child` command, writes the input to
+//! its stdin, and reads [`Message`]s back from its stdout, one JSON line each: the progress
+//! of each part, so the bars are still drawn by the runner, then the answers.
+
+use std::{
+    cell::Cell,
+    collections::HashMap,
+    io::{self, BufRead, BufReader, Read, Write},
+    time::{Duration, Instant},
+};
+
+use aoc_common::{answer::Answer, progress::Progress};
+use serde::{Deserialize, Serialize};
+
+use crate::{progress::PartBar, report::Report};
+
+/// How often a child passes on the progress it's made, since some solvers advance millions
+/// of times
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(50);
+
+/// An [`Answer`] on its way from the child, keeping its kind
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+enum SentAnswer {
+    Num(u64),
+    Text(String),
+    Grid(String),
+}
+
+impl From<Answer> for SentAnswer {
+    fn from(answer: Answer) -> Self {
+        match answer {
+            Answer::Num(n) => Self::Num(n),
+            Answer::Text(text) => Self::Text(text),
+            Answer::Grid(grid) => Self::Grid(grid),
+        }
+    }
+}
+
+impl From<SentAnswer> for Answer {
+    fn from(answer: SentAnswer) -> Self {
+        match answer {
+            SentAnswer::Num(n) => Self::Num(n),
+            SentAnswer::Text(text) => Self::Text(text),
+            SentAnswer::Grid(grid) => Self::Grid(grid),
+        }
+    }
+}
+
+/// What a child tells the runner
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+enum Message {
+    Start { part: u8, total: Option<u64> },
+    Advance { part: u8, steps: u64 },
+    Solved(Result<(Option<SentAnswer>, Option<SentAnswer>, f64), String>),
+}
+
+fn send(message: &Message) {
+    let line = serde_json::to_string(message).expect("messages always serialize");
+    let mut stdout = io::stdout().lock();
+    // Nobody's listening any more if this fails, so there's no one to tell
+    let _ = writeln!(stdout, "{}", line).and_then(|()| stdout.flush());
+}
+
+/// Passes one part's progress on to the runner, a batch at a time
+pub struct Forward {
+    part: u8,
+    pending: Cell<u64>,
+    sent: Cell<Instant>,
+}
+
+impl Forward {
+    pub fn new(part: u8) -> Self {
+        Self {
+            part,
+            pending: Cell::new(0),
+            sent: Cell::new(Instant::now()),
+        }
+    }
+
+    fn flush(&self) {
+        let steps = self.pending.take();
+        if steps > 0 {
+            send(&Message::Advance {
+                part: self.part,
+                steps,
+            });
+        }
+        self.sent.set(Instant::now());
+    }
+}
+
+impl Progress for Forward {
+    fn start(&self, total: Option<u64>) {
+        self.flush();
+        send(&Message::Start {
+            part: self.part,
+            total,
+        });
+    }
+
+    fn advance(&self, steps: u64) {
+        self.pending.set(self.pending.get() + steps);
+        if self.sent.get().elapsed() >= PROGRESS_INTERVAL {
+            self.flush();
+        }
+    }
+}
+
+impl Drop for Forward {
+    fn drop(&mut self) {
+        self.flush();
+    }
+}
+
+/// The input the runner wrote to this child's stdin
+pub fn input() -> Result<String, String> {
+    let mut input = String::new();
+    io::stdin()
+        .read_to_string(&mut input)
+        .map_err(|e| format!("couldn't read input: {}", e))?;
+    Ok(input)
+}
+
+/// Tells the runner how solving went, as this child's last message
+pub fn answer(solved: &Result<Report, String>) {
+    let solved = solved.as_ref().map(|report| {
+        (
+            report.part1.clone().map(SentAnswer::from),
+            report.part2.clone().map(SentAnswer::from),
+            report.elapsed_ms,
+        )
+    });
+    send(&Message::Solved(solved.map_err(Clone::clone)));
+}
+
+/// Reads a child solving `day` until it exits, drawing its progress, and returns how solving
+/// went, or `None` if it exited without saying
+pub fn read(day: u8, output: impl Read) -> Option<Result<Report, String>> {
+    let mut bars: HashMap<u8, PartBar> = HashMap::new();
+    let mut solved = None;
+    for line in BufReader::new(output).lines() {
+        let Ok(line) = line else { break };
+        // Anything else a solver printed isn't for us
+        let Ok(message) = serde_json::from_str(&line) else {
+            continue;
+        };
+        match message {
+            Message::Start { part, total } => bars
+                .entry(part)
+                .or_insert_with(|| PartBar::new(day, part))
+                .start(total),
+            Message::Advance { part, steps } => {
+                if let Some(bar) = bars.get(&part) {
+                    bar.advance(steps);
+                }
+            }
+            Message::Solved(result) => {
+                solved = Some(result.map(|(part1, part2, elapsed_ms)| Report {
+                    day,
+                    part1: part1.map(Answer::from),
+                    part2: part2.map(Answer::from),
+                    elapsed_ms,
+                }));
+            }
+        }
+    }
+    solved
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_what_a_child_sends() {
+        let lines = [
+            Message::Start {
+                part: 1,
+                total: Some(10),
+            },
+            Message::Advance { part: 1, steps: 4 },
+            Message::Solved(Ok((
+                Some(SentAnswer::Num(7)),
+                Some(SentAnswer::Grid(String::from("#.\n.#"))),
+                1.5,
+            ))),
+        ]
+        .iter()
+        .map(|message| serde_json::to_string(message).unwrap())
+        .chain([String::from("something a solver printed")])
+        .collect::<Vec<_>>()
+        .join("\n");
+        assert_eq!(
+            read(3, lines.as_bytes()),
+            Some(Ok(Report {
+                day: 3,
+                part1: Some(Answer::Num(7)),
+                part2: Some(Answer::Grid(String::from("#.\n.#"))),
+                elapsed_ms: 1.5,
+            }))
+        );
+
+        let failed = serde_json::to_string(&Message::Solved(Err(String::from("bad")))).unwrap();
+        assert_eq!(read(3, failed.as_bytes()), Some(Err(String::from("bad"))));
+        assert_eq!(read(3, "".as_bytes()), None);
+    }
+}
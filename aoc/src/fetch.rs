@@ -0,0 +1,117 @@
+//! Downloads puzzle inputs from adventofcode.com and keeps them in `inputs/`
+
+use std::{env, ffi::OsString, fs, path::PathBuf};
+
+/// Environment variable holding the adventofcode.com `session` cookie
+pub const SESSION_VAR: &str = "AOC_SESSION";
+
+pub const USER_AGENT: &str = "github.com/NotTheEconomist/aoc2021-rust";
+
+/// Environment variable naming the directory that holds `inputs/` and `answers.toml`, if it
+/// shouldn't be the current directory
+pub const CACHE_DIR_VAR: &str = "AOC_CACHE_DIR";
+
+/// Where `inputs/` and `answers.toml` are kept: [`CACHE_DIR_VAR`] if it's set, or else the
+/// current directory, which is the workspace root under `cargo run`
+pub fn cache_dir() -> PathBuf {
+    cache_dir_from(env::var_os(CACHE_DIR_VAR))
+}
+
+fn cache_dir_from(var: Option<OsString>) -> PathBuf {
+    var.filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .unwrap_or_else(|| env::current_dir().unwrap_or_default())
+}
+
+/// `inputs/` in the [`cache_dir`], where anything specific to one user's account is kept
+pub fn inputs_dir() -> PathBuf {
+    cache_dir().join("inputs")
+}
+
+/// Where a day's downloaded input is cached
+pub fn cache_path(day: u8) -> PathBuf {
+    inputs_dir().join(format!("day{}.txt", day))
+}
+
+/// The session cookie from [`SESSION_VAR`], if it's set
+pub fn session() -> Option<String> {
+    env::var(SESSION_VAR)
+        .ok()
+        .map(|session| session.trim().to_string())
+        .filter(|session| !session.is_empty())
+}
+
+fn download(day: u8, session: &str) -> Result<String, String> {
+    let url = format!("https://adventofcode.com/2021/day/{}/input", day);
+    let response = ureq::get(&url)
+        .set("Cookie", &format!("session={}", session))
+        .set("User-Agent", USER_AGENT)
+        .call()
+        .map_err(|e| match e {
+            ureq::Error::Status(code, _) => format!(
+                "adventofcode.com refused day {} with status {}, is {} still valid?",
+                day, code, SESSION_VAR
+            ),
+            e => format!("couldn't reach adventofcode.com: {}", e),
+        })?;
+    response
+        .into_string()
+        .map_err(|e| format!("couldn't read day {} input: {}", day, e))
+}
+
+/// Downloads a day's input into the cache, unless it's already there and `force` is unset
+pub fn fetch(day: u8, force: bool) -> Result<PathBuf, String> {
+    let path = cache_path(day);
+    if path.exists() && !force {
+        return Ok(path);
+    }
+    let session = session().ok_or_else(|| {
+        format!(
+            "set {} to your adventofcode.com session cookie to download inputs",
+            SESSION_VAR
+        )
+    })?;
+    let input = download(day, &session)?;
+    let dir = path.parent().expect("cache path is inside inputs/");
+    fs::create_dir_all(dir).map_err(|e| format!("couldn't create {}: {}", dir.display(), e))?;
+    fs::write(&path, input).map_err(|e| format!("couldn't write {}: {}", path.display(), e))?;
+    Ok(path)
+}
+
+/// Reads a day's cached input, downloading it first if there's a session to do it with.
+///
+/// Returns `None` when there's neither a cached copy nor a session, so the caller can fall
+/// back to the bundled input.
+pub fn local_input(day: u8) -> Result<Option<String>, String> {
+    let path = cache_path(day);
+    if !path.exists() {
+        if session().is_none() {
+            return Ok(None);
+        }
+        fetch(day, false)?;
+    }
+    fs::read_to_string(&path)
+        .map(Some)
+        .map_err(|e| format!("couldn't read {}: {}", path.display(), e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cache_lives_in_inputs() {
+        assert!(cache_path(16).ends_with("inputs/day16.txt"));
+    }
+
+    #[test]
+    fn cache_dir_defaults_to_the_current_directory() {
+        let here = env::current_dir().unwrap();
+        assert_eq!(cache_dir_from(None), here);
+        assert_eq!(cache_dir_from(Some(OsString::new())), here);
+        assert_eq!(
+            cache_dir_from(Some(OsString::from("/srv/aoc"))),
+            PathBuf::from("/srv/aoc")
+        );
+    }
+}
@@ -0,0 +1,521 @@
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    process::{self, ExitCode},
+    time::{Duration, Instant},
+};
+
+use aoc_common::{
+    answer::Answer,
+    input::InputSource,
+    progress::{with_progress, Progress},
+    registry::{self, Solver},
+};
+use clap::{Parser, Subcommand};
+use rayon::prelude::*;
+use report::Report;
+use timeout::{Limit, Timeouts};
+
+mod child;
+mod fetch;
+mod progress;
+mod report;
+mod submit;
+mod timeout;
+mod verify;
+
+// Each day registers its solver with `aoc_common::registry` when it's linked in, and it's only
+// linked in if something names it
+use day1 as _;
+use day10 as _;
+use day11 as _;
+use day12 as _;
+use day13 as _;
+use day14 as _;
+use day15 as _;
+use day16 as _;
+use day17 as _;
+use day18 as _;
+use day19 as _;
+use day2 as _;
+use day3 as _;
+use day4 as _;
+use day5 as _;
+use day6 as _;
+use day7 as _;
+use day8 as _;
+use day9 as _;
+
+/// Parses `--day`, accepting only days that registered a solver
+fn registered_day(arg: &str) -> Result<u8, String> {
+    let day: u8 = arg
+        .parse()
+        .map_err(|_| format!("`{}` isn't a day number", arg))?;
+    solver(day).map(|_| day)
+}
+
+#[derive(Parser)]
+#[command(about = "Advent of Code 2021 solutions")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Solve a day's puzzle, or every day's
+    Run {
+        #[arg(long, value_parser = registered_day, required_unless_present = "all")]
+        day: Option<u8>,
+        /// Solve every day in parallel and print a summary table
+        #[arg(long, conflicts_with_all = ["day", "part", "input"])]
+        all: bool,
+        /// Only solve this part, instead of both
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: Option<u8>,
+        /// Read the puzzle input from this file (or `-` for stdin) instead
+        #[arg(long)]
+        input: Option<PathBuf>,
+        /// Print the answers as a single line of JSON (one line per day with `--all`)
+        #[arg(long)]
+        json: bool,
+        /// Give up on a day after SECS seconds, or only on day DAY with DAY=SECS. Repeatable.
+        #[arg(long = "timeout", value_name = "[DAY=]SECS", value_parser = timeout::parse_limit)]
+        timeouts: Vec<Limit>,
+    },
+    /// Download a day's puzzle input into inputs/, using the AOC_SESSION cookie
+    Fetch {
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+        /// Download again even if the input is already cached
+        #[arg(long)]
+        force: bool,
+    },
+    /// Solve one part of a day and submit the answer to adventofcode.com
+    Submit {
+        #[arg(long, value_parser = registered_day)]
+        day: u8,
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: u8,
+        /// Solve this file (or `-` for stdin) instead of your cached input
+        #[arg(long)]
+        input: Option<PathBuf>,
+    },
+    /// Solve a day for every input in a directory, like several people's, and compare them
+    Compare {
+        #[arg(long, value_parser = registered_day)]
+        day: u8,
+        /// The directory of inputs, one per file
+        #[arg(long)]
+        dir: PathBuf,
+        /// Only solve this part, instead of both
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: Option<u8>,
+        /// Give up on a day after SECS seconds, or only on day DAY with DAY=SECS. Repeatable.
+        #[arg(long = "timeout", value_name = "[DAY=]SECS", value_parser = timeout::parse_limit)]
+        timeouts: Vec<Limit>,
+    },
+    /// Solve every day and compare the answers to the ones recorded in answers.toml
+    Verify {
+        /// Read the recorded answers from this file instead
+        #[arg(long)]
+        answers: Option<PathBuf>,
+        /// Record answers for any parts that don't have one yet
+        #[arg(long)]
+        record: bool,
+        /// Give up on a day after SECS seconds, or only on day DAY with DAY=SECS. Repeatable.
+        #[arg(long = "timeout", value_name = "[DAY=]SECS", value_parser = timeout::parse_limit)]
+        timeouts: Vec<Limit>,
+    },
+    /// Solve a day's input from stdin for a runner that started this as a child process, and
+    /// tell it how that went on stdout
+    #[command(hide = true)]
+    SolveChild {
+        #[arg(long, value_parser = registered_day)]
+        day: u8,
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: Option<u8>,
+    },
+}
+
+/// The solver registered for `day`
+fn solver(day: u8) -> Result<&'static Solver, String> {
+    registry::solver(day).ok_or_else(|| format!("there's no solution for day {}", day))
+}
+
+/// Solves one part of a day, or both if `part` is `None`, failing if that takes longer than
+/// `timeout`. With a timeout, it's solved in a child process, which is killed if it runs over.
+fn solve(
+    day: u8,
+    part: Option<u8>,
+    input: &str,
+    timeout: Option<Duration>,
+) -> Result<Report, String> {
+    let Some(timeout) = timeout else {
+        return solve_parts(day, part, input, |part| progress::PartBar::new(day, part));
+    };
+    let exe = env::current_exe().map_err(|e| format!("couldn't find the runner: {}", e))?;
+    let mut command = process::Command::new(exe);
+    command.args(["solve-child", "--day", &day.to_string()]);
+    if let Some(part) = part {
+        command.args(["--part", &part.to_string()]);
+    }
+    match timeout::run(&mut command, input, timeout, move |output| {
+        child::read(day, output)
+    }) {
+        Ok(Some((_, Some(solved)))) => solved,
+        Ok(Some((status, None))) => {
+            Err(format!("day{} stopped without answering ({})", day, status))
+        }
+        Ok(None) => Err(format!(
+            "day{} timed out after {:?} and was stopped",
+            day, timeout
+        )),
+        Err(e) => Err(format!(
+            "couldn't solve day{} in a child process: {}",
+            day, e
+        )),
+    }
+}
+
+/// Solves as [`solve`] does without a timeout, reporting each part's progress to what
+/// `progress(part)` gives
+fn solve_parts<P: Progress + 'static>(
+    day: u8,
+    part: Option<u8>,
+    input: &str,
+    progress: impl Fn(u8) -> P,
+) -> Result<Report, String> {
+    let solution = solver(day)?;
+    let mut report = Report {
+        day,
+        part1: None,
+        part2: None,
+        elapsed_ms: 0.0,
+    };
+    let start = Instant::now();
+    for (idx, answer) in [&mut report.part1, &mut report.part2]
+        .into_iter()
+        .enumerate()
+    {
+        let this_part = idx as u8 + 1;
+        if part.is_some_and(|part| part != this_part) {
+            continue;
+        }
+        let solved = with_progress(progress(this_part), || solution.parts[idx](input));
+        *answer = Some(solved.map_err(|e| format!("day{} part{}: {}", day, this_part, e))?);
+    }
+    report.elapsed_ms = start.elapsed().as_secs_f64() * 1000.0;
+    Ok(report)
+}
+
+/// Reads `path`, or else a day's cached (or freshly downloaded) input, or else the input
+/// bundled with it
+fn day_input(day: u8, path: Option<PathBuf>) -> Result<String, String> {
+    let bundled = solver(day)?.input;
+    match path {
+        Some(path) => InputSource::from_path(path)
+            .read(bundled)
+            .map_err(|e| format!("couldn't read input: {}", e)),
+        None => Ok(fetch::local_input(day)?.unwrap_or_else(|| bundled.to_string())),
+    }
+}
+
+fn print_report(report: &Report, json: bool) {
+    if json {
+        let json = serde_json::to_string(report).expect("reports always serialize");
+        println!("{}", json);
+    } else {
+        print!("{}", report);
+    }
+}
+
+fn run(
+    day: u8,
+    part: Option<u8>,
+    input: Option<PathBuf>,
+    json: bool,
+    timeouts: &Timeouts,
+) -> Result<(), String> {
+    let input = day_input(day, input)?;
+    let report = solve(day, part, &input, timeouts.for_day(day))?;
+    print_report(&report, json);
+    Ok(())
+}
+
+/// Solves both parts of every registered day in parallel, in day order
+fn solve_all(timeouts: &Timeouts) -> Vec<(u8, Result<Report, String>)> {
+    registry::solvers()
+        .into_par_iter()
+        .map(|solver| {
+            let day = solver.day;
+            (
+                day,
+                day_input(day, None)
+                    .and_then(|input| solve(day, None, &input, timeouts.for_day(day))),
+            )
+        })
+        .collect()
+}
+
+/// Solves every day in parallel, failing if any of them did
+fn run_all(json: bool, timeouts: &Timeouts) -> Result<(), String> {
+    let results = solve_all(timeouts);
+    if json {
+        for report in results.iter().flat_map(|(_, result)| result) {
+            print_report(report, true);
+        }
+    } else {
+        print!("{}", report::summary(&results));
+    }
+    let failed = results.iter().filter(|(_, result)| result.is_err()).count();
+    match failed {
+        0 => Ok(()),
+        _ => Err(format!("{} of {} days failed", failed, results.len())),
+    }
+}
+
+/// Every file in `dir` in name order, leaving out hidden ones like `.gitignore`
+fn input_files(dir: &Path) -> Result<Vec<PathBuf>, String> {
+    let entries =
+        fs::read_dir(dir).map_err(|e| format!("couldn't read {}: {}", dir.display(), e))?;
+    let mut files = Vec::new();
+    for entry in entries {
+        let path = entry
+            .map_err(|e| format!("couldn't read {}: {}", dir.display(), e))?
+            .path();
+        let hidden = path
+            .file_name()
+            .is_some_and(|name| name.to_string_lossy().starts_with('.'));
+        if path.is_file() && !hidden {
+            files.push(path);
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// Solves a day for each file in parallel, labelling each result with the file's name
+fn compare(
+    day: u8,
+    part: Option<u8>,
+    files: &[PathBuf],
+    timeout: Option<Duration>,
+) -> Vec<(String, Result<Report, String>)> {
+    files
+        .par_iter()
+        .map(|path| {
+            let name = path.file_name().map_or_else(
+                || path.display().to_string(),
+                |name| name.to_string_lossy().into_owned(),
+            );
+            let result = fs::read_to_string(path)
+                .map_err(|e| format!("couldn't read input: {}", e))
+                .and_then(|input| solve(day, part, &input, timeout));
+            (name, result)
+        })
+        .collect()
+}
+
+/// Solves a day for every input in `dir` and prints the answers side by side, failing if any
+/// input couldn't be solved
+fn run_compare(day: u8, dir: &Path, part: Option<u8>, timeouts: &Timeouts) -> Result<(), String> {
+    let files = input_files(dir)?;
+    if files.is_empty() {
+        return Err(format!("there are no inputs in {}", dir.display()));
+    }
+    let results = compare(day, part, &files, timeouts.for_day(day));
+    print!("{}", report::comparison(&results));
+    let failed = results.iter().filter(|(_, result)| result.is_err()).count();
+    match failed {
+        0 => Ok(()),
+        _ => Err(format!("{} of {} inputs failed", failed, results.len())),
+    }
+}
+
+/// Solves one part and submits it, recording it in answers.toml if it's right
+fn run_submit(day: u8, part: u8, input: Option<PathBuf>) -> Result<(), String> {
+    let input = day_input(day, input)?;
+    let report = solve(day, Some(part), &input, None)?;
+    let answer = [report.part1, report.part2]
+        .into_iter()
+        .flatten()
+        .next()
+        .expect("solve answers the part it's asked for");
+    if answer.is_multiline() {
+        println!("day{} part{}:\n{}", day, part, answer);
+    } else {
+        println!("day{} part{}: {}", day, part, answer);
+    }
+    let answer =
+        match answer {
+            Answer::Grid(_) => return Err(String::from(
+                "that answer is drawn in letters, read them off and submit those on the website",
+            )),
+            answer => answer.to_string(),
+        };
+    let response = submit::submit(day, part, &answer)?;
+    println!("{}", response);
+    match response {
+        submit::Response::Correct => {
+            let path = verify::default_path();
+            let mut answers = verify::Answers::load(&path)?;
+            answers.set(day, part, answer);
+            answers.save(&path)
+        }
+        submit::Response::WrongLevel => Ok(()),
+        _ => Err(String::from("answer not accepted")),
+    }
+}
+
+/// Solves a day for the runner that started this process, with `--timeout`
+fn run_child(day: u8, part: Option<u8>) -> Result<(), String> {
+    let solved =
+        child::input().and_then(|input| solve_parts(day, part, &input, child::Forward::new));
+    child::answer(&solved);
+    Ok(())
+}
+
+/// Solves every day and checks the answers, failing on any wrong answer or error
+fn run_verify(path: Option<PathBuf>, record: bool, timeouts: &Timeouts) -> Result<(), String> {
+    let path = path.unwrap_or_else(verify::default_path);
+    let mut answers = verify::Answers::load(&path)?;
+    let verdicts: Vec<verify::Verdict> = solve_all(timeouts)
+        .iter()
+        .map(|(day, result)| verify::check(*day, result, &answers))
+        .collect();
+    print!("{}", verify::summary(&verdicts));
+    if record {
+        let added = verify::record(&verdicts, &mut answers);
+        if added > 0 {
+            answers.save(&path)?;
+            println!("recorded {} new answers in {}", added, path.display());
+        }
+    }
+    let failures = verdicts
+        .iter()
+        .flat_map(|verdict| &verdict.parts)
+        .filter(|outcome| outcome.is_failure())
+        .count();
+    match failures {
+        0 => Ok(()),
+        _ => Err(format!("{} answers didn't verify", failures)),
+    }
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let result = match cli.command {
+        Command::Run {
+            all: true,
+            json,
+            timeouts,
+            ..
+        } => run_all(json, &Timeouts::new(&timeouts)),
+        Command::Run {
+            day,
+            part,
+            input,
+            json,
+            timeouts,
+            ..
+        } => run(
+            day.expect("clap requires --day without --all"),
+            part,
+            input,
+            json,
+            &Timeouts::new(&timeouts),
+        ),
+        Command::Fetch { day, force } => fetch::fetch(day, force).map(|path| {
+            println!("day{} input is in {}", day, path.display());
+        }),
+        Command::Submit { day, part, input } => run_submit(day, part, input),
+        Command::Compare {
+            day,
+            dir,
+            part,
+            timeouts,
+        } => run_compare(day, &dir, part, &Timeouts::new(&timeouts)),
+        Command::Verify {
+            answers,
+            record,
+            timeouts,
+        } => run_verify(answers, record, &Timeouts::new(&timeouts)),
+        Command::SolveChild { day, part } => run_child(day, part),
+    };
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::CommandFactory;
+
+    use super::*;
+
+    #[test]
+    fn dispatches_to_the_right_day() {
+        let day1 = solver(1).unwrap();
+        assert_eq!(
+            day1.parts[0]("199\n200\n208\n210\n200\n207\n240\n269\n260\n263"),
+            Ok(Answer::Num(7))
+        );
+        assert_eq!(solver(17).unwrap().input, day17::INPUT);
+        assert!(solver(25).is_err());
+        let days: Vec<u8> = registry::solvers()
+            .iter()
+            .map(|solver| solver.day)
+            .collect();
+        assert_eq!(days, (1..=19).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn bad_input_is_an_error() {
+        assert!(day16::part1("not hex").is_err());
+        assert!(day17::part2("target area: nowhere").is_err());
+    }
+
+    #[test]
+    fn json_report() {
+        let mut report = solve(1, Some(2), "1\n2\n3\n4\n", None).unwrap();
+        assert_eq!(report.part1, None);
+        assert_eq!(report.part2, Some(Answer::Num(1)));
+        report.elapsed_ms = 1.5;
+        assert_eq!(
+            serde_json::to_string(&report).unwrap(),
+            r#"{"day":1,"part2":"1","elapsed_ms":1.5}"#
+        );
+    }
+
+    #[test]
+    fn compares_inputs_in_a_directory() {
+        let dir = std::env::temp_dir().join(format!("aoc-compare-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("b.txt"), "1\n2\n3\n4\n").unwrap();
+        fs::write(dir.join("a.txt"), "3\n2\n1\n").unwrap();
+        fs::write(dir.join("c.txt"), "not depths").unwrap();
+        fs::write(dir.join(".hidden"), "1\n2\n").unwrap();
+        fs::create_dir_all(dir.join("nested")).unwrap();
+        let files = input_files(&dir).unwrap();
+        let results = compare(1, Some(1), &files, None);
+        fs::remove_dir_all(&dir).unwrap();
+
+        let names: Vec<&str> = results.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(names, ["a.txt", "b.txt", "c.txt"]);
+        let answers: Vec<Option<Answer>> = results
+            .iter()
+            .map(|(_, result)| result.as_ref().ok().and_then(|report| report.part1.clone()))
+            .collect();
+        assert_eq!(answers, [Some(Answer::Num(0)), Some(Answer::Num(3)), None]);
+    }
+
+    #[test]
+    fn cli_parses() {
+        Cli::command().debug_assert();
+    }
+}
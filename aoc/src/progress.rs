@@ -0,0 +1,66 @@
+//! Shows how far along slow solvers are, as a bar on stderr for each part being solved
+
+use std::{cell::RefCell, sync::OnceLock};
+
+use aoc_common::progress::Progress;
+use indicatif::{MultiProgress, ProgressBar, ProgressStyle};
+
+/// Every bar is drawn through this, so parts solved in parallel each get a line of their own.
+/// It draws nothing when stderr isn't a terminal.
+fn bars() -> &'static MultiProgress {
+    static BARS: OnceLock<MultiProgress> = OnceLock::new();
+    BARS.get_or_init(MultiProgress::new)
+}
+
+fn style(template: &str) -> ProgressStyle {
+    ProgressStyle::with_template(template)
+        .expect("bar templates are valid")
+        .progress_chars("=> ")
+}
+
+/// One part's bar, which only appears if the solver reports any progress
+pub struct PartBar {
+    label: String,
+    bar: RefCell<Option<ProgressBar>>,
+}
+
+impl PartBar {
+    pub fn new(day: u8, part: u8) -> Self {
+        Self {
+            label: format!("day{} part{}", day, part),
+            bar: RefCell::new(None),
+        }
+    }
+}
+
+impl Progress for PartBar {
+    fn start(&self, total: Option<u64>) {
+        let bar = match total {
+            Some(total) => ProgressBar::new(total)
+                .with_style(style("{prefix:>11} [{bar:40}] {percent:>3}% ({eta} left)")),
+            None => ProgressBar::new_spinner().with_style(style(
+                "{prefix:>11} {spinner} {human_pos} steps ({elapsed})",
+            )),
+        };
+        let bar = bars().add(bar.with_prefix(self.label.clone()));
+        if let Some(previous) = self.bar.replace(Some(bar)) {
+            previous.finish_and_clear();
+        }
+    }
+
+    fn advance(&self, steps: u64) {
+        if let Some(bar) = self.bar.borrow().as_ref() {
+            bar.inc(steps);
+        }
+    }
+}
+
+/// Takes the bar away again once the part is solved
+impl Drop for PartBar {
+    fn drop(&mut self) {
+        if let Some(bar) = self.bar.take() {
+            bar.finish_and_clear();
+            bars().remove(&bar);
+        }
+    }
+}
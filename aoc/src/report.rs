@@ -0,0 +1,211 @@
+//! Lays out answers for people and for scripts: one report per solve, or a table of many
+
+use std::fmt::Display;
+
+use aoc_common::answer::Answer;
+use serde::{Serialize, Serializer};
+
+/// The answers from solving a day, and how long they took
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Report {
+    pub day: u8,
+    #[serde(skip_serializing_if = "Option::is_none", serialize_with = "as_text")]
+    pub part1: Option<Answer>,
+    #[serde(skip_serializing_if = "Option::is_none", serialize_with = "as_text")]
+    pub part2: Option<Answer>,
+    pub elapsed_ms: f64,
+}
+
+/// Every kind of answer goes into JSON as a string, so `part1` and `part2` always have the
+/// same type
+fn as_text<S: Serializer>(answer: &Option<Answer>, serializer: S) -> Result<S::Ok, S::Error> {
+    match answer {
+        Some(answer) => serializer.collect_str(answer),
+        None => serializer.serialize_none(),
+    }
+}
+
+impl Display for Report {
+    /// One `partN: answer` line per part, putting multi-line answers (like day 13's) on their
+    /// own lines
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (part, answer) in [(1, &self.part1), (2, &self.part2)] {
+            match answer {
+                Some(answer) if answer.is_multiline() => writeln!(f, "part{}:\n{}", part, answer)?,
+                Some(answer) => writeln!(f, "part{}: {}", part, answer)?,
+                None => {}
+            }
+        }
+        Ok(())
+    }
+}
+
+/// How the first column of a [`table`] lines up
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Align {
+    Left,
+    Right,
+}
+
+fn cell(answer: &Option<Answer>) -> String {
+    match answer {
+        Some(answer) if answer.is_multiline() => {
+            format!("({} lines)", answer.to_string().lines().count())
+        }
+        Some(answer) => answer.to_string(),
+        None => String::new(),
+    }
+}
+
+/// One row per result, labelled with its key under `key_header`, then a total time
+fn table(
+    key_header: &str,
+    align: Align,
+    results: &[(impl Display, Result<Report, String>)],
+) -> String {
+    let rows: Vec<[String; 4]> = results
+        .iter()
+        .map(|(key, result)| match result {
+            Ok(report) => [
+                key.to_string(),
+                cell(&report.part1),
+                cell(&report.part2),
+                format!("{:.3}", report.elapsed_ms),
+            ],
+            Err(e) => [
+                key.to_string(),
+                format!("error: {}", e),
+                String::new(),
+                String::new(),
+            ],
+        })
+        .collect();
+    let header = [
+        String::from(key_header),
+        String::from("part1"),
+        String::from("part2"),
+        String::from("ms"),
+    ];
+    let mut widths = [0; 4];
+    for row in std::iter::once(&header).chain(&rows) {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.len());
+        }
+    }
+    let mut table = String::new();
+    for row in std::iter::once(&header).chain(&rows) {
+        let key = match align {
+            Align::Left => format!("{:<w$}", row[0], w = widths[0]),
+            Align::Right => format!("{:>w$}", row[0], w = widths[0]),
+        };
+        let line = format!(
+            "{}  {:<w1$}  {:<w2$}  {:>w3$}",
+            key,
+            row[1],
+            row[2],
+            row[3],
+            w1 = widths[1],
+            w2 = widths[2],
+            w3 = widths[3],
+        );
+        table.push_str(line.trim_end());
+        table.push('\n');
+    }
+    let total: f64 = results
+        .iter()
+        .flat_map(|(_, result)| result)
+        .map(|report| report.elapsed_ms)
+        .sum();
+    table.push_str(&format!("total {:.3} ms\n", total));
+    table
+}
+
+/// Lays out every day's answers and time in a table, with a total time underneath
+pub fn summary(results: &[(u8, Result<Report, String>)]) -> String {
+    table("day", Align::Right, results)
+}
+
+/// Lays out one day's answers for each of several inputs, named by file
+pub fn comparison(results: &[(String, Result<Report, String>)]) -> String {
+    table("input", Align::Left, results)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report(day: u8, part1: Answer, part2: Answer, elapsed_ms: f64) -> Result<Report, String> {
+        Ok(Report {
+            day,
+            part1: Some(part1),
+            part2: Some(part2),
+            elapsed_ms,
+        })
+    }
+
+    #[test]
+    fn multiline_answers_start_on_a_new_line() {
+        let report = Report {
+            day: 13,
+            part1: Some(Answer::Num(42)),
+            part2: Some(Answer::Grid(String::from("#.\n.#"))),
+            elapsed_ms: 1.0,
+        };
+        assert_eq!(report.to_string(), "part1: 42\npart2:\n#.\n.#\n");
+    }
+
+    #[test]
+    fn summary_table() {
+        let results = vec![
+            (1, report(1, Answer::Num(7), Answer::Num(5), 0.25)),
+            (2, Err(String::from("day2 part1: bad"))),
+            (
+                3,
+                report(
+                    3,
+                    Answer::Num(198),
+                    Answer::Grid(String::from("#.\n.#")),
+                    1.0,
+                ),
+            ),
+        ];
+        assert_eq!(
+            summary(&results),
+            "\
+day  part1                   part2         ms
+  1  7                       5          0.250
+  2  error: day2 part1: bad
+  3  198                     (2 lines)  1.000
+total 1.250 ms
+"
+        );
+    }
+
+    #[test]
+    fn comparison_table() {
+        let results = vec![
+            (
+                String::from("alice.txt"),
+                report(16, Answer::Num(893), Answer::Num(4358595186090), 0.5),
+            ),
+            (
+                String::from("bob.txt"),
+                Err(String::from("day16 part1: not hex")),
+            ),
+            (
+                String::from("c.txt"),
+                report(16, Answer::Num(31), Answer::Num(54), 0.125),
+            ),
+        ];
+        assert_eq!(
+            comparison(&results),
+            "\
+input      part1                        part2             ms
+alice.txt  893                          4358595186090  0.500
+bob.txt    error: day16 part1: not hex
+c.txt      31                           54             0.125
+total 0.625 ms
+"
+        );
+    }
+}
@@ -0,0 +1,305 @@
+//! Posts answers to adventofcode.com and makes sense of what it says back
+
+use std::{
+    fmt::Display,
+    fs,
+    path::PathBuf,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use crate::fetch::{self, SESSION_VAR, USER_AGENT};
+
+/// How long adventofcode.com makes you wait after a wrong answer, unless it says otherwise
+const WRONG_ANSWER_WAIT: Duration = Duration::from_secs(60);
+
+/// What adventofcode.com made of an answer
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Response {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// Wrong, without saying which way
+    Wrong,
+    /// This part has already been solved, or part 1 hasn't been yet
+    WrongLevel,
+    /// Submitted too soon after the last answer, with how much longer there is to wait
+    TooSoon(Duration),
+}
+
+impl Display for Response {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Correct => write!(f, "that's the right answer"),
+            Self::TooHigh => write!(f, "that's not the right answer, it's too high"),
+            Self::TooLow => write!(f, "that's not the right answer, it's too low"),
+            Self::Wrong => write!(f, "that's not the right answer"),
+            Self::WrongLevel => write!(
+                f,
+                "that part is already solved, or its first part isn't yet"
+            ),
+            Self::TooSoon(wait) => write!(
+                f,
+                "answered too recently, wait {}s before trying again",
+                wait.as_secs()
+            ),
+        }
+    }
+}
+
+/// The text of the page's `<article>`, which is where the verdict is, without any tags
+fn article_text(html: &str) -> &str {
+    let start = html.find("<article").unwrap_or(0);
+    let end = html[start..]
+        .find("</article>")
+        .map_or(html.len(), |end| start + end);
+    &html[start..end]
+}
+
+fn strip_tags(html: &str) -> String {
+    let mut text = String::with_capacity(html.len());
+    let mut in_tag = false;
+    for ch in html.chars() {
+        match ch {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            ch if !in_tag => text.push(ch),
+            _ => {}
+        }
+    }
+    text
+}
+
+/// Reads durations like `1m 5s`, `35s`, `5 minutes` or `one minute`
+fn parse_wait(text: &str) -> Option<Duration> {
+    let words: Vec<&str> = text
+        .split_whitespace()
+        .map(|word| word.trim_end_matches(['.', ',', ';']))
+        .collect();
+    let mut seconds = 0;
+    let mut found = false;
+    for (idx, word) in words.iter().enumerate() {
+        let split = word
+            .find(|ch: char| !ch.is_ascii_digit())
+            .unwrap_or(word.len());
+        let (number, unit) = match (&word[..split], &word[split..]) {
+            ("", "one") => (1, words.get(idx + 1).copied().unwrap_or_default()),
+            ("", _) => continue,
+            (number, "") => (
+                number.parse().ok()?,
+                words.get(idx + 1).copied().unwrap_or_default(),
+            ),
+            (number, unit) => (number.parse().ok()?, unit),
+        };
+        let scale: u64 = match unit {
+            "s" | "second" | "seconds" => 1,
+            "m" | "minute" | "minutes" => 60,
+            "h" | "hour" | "hours" => 3600,
+            _ => continue,
+        };
+        seconds += number * scale;
+        found = true;
+    }
+    found.then(|| Duration::from_secs(seconds))
+}
+
+/// Works out the verdict from the page adventofcode.com sends back after an answer
+pub fn parse_response(html: &str) -> Result<Response, String> {
+    let text = strip_tags(article_text(html));
+    let response = if text.contains("That's the right answer") {
+        Response::Correct
+    } else if text.contains("You gave an answer too recently") {
+        let wait = text
+            .split_once("You have")
+            .and_then(|(_, rest)| rest.split_once("left to wait"))
+            .and_then(|(wait, _)| parse_wait(wait))
+            .unwrap_or(WRONG_ANSWER_WAIT);
+        Response::TooSoon(wait)
+    } else if text.contains("That's not the right answer") {
+        if text.contains("your answer is too high") {
+            Response::TooHigh
+        } else if text.contains("your answer is too low") {
+            Response::TooLow
+        } else {
+            Response::Wrong
+        }
+    } else if text.contains("You don't seem to be solving the right level") {
+        Response::WrongLevel
+    } else {
+        return Err(format!(
+            "couldn't understand adventofcode.com's reply: {}",
+            text.trim()
+        ));
+    };
+    Ok(response)
+}
+
+/// How long to hold off submitting after getting `response` to an answer
+fn wait_after(response: Response, text: &str) -> Option<Duration> {
+    match response {
+        Response::TooSoon(wait) => Some(wait),
+        Response::TooHigh | Response::TooLow | Response::Wrong => Some(
+            text.split_once("please wait")
+                .and_then(|(_, rest)| rest.split_once("before trying again"))
+                .and_then(|(wait, _)| parse_wait(wait))
+                .unwrap_or(WRONG_ANSWER_WAIT),
+        ),
+        Response::Correct | Response::WrongLevel => None,
+    }
+}
+
+/// When the next answer can be submitted, remembered between runs in `inputs/`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cooldown {
+    until: SystemTime,
+}
+
+impl Cooldown {
+    fn path() -> PathBuf {
+        fetch::inputs_dir().join("submit-cooldown")
+    }
+
+    /// The last cooldown saved, if there's been one
+    pub fn load() -> Option<Self> {
+        let seconds = fs::read_to_string(Self::path()).ok()?.trim().parse().ok()?;
+        Some(Self {
+            until: UNIX_EPOCH + Duration::from_secs(seconds),
+        })
+    }
+
+    fn save(&self) -> Result<(), String> {
+        let path = Self::path();
+        let seconds = self
+            .until
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |since| since.as_secs());
+        let dir = path.parent().expect("cooldown is inside inputs/");
+        fs::create_dir_all(dir).map_err(|e| format!("couldn't create {}: {}", dir.display(), e))?;
+        fs::write(&path, seconds.to_string())
+            .map_err(|e| format!("couldn't write {}: {}", path.display(), e))
+    }
+
+    /// How much longer there is to wait after `now`, if any
+    pub fn remaining(&self, now: SystemTime) -> Option<Duration> {
+        self.until
+            .duration_since(now)
+            .ok()
+            .filter(|wait| !wait.is_zero())
+    }
+}
+
+/// Posts `answer` for one part of a day, refusing to if the last answer was too recent
+pub fn submit(day: u8, part: u8, answer: &str) -> Result<Response, String> {
+    if let Some(wait) = Cooldown::load().and_then(|cooldown| cooldown.remaining(SystemTime::now()))
+    {
+        return Err(format!(
+            "adventofcode.com needs {}s more before another answer",
+            wait.as_secs() + 1
+        ));
+    }
+    let session = fetch::session().ok_or_else(|| {
+        format!(
+            "set {} to your adventofcode.com session cookie to submit answers",
+            SESSION_VAR
+        )
+    })?;
+    let url = format!("https://adventofcode.com/2021/day/{}/answer", day);
+    let html = ureq::post(&url)
+        .set("Cookie", &format!("session={}", session))
+        .set("User-Agent", USER_AGENT)
+        .send_form(&[("level", &part.to_string()), ("answer", answer)])
+        .map_err(|e| format!("couldn't submit to adventofcode.com: {}", e))?
+        .into_string()
+        .map_err(|e| format!("couldn't read adventofcode.com's reply: {}", e))?;
+    let response = parse_response(&html)?;
+    if let Some(wait) = wait_after(response, &strip_tags(article_text(&html))) {
+        Cooldown {
+            until: SystemTime::now() + wait,
+        }
+        .save()?;
+    }
+    Ok(response)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn page(article: &str) -> String {
+        format!(
+            "<html><body><main><article><p>{}</p></article></main></body></html>",
+            article
+        )
+    }
+
+    #[test]
+    fn parses_responses() {
+        assert_eq!(
+            parse_response(&page(
+                "That's the right answer!  You are <span class=\"day-success\">one gold star</span> closer."
+            )),
+            Ok(Response::Correct)
+        );
+        assert_eq!(
+            parse_response(&page(
+                "That's not the right answer; your answer is too high.  If you're stuck, make sure you're using the full input data. Please wait one minute before trying again. <a href=\"/2021/day/16\">[Return to Day 16]</a>"
+            )),
+            Ok(Response::TooHigh)
+        );
+        assert_eq!(
+            parse_response(&page(
+                "That's not the right answer; your answer is too low."
+            )),
+            Ok(Response::TooLow)
+        );
+        assert_eq!(
+            parse_response(&page("That's not the right answer.")),
+            Ok(Response::Wrong)
+        );
+        assert_eq!(
+            parse_response(&page(
+                "You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 5s left to wait."
+            )),
+            Ok(Response::TooSoon(Duration::from_secs(65)))
+        );
+        assert_eq!(
+            parse_response(&page(
+                "You don't seem to be solving the right level.  Did you already complete it?"
+            )),
+            Ok(Response::WrongLevel)
+        );
+        assert!(parse_response("<html>Puzzle inputs differ by user.</html>").is_err());
+    }
+
+    #[test]
+    fn waits_after_wrong_answers() {
+        let text = "That's not the right answer. Because you have guessed incorrectly 4 times on this puzzle, please wait 5 minutes before trying again.";
+        assert_eq!(
+            wait_after(Response::Wrong, text),
+            Some(Duration::from_secs(300))
+        );
+        assert_eq!(
+            wait_after(
+                Response::TooLow,
+                "please wait one minute before trying again"
+            ),
+            Some(Duration::from_secs(60))
+        );
+        assert_eq!(
+            wait_after(Response::TooHigh, "That's not the right answer"),
+            Some(WRONG_ANSWER_WAIT)
+        );
+        assert_eq!(wait_after(Response::Correct, ""), None);
+        assert_eq!(parse_wait("35s"), Some(Duration::from_secs(35)));
+        assert_eq!(parse_wait("nothing"), None);
+    }
+
+    #[test]
+    fn cooldown_remaining() {
+        let now = SystemTime::now();
+        let cooldown = Cooldown {
+            until: now + Duration::from_secs(30),
+        };
+        assert_eq!(cooldown.remaining(now), Some(Duration::from_secs(30)));
+        assert_eq!(cooldown.remaining(now + Duration::from_secs(31)), None);
+    }
+}
@@ -0,0 +1,198 @@
+//! Gives up on solvers that take too long, so one stuck day doesn't hold up a whole run
+//!
+//! Threads can't be killed, so a day with a timeout is solved in a child process instead,
+//! which is killed if it runs over.
+
+use std::{
+    collections::BTreeMap,
+    io::{self, Write},
+    panic,
+    process::{ChildStdout, Command, ExitStatus, Stdio},
+    sync::mpsc::{self, RecvTimeoutError},
+    thread,
+    time::Duration,
+};
+
+/// One `--timeout`, either `SECS` for every day or `DAY=SECS` for just one
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Limit {
+    All(Duration),
+    Day(u8, Duration),
+}
+
+fn seconds(arg: &str) -> Result<Duration, String> {
+    arg.parse()
+        .ok()
+        .and_then(|secs| Duration::try_from_secs_f64(secs).ok())
+        .filter(|duration| !duration.is_zero())
+        .ok_or_else(|| format!("`{}` isn't a positive number of seconds", arg))
+}
+
+/// Parses `--timeout 30` or `--timeout 12=5`
+pub fn parse_limit(arg: &str) -> Result<Limit, String> {
+    match arg.split_once('=') {
+        Some((day, secs)) => {
+            let day = day
+                .parse()
+                .map_err(|_| format!("`{}` isn't a day number", day))?;
+            Ok(Limit::Day(day, seconds(secs)?))
+        }
+        None => seconds(arg).map(Limit::All),
+    }
+}
+
+/// How long each day gets, from every `--timeout` given
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Timeouts {
+    all: Option<Duration>,
+    days: BTreeMap<u8, Duration>,
+}
+
+impl Timeouts {
+    /// Later limits replace earlier ones for the same day
+    pub fn new(limits: &[Limit]) -> Self {
+        let mut timeouts = Self::default();
+        for limit in limits {
+            match *limit {
+                Limit::All(duration) => timeouts.all = Some(duration),
+                Limit::Day(day, duration) => {
+                    timeouts.days.insert(day, duration);
+                }
+            }
+        }
+        timeouts
+    }
+
+    /// The day's own limit, or else the one for every day
+    pub fn for_day(&self, day: u8) -> Option<Duration> {
+        self.days.get(&day).copied().or(self.all)
+    }
+}
+
+/// Starts `command` with `input` on its stdin, and hands its stdout to `read` on a thread of
+/// its own. Returns how it exited and what `read` made of its output, or `None` if it hadn't
+/// finished within `timeout`, in which case it's been killed. A panic in `read` carries on in
+/// the caller, after the child's been killed.
+pub fn run<T: Send + 'static>(
+    command: &mut Command,
+    input: &str,
+    timeout: Duration,
+    read: impl FnOnce(ChildStdout) -> T + Send + 'static,
+) -> io::Result<Option<(ExitStatus, T)>> {
+    let mut child = command
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()?;
+    let mut stdin = child.stdin.take().expect("stdin is piped");
+    let input = input.to_string();
+    // On its own thread, so a child that answers before reading it all can't hold us up
+    thread::spawn(move || {
+        // A child that's finished or been killed has no more use for it
+        let _ = stdin.write_all(input.as_bytes());
+    });
+    let stdout = child.stdout.take().expect("stdout is piped");
+    let (sender, receiver) = mpsc::channel();
+    let reading = thread::spawn(move || {
+        let _ = sender.send(read(stdout));
+    });
+    // The output ends when the child exits
+    match receiver.recv_timeout(timeout) {
+        Ok(value) => Ok(Some((child.wait()?, value))),
+        Err(RecvTimeoutError::Timeout) => {
+            child.kill()?;
+            child.wait()?;
+            Ok(None)
+        }
+        Err(RecvTimeoutError::Disconnected) => {
+            let _ = child.kill();
+            let _ = child.wait();
+            match reading.join() {
+                Err(payload) => panic::resume_unwind(payload),
+                Ok(()) => unreachable!("the thread only exits without sending if read panics"),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Read;
+
+    use super::*;
+
+    #[test]
+    fn parses_limits() {
+        assert_eq!(parse_limit("30"), Ok(Limit::All(Duration::from_secs(30))));
+        assert_eq!(
+            parse_limit("12=0.5"),
+            Ok(Limit::Day(12, Duration::from_millis(500)))
+        );
+        assert!(parse_limit("0").is_err());
+        assert!(parse_limit("-1").is_err());
+        assert!(parse_limit("twelve=5").is_err());
+        assert!(parse_limit("12=").is_err());
+    }
+
+    #[test]
+    fn day_limits_override_the_default() {
+        let timeouts = Timeouts::new(&[
+            Limit::Day(12, Duration::from_secs(5)),
+            Limit::All(Duration::from_secs(30)),
+            Limit::Day(12, Duration::from_secs(2)),
+        ]);
+        assert_eq!(timeouts.for_day(12), Some(Duration::from_secs(2)));
+        assert_eq!(timeouts.for_day(1), Some(Duration::from_secs(30)));
+        assert_eq!(Timeouts::default().for_day(1), None);
+    }
+
+    fn read_all(mut output: ChildStdout) -> String {
+        let mut read = String::new();
+        output.read_to_string(&mut read).unwrap();
+        read
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn passes_input_and_output_through() {
+        let (status, output) = run(
+            &mut Command::new("cat"),
+            "7\n",
+            Duration::from_secs(30),
+            read_all,
+        )
+        .unwrap()
+        .unwrap();
+        assert!(status.success());
+        assert_eq!(output, "7\n");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn kills_slow_children() {
+        let marker = std::env::temp_dir().join(format!("aoc-timeout-{}", std::process::id()));
+        let mut command = Command::new("sh");
+        command
+            .arg("-c")
+            .arg(format!("sleep 1; touch {}", marker.display()));
+        assert!(run(&mut command, "", Duration::from_millis(50), read_all)
+            .unwrap()
+            .is_none());
+        // It would have got this far by now if it were still running
+        thread::sleep(Duration::from_millis(1500));
+        assert!(!marker.exists());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn passes_panics_on() {
+        let result = panic::catch_unwind(|| {
+            run(
+                &mut Command::new("true"),
+                "",
+                Duration::from_secs(30),
+                |_| panic!("reader bug"),
+            )
+        });
+        assert!(result.is_err());
+    }
+}
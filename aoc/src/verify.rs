@@ -0,0 +1,307 @@
+//! Checks solutions against the answers recorded in `answers.toml`
+//!
+//! The file holds one table per day, with the answers as strings (or plain integers):
+//!
+//! ```toml
+//! [day1]
+//! part1 = "1532"
+//! part2 = 1571
+//! ```
+
+use std::{
+    collections::BTreeMap,
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Deserializer, Serialize};
+
+use crate::{fetch, report::Report};
+
+/// Where answers are kept by default, in the [`fetch::cache_dir`] next to `inputs/`
+pub fn default_path() -> PathBuf {
+    fetch::cache_dir().join("answers.toml")
+}
+
+/// Accepts `part1 = 1532` as well as `part1 = "1532"`
+fn answer<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<String>, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Stored {
+        Text(String),
+        Number(i64),
+    }
+    Ok(
+        Option::<Stored>::deserialize(deserializer)?.map(|stored| match stored {
+            Stored::Text(text) => text,
+            Stored::Number(number) => number.to_string(),
+        }),
+    )
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DayAnswers {
+    #[serde(
+        default,
+        deserialize_with = "answer",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub part1: Option<String>,
+    #[serde(
+        default,
+        deserialize_with = "answer",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub part2: Option<String>,
+}
+
+impl DayAnswers {
+    fn part(&self, part: u8) -> Option<&String> {
+        match part {
+            1 => self.part1.as_ref(),
+            _ => self.part2.as_ref(),
+        }
+    }
+
+    fn part_mut(&mut self, part: u8) -> &mut Option<String> {
+        match part {
+            1 => &mut self.part1,
+            _ => &mut self.part2,
+        }
+    }
+}
+
+/// The contents of `answers.toml`, keyed by `dayN`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Answers(BTreeMap<String, DayAnswers>);
+
+impl Answers {
+    /// Reads answers from `path`, or starts with none if it doesn't exist yet
+    pub fn load(path: &Path) -> Result<Self, String> {
+        match fs::read_to_string(path) {
+            Ok(text) => text.parse(),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(format!("couldn't read {}: {}", path.display(), e)),
+        }
+        .map_err(|e| format!("{}: {}", path.display(), e))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        let text = toml::to_string(self).expect("answers always serialize");
+        fs::write(path, text).map_err(|e| format!("couldn't write {}: {}", path.display(), e))
+    }
+
+    pub fn get(&self, day: u8, part: u8) -> Option<&String> {
+        self.0.get(&format!("day{}", day))?.part(part)
+    }
+
+    pub fn set(&mut self, day: u8, part: u8, answer: String) {
+        *self
+            .0
+            .entry(format!("day{}", day))
+            .or_default()
+            .part_mut(part) = Some(answer);
+    }
+}
+
+impl std::str::FromStr for Answers {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        toml::from_str(s).map_err(|e| e.to_string())
+    }
+}
+
+/// How one part's answer compares to the recorded one
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    Correct,
+    Wrong {
+        expected: String,
+        got: String,
+    },
+    /// There's no recorded answer to compare against
+    Unknown(String),
+    Failed(String),
+}
+
+impl Outcome {
+    /// Whether this is a regression that should fail the run
+    pub fn is_failure(&self) -> bool {
+        matches!(self, Self::Wrong { .. } | Self::Failed(_))
+    }
+}
+
+/// One line of output, shown compactly if it spans several (like day 13's)
+fn short(answer: &str) -> String {
+    match answer.lines().count() {
+        0 | 1 => answer.to_string(),
+        lines => format!("({} lines)", lines),
+    }
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Correct => write!(f, "ok"),
+            Self::Wrong { expected, got } => {
+                write!(f, "WRONG: expected {}, got {}", short(expected), short(got))
+            }
+            Self::Unknown(got) => write!(f, "unknown: got {}", short(got)),
+            Self::Failed(e) => write!(f, "FAILED: {}", e),
+        }
+    }
+}
+
+/// A day's outcome for each part
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Verdict {
+    pub day: u8,
+    pub parts: [Outcome; 2],
+}
+
+/// Compares the result of solving a day against the recorded answers
+pub fn check(day: u8, result: &Result<Report, String>, answers: &Answers) -> Verdict {
+    let parts = [1, 2].map(|part| {
+        let report = match result {
+            Ok(report) => report,
+            Err(e) => return Outcome::Failed(e.clone()),
+        };
+        let got = match part {
+            1 => &report.part1,
+            _ => &report.part2,
+        };
+        let got = got.as_ref().expect("verify solves both parts").to_string();
+        match answers.get(day, part) {
+            Some(expected) if expected.trim_end() == got.trim_end() => Outcome::Correct,
+            Some(expected) => Outcome::Wrong {
+                expected: expected.clone(),
+                got,
+            },
+            None => Outcome::Unknown(got),
+        }
+    });
+    Verdict { day, parts }
+}
+
+/// Fills in answers that weren't recorded yet, returning how many were added
+pub fn record(verdicts: &[Verdict], answers: &mut Answers) -> usize {
+    let mut added = 0;
+    for verdict in verdicts {
+        for (part, outcome) in (1..).zip(&verdict.parts) {
+            if let Outcome::Unknown(got) = outcome {
+                answers.set(verdict.day, part, got.clone());
+                added += 1;
+            }
+        }
+    }
+    added
+}
+
+/// One line per day, then a count of each kind of outcome
+pub fn summary(verdicts: &[Verdict]) -> String {
+    let mut text = String::new();
+    for verdict in verdicts {
+        for (part, outcome) in (1..).zip(&verdict.parts) {
+            text.push_str(&format!(
+                "day{:<2} part{}  {}\n",
+                verdict.day, part, outcome
+            ));
+        }
+    }
+    let outcomes: Vec<&Outcome> = verdicts.iter().flat_map(|v| &v.parts).collect();
+    let count = |f: fn(&Outcome) -> bool| outcomes.iter().filter(|o| f(o)).count();
+    text.push_str(&format!(
+        "{} ok, {} wrong, {} failed, {} unknown\n",
+        count(|o| matches!(o, Outcome::Correct)),
+        count(|o| matches!(o, Outcome::Wrong { .. })),
+        count(|o| matches!(o, Outcome::Failed(_))),
+        count(|o| matches!(o, Outcome::Unknown(_))),
+    ));
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_common::answer::Answer;
+
+    use super::*;
+
+    fn report(day: u8, part1: u64, part2: u64) -> Result<Report, String> {
+        Ok(Report {
+            day,
+            part1: Some(Answer::Num(part1)),
+            part2: Some(Answer::Num(part2)),
+            elapsed_ms: 0.0,
+        })
+    }
+
+    #[test]
+    fn parses_strings_and_integers() {
+        let answers: Answers = "[day1]\npart1 = \"1532\"\npart2 = 1571\n\n[day13]\npart1 = 737\n"
+            .parse()
+            .unwrap();
+        assert_eq!(answers.get(1, 1).map(String::as_str), Some("1532"));
+        assert_eq!(answers.get(1, 2).map(String::as_str), Some("1571"));
+        assert_eq!(answers.get(13, 2), None);
+        assert_eq!(answers.get(2, 1), None);
+        assert!("[day1]\npart1 = [1]".parse::<Answers>().is_err());
+    }
+
+    #[test]
+    fn round_trips_multiline_answers() {
+        let mut answers = Answers::default();
+        answers.set(13, 2, String::from("#.\n.#"));
+        answers.set(2, 1, String::from("150"));
+        let text = toml::to_string(&answers).unwrap();
+        assert_eq!(text.parse::<Answers>().unwrap(), answers);
+    }
+
+    #[test]
+    fn reports_regressions() {
+        let answers: Answers = "[day1]\npart1 = 7\npart2 = 5\n[day2]\npart1 = 150"
+            .parse()
+            .unwrap();
+        let verdicts = vec![
+            check(1, &report(1, 7, 6), &answers),
+            check(2, &report(2, 150, 900), &answers),
+            check(3, &Err(String::from("day3 part1: bad")), &answers),
+        ];
+        assert_eq!(verdicts[0].parts[0], Outcome::Correct);
+        assert_eq!(
+            verdicts[0].parts[1],
+            Outcome::Wrong {
+                expected: String::from("5"),
+                got: String::from("6")
+            }
+        );
+        assert_eq!(verdicts[1].parts[1], Outcome::Unknown(String::from("900")));
+        assert!(verdicts[2].parts.iter().all(Outcome::is_failure));
+        assert_eq!(
+            summary(&verdicts),
+            "\
+day1  part1  ok
+day1  part2  WRONG: expected 5, got 6
+day2  part1  ok
+day2  part2  unknown: got 900
+day3  part1  FAILED: day3 part1: bad
+day3  part2  FAILED: day3 part1: bad
+2 ok, 1 wrong, 2 failed, 1 unknown
+"
+        );
+    }
+
+    #[test]
+    fn records_only_unknown_answers() {
+        let mut answers: Answers = "[day1]\npart1 = 7\npart2 = 5".parse().unwrap();
+        let verdicts = vec![
+            check(1, &report(1, 7, 6), &answers),
+            check(2, &report(2, 150, 900), &answers),
+        ];
+        assert_eq!(record(&verdicts, &mut answers), 2);
+        assert_eq!(answers.get(1, 2).map(String::as_str), Some("5"));
+        assert_eq!(answers.get(2, 2).map(String::as_str), Some("900"));
+    }
+}
@@ -0,0 +1,42 @@
+//! Solving with `--timeout`, which goes through a child process of the runner
+
+use std::{
+    io::Write,
+    process::{Command, Output, Stdio},
+};
+
+/// Runs the runner on day 1 with `input` on stdin
+fn run_day1(input: &str, args: &[&str]) -> Output {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_aoc"))
+        .args(["run", "--day", "1", "--input", "-", "--json"])
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("the runner should start");
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(input.as_bytes())
+        .unwrap();
+    child.wait_with_output().unwrap()
+}
+
+#[test]
+fn solves_within_a_timeout() {
+    let output = run_day1("1\n2\n3\n4\n", &["--timeout", "30"]);
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains(r#""part1":"3","part2":"1""#), "{}", stdout);
+}
+
+#[test]
+fn fails_the_same_way_within_a_timeout() {
+    let timed = run_day1("not depths", &["--timeout", "30"]);
+    let untimed = run_day1("not depths", &[]);
+    assert!(!timed.status.success());
+    assert_eq!(timed.status.code(), untimed.status.code());
+    assert_eq!(timed.stderr, untimed.stderr);
+}
@@ -0,0 +1,224 @@
+use aoc_common::{answer::Answer, increases_with_lag};
+use std::{
+    io::{self, BufRead},
+    num::ParseIntError,
+};
+
+pub const INPUT: &str = include_str!("input.txt");
+
+/// Number of depth increases
+pub fn part1(input: &str) -> Result<Answer, String> {
+    let input = parse_input(input).map_err(|err| err.to_string())?;
+    Ok(solve_part1(&input).into())
+}
+
+/// Number of three-measurement window increases
+pub fn part2(input: &str) -> Result<Answer, String> {
+    let input = parse_input(input).map_err(|err| err.to_string())?;
+    Ok(solve_part2(&input).into())
+}
+
+aoc_common::register_day!(1);
+
+/// Parses one depth measurement per line
+pub fn parse_input(input: &str) -> Result<Vec<i32>, ParseIntError> {
+    input.lines().map(|line| line.parse()).collect()
+}
+
+/// Number of measurements that are deeper than the one before
+pub fn solve_part1(input: &[i32]) -> usize {
+    increases_with_lag(input, 1)
+}
+
+/// Number of three-measurement windows whose sum is larger than the window before
+pub fn solve_part2(input: &[i32]) -> usize {
+    increases_with_lag(input, 3)
+}
+
+/// Counts how many times the sum of a sliding window of `window` measurements is larger than the
+/// sum of the window before it. A window of 1 compares each measurement against the last.
+///
+/// This is the fast path for [`count_increases_by`] with a sum aggregate; see
+/// [`increases_with_lag`] for why no sums need to be computed.
+///
+/// # Panics
+///
+/// Panics if `window` is 0.
+pub fn count_increases_windowed<I, T>(iter: I, window: usize) -> usize
+where
+    I: IntoIterator<Item = T>,
+    T: Ord,
+{
+    increases_with_lag(iter, window)
+}
+
+/// Counts how many times `agg` of a sliding window of `window` measurements is larger than `agg`
+/// of the window before it.
+///
+/// Every window is aggregated in full, so prefer [`count_increases_windowed`] when `agg` is a
+/// sum. See [`aggregate`] for some ready-made aggregates.
+///
+/// # Panics
+///
+/// Panics if `window` is 0.
+pub fn count_increases_by<I, T, F>(iter: I, window: usize, agg: F) -> usize
+where
+    I: IntoIterator<Item = T>,
+    T: PartialOrd,
+    F: Fn(&[T]) -> T,
+{
+    assert!(window > 0, "window must be at least 1");
+    let values: Vec<T> = iter.into_iter().collect();
+    let aggregates: Vec<T> = values.windows(window).map(agg).collect();
+    aggregates
+        .windows(2)
+        .filter(|pair| pair[1] > pair[0])
+        .count()
+}
+
+/// Window aggregates for use with [`count_increases_by`]. Windows are never empty.
+pub mod aggregate {
+    use std::iter::Sum;
+
+    pub fn sum<T: Copy + Sum<T>>(window: &[T]) -> T {
+        window.iter().copied().sum()
+    }
+
+    pub fn max<T: Copy + Ord>(window: &[T]) -> T {
+        *window.iter().max().expect("windows are never empty")
+    }
+
+    /// The middle value of the window, or the lower of the two middle values for even windows
+    pub fn median<T: Copy + Ord>(window: &[T]) -> T {
+        let mut sorted = window.to_vec();
+        sorted.sort_unstable();
+        sorted[(sorted.len() - 1) / 2]
+    }
+
+    pub fn mean(window: &[f64]) -> f64 {
+        window.iter().sum::<f64>() / window.len() as f64
+    }
+}
+
+/// Same as [`count_increases_windowed`], but consumes the measurements lazily, keeping only the
+/// last `window` values so each incoming value can be compared against the one falling out of
+/// the window. [`increases_with_lag`] already works this way, so this just hands over to it.
+///
+/// # Panics
+///
+/// Panics if `window` is 0.
+pub fn count_increases_streaming<I>(iter: I, window: usize) -> usize
+where
+    I: IntoIterator<Item = i64>,
+{
+    increases_with_lag(iter, window)
+}
+
+/// Streams one measurement per line out of `reader`, in constant memory.
+///
+/// Blank lines are skipped. Lines that aren't integers are reported as
+/// [`io::ErrorKind::InvalidData`].
+pub fn count_increases_from_reader<R: BufRead>(reader: R, window: usize) -> io::Result<usize> {
+    let mut error = None;
+    let values = reader
+        .lines()
+        .enumerate()
+        .map_while(|(idx, line)| {
+            let parsed = line.and_then(|line| {
+                line.trim().parse::<i64>().map(Some).or_else(|err| {
+                    if line.trim().is_empty() {
+                        Ok(None)
+                    } else {
+                        Err(io::Error::new(
+                            io::ErrorKind::InvalidData,
+                            format!("line {}: {:?} is not a depth: {}", idx + 1, line, err),
+                        ))
+                    }
+                })
+            });
+            match parsed {
+                Ok(value) => Some(value),
+                Err(err) => {
+                    error = Some(err);
+                    None
+                }
+            }
+        })
+        .flatten();
+    let count = count_increases_streaming(values, window);
+    match error {
+        Some(err) => Err(err),
+        None => Ok(count),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const INPUT: [i32; 10] = [199, 200, 208, 210, 200, 207, 240, 269, 260, 263];
+
+    #[test]
+    fn test_parse_input() {
+        let input = parse_input(include_str!("test_input.txt")).unwrap();
+        assert_eq!(input, INPUT);
+        assert!(parse_input("1\ntwo").is_err());
+    }
+
+    #[test]
+    fn test_solve_part1() {
+        let expect = 7;
+        assert_eq!(solve_part1(&INPUT), expect);
+    }
+
+    #[test]
+    fn test_solve_part2() {
+        let expect = 5;
+        assert_eq!(solve_part2(&INPUT), expect);
+    }
+
+    #[test]
+    fn test_windowed() {
+        assert_eq!(count_increases_windowed(INPUT, 1), 7);
+        assert_eq!(count_increases_windowed(INPUT, 3), 5);
+        assert_eq!(count_increases_windowed(INPUT, 10), 0);
+        assert_eq!(count_increases_windowed(INPUT, 11), 0);
+        assert_eq!(count_increases_windowed([1, 2, 3, 4, 1], 4), 0);
+        assert_eq!(count_increases_windowed([1, 2, 3, 4, 5], 4), 1);
+    }
+
+    #[test]
+    fn test_streaming() {
+        for window in 1..=11 {
+            assert_eq!(
+                count_increases_streaming(INPUT.map(i64::from), window),
+                count_increases_windowed(INPUT, window)
+            );
+        }
+    }
+
+    #[test]
+    fn test_from_reader() {
+        let input = include_str!("test_input.txt");
+        assert_eq!(count_increases_from_reader(input.as_bytes(), 1).unwrap(), 7);
+        assert_eq!(count_increases_from_reader(input.as_bytes(), 3).unwrap(), 5);
+
+        let err = count_increases_from_reader("1\n2\nthree\n".as_bytes(), 1).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn test_count_increases_by() {
+        for window in 1..=11 {
+            assert_eq!(
+                count_increases_by(INPUT, window, aggregate::sum),
+                count_increases_windowed(INPUT, window)
+            );
+        }
+        let values = [1, 5, 2, 2, 3, 9];
+        assert_eq!(count_increases_by(values, 2, aggregate::max), 2);
+        assert_eq!(count_increases_by(values, 3, aggregate::median), 1);
+        let values = values.map(f64::from);
+        assert_eq!(count_increases_by(values, 2, aggregate::mean), 3);
+    }
+}
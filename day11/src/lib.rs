@@ -0,0 +1,243 @@
+use aoc_common::answer::Answer;
+use grid2d::Grid;
+
+use std::{collections::HashSet, fmt::Display, str::FromStr};
+
+#[cfg(feature = "visualize")]
+pub mod viz;
+
+pub const INPUT: &str = "\
+1326253315
+3427728113
+5751612542
+6543868322
+4422526221
+2234325647
+1773174887
+7281321674
+6562513118
+4824541522";
+
+#[derive(Debug, PartialEq, Eq, Default)]
+struct OctopusCavern {
+    octopuses: Grid<u16>,
+}
+impl OctopusCavern {
+    fn new(input: Input) -> Self {
+        Self {
+            octopuses: input.grid,
+        }
+    }
+    fn step(&mut self) -> usize {
+        let mut flashes: HashSet<(usize, usize)> = HashSet::new();
+        self.octopuses.iter_mut().for_each(|n| *n += 1);
+        loop {
+            let mut flash_coords: HashSet<(usize, usize)> = HashSet::new();
+            // Find the octopuses which are ready to flash
+            for (coords, &n) in self.octopuses.iter_coords() {
+                if n >= 10 {
+                    // Make sure they aren't already flashing
+                    if !flashes.contains(&coords) {
+                        // And insert it into this round of flashers
+                        flash_coords.insert(coords);
+                    }
+                }
+            }
+
+            // Once we know what's flashing this round, push them all into the step-wide set
+            flashes.extend(flash_coords.iter());
+            if flash_coords.is_empty() {
+                // If there aren't any more flashing octopuses this round, we're done
+                break;
+            } else {
+                // Otherwise, light up the surrounding square of each flasher
+                for (x, y) in flash_coords.into_iter() {
+                    self.octopuses[(x, y)] = 0;
+                    let neighbors: Vec<_> = self.octopuses.neighbors8(x, y).collect();
+                    for neighbor in neighbors {
+                        if !flashes.contains(&neighbor) {
+                            self.octopuses[neighbor] += 1;
+                        }
+                    }
+                }
+            }
+        }
+        flashes.len()
+    }
+}
+impl Iterator for OctopusCavern {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(self.step())
+    }
+}
+impl Display for OctopusCavern {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.octopuses)
+    }
+}
+
+#[derive(Clone)]
+pub struct Input {
+    grid: Grid<u16>,
+}
+impl FromStr for Input {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let grid = s.parse::<Grid<u16>>().map_err(|e| e.to_string())?;
+        Ok(Self { grid })
+    }
+}
+
+pub fn solve_part1(input: Input) -> u64 {
+    let mut game = OctopusCavern::new(input);
+    (0..100).fold(0, |acc, _| acc + game.step()) as u64
+}
+
+pub fn solve_part2(input: Input) -> u64 {
+    let game = OctopusCavern::new(input);
+    let octopus_count = game.octopuses.len();
+    (1u64..)
+        .zip(game)
+        .filter_map(|(i, flashes)| {
+            if flashes == octopus_count {
+                Some(i)
+            } else {
+                None
+            }
+        })
+        .next()
+        .unwrap()
+}
+
+/// Total flashes after 100 steps
+pub fn part1(input: &str) -> Result<Answer, String> {
+    let input = input.parse::<Input>()?;
+    Ok(solve_part1(input).into())
+}
+
+/// First step on which every octopus flashes at once
+pub fn part2(input: &str) -> Result<Answer, String> {
+    let input = input.parse::<Input>()?;
+    Ok(solve_part2(input).into())
+}
+
+aoc_common::register_day!(11);
+
+#[cfg(test)]
+mod test {
+    use crate::OctopusCavern;
+
+    const INPUT: &str = "\
+5483143223
+2745854711
+5264556173
+6141336146
+6357385478
+4167524645
+2176841721
+6882881134
+4846848554
+5283751526";
+
+    #[test]
+    fn solve_part1() {
+        let input = INPUT.parse().expect("Failed to parse input");
+        let part1 = super::solve_part1(input);
+        let expected = 1656;
+        assert_eq!(part1, expected);
+    }
+
+    #[test]
+    fn solve_part2() {
+        let input = INPUT.parse().expect("Failed to parse input");
+        let part2 = super::solve_part2(input);
+        let expected = 195;
+        assert_eq!(part2, expected);
+    }
+
+    #[test]
+    fn assert_force_flashes() {
+        let mut game = OctopusCavern::new(
+            "\
+11111
+19991
+19191
+19991
+11111"
+                .parse()
+                .unwrap(),
+        );
+        game.step();
+        let expected = OctopusCavern::new(
+            "\
+34543
+40004
+50005
+40004
+34543"
+                .parse()
+                .unwrap(),
+        );
+        assert_eq!(game, expected);
+    }
+
+    #[test]
+    fn flashes_on_bottom_edge() {
+        // Neighbors used to be bounds checked against a hardcoded 10x10 cavern
+        let mut game = OctopusCavern::new("989\n999".parse().unwrap());
+        assert_eq!(game.step(), 6);
+        assert_eq!(game.to_string(), "000\n000");
+    }
+
+    #[test]
+    fn step_once() {
+        let input = INPUT.parse().expect("Failed to parse input");
+        let mut game = OctopusCavern::new(input);
+        game.step();
+        {
+            let expected = OctopusCavern::new(
+                "\
+6594254334
+3856965822
+6375667284
+7252447257
+7468496589
+5278635756
+3287952832
+7993992245
+5957959665
+6394862637"
+                    .parse()
+                    .unwrap(),
+            );
+
+            assert_eq!(game, expected);
+        }
+
+        {
+            let expected = OctopusCavern::new(
+                "\
+8807476555
+5089087054
+8597889608
+8485769600
+8700908800
+6600088989
+6800005943
+0000007456
+9000000876
+8700006848"
+                    .parse()
+                    .unwrap(),
+            );
+
+            let num_flashes = game.step();
+
+            assert_eq!(num_flashes, 35);
+            assert_eq!(game, expected);
+        }
+    }
+}
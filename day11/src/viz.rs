@@ -0,0 +1,56 @@
+//! The cavern after every step, until every octopus flashes at once
+
+use aoc_viz::{Color, Frame};
+
+use crate::{Input, OctopusCavern};
+
+fn frame(cavern: &OctopusCavern, caption: String) -> Frame {
+    let octopuses = &cavern.octopuses;
+    let mut frame = Frame::new(octopuses.width(), octopuses.height()).with_caption(caption);
+    for ((x, y), &energy) in octopuses.iter_coords() {
+        let (ch, color) = match energy {
+            0 => ('*', Color::Yellow),
+            1..=3 => (digit(energy), Color::DarkGrey),
+            4..=6 => (digit(energy), Color::Grey),
+            _ => (digit(energy), Color::White),
+        };
+        frame.set(x, y, ch, Some(color));
+    }
+    frame
+}
+
+fn digit(energy: u16) -> char {
+    char::from_digit(u32::from(energy), 10).unwrap_or('+')
+}
+
+/// One frame per step, with the octopuses that just flashed lit up
+pub fn frames(input: Input) -> impl Iterator<Item = Frame> {
+    let mut cavern = OctopusCavern::new(input);
+    let everyone = cavern.octopuses.len();
+    let start = frame(&cavern, String::from("step 0"));
+    let mut synchronized = false;
+    std::iter::once(start).chain((1..).map_while(move |step| {
+        if synchronized {
+            return None;
+        }
+        let flashes = cavern.step();
+        synchronized = flashes == everyone;
+        Some(frame(
+            &cavern,
+            format!("step {}: {} flashed", step, flashes),
+        ))
+    }))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_frames_stop_when_synchronized() {
+        let frames: Vec<Frame> = frames("989\n999".parse().unwrap()).collect();
+        assert_eq!(frames.len(), 2);
+        assert_eq!(frames[0].to_string(), "989\n999\nstep 0");
+        assert_eq!(frames[1].to_string(), "***\n***\nstep 1: 6 flashed");
+    }
+}
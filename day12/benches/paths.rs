@@ -0,0 +1,61 @@
+//! Compare counting paths one branch after another with counting every branch at once, on the
+//! bundled input and on a bigger made-up system, with
+//!
+//! ```text
+//! cargo bench -p day12
+//! ```
+
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, Criterion};
+#[cfg(feature = "parallel")]
+use day12::count_paths_parallel;
+use day12::{count_paths, CaveSystem, Input, INPUT};
+
+/// `small` small caves all joined to each other and to every one of `large` large caves, with
+/// the start and end each joined to every small cave
+fn made_up(small: usize, large: usize) -> Input {
+    let smalls: Vec<String> = (0..small)
+        .map(|i| format!("s{}", (b'a' + i as u8) as char))
+        .collect();
+    let larges: Vec<String> = (0..large)
+        .map(|i| format!("L{}", (b'A' + i as u8) as char))
+        .collect();
+    let mut lines = Vec::new();
+    for (i, cave) in smalls.iter().enumerate() {
+        lines.push(format!("start-{}", cave));
+        lines.push(format!("{}-end", cave));
+        for other in &smalls[i + 1..] {
+            lines.push(format!("{}-{}", cave, other));
+        }
+        for other in &larges {
+            lines.push(format!("{}-{}", cave, other));
+        }
+    }
+    lines.join("\n").parse().expect("made-up caves parse")
+}
+
+fn paths(c: &mut Criterion) {
+    let input: Input = INPUT.parse().expect("bundled input parses");
+    let large = made_up(9, 2);
+    for (name, input) in [("input", &input), ("large", &large)] {
+        let system: &CaveSystem = input.system();
+        println!(
+            "{}: {} caves, {} paths",
+            name,
+            system.len(),
+            count_paths(system, true)
+        );
+
+        c.bench_function(&format!("day12 {} serial", name), |b| {
+            b.iter(|| count_paths(black_box(system), true))
+        });
+        #[cfg(feature = "parallel")]
+        c.bench_function(&format!("day12 {} parallel", name), |b| {
+            b.iter(|| count_paths_parallel(black_box(system), true))
+        });
+    }
+}
+
+criterion_group!(benches, paths);
+criterion_main!(benches);
@@ -0,0 +1,739 @@
+use std::collections::HashMap;
+use std::fmt::{Display, Write};
+use std::str::FromStr;
+
+use aoc_common::{answer::Answer, progress};
+use tracing::{debug, trace, warn};
+
+mod route;
+
+pub use route::cheapest_path;
+
+pub const INPUT: &str = include_str!("input.txt");
+
+/// What kind of cave it is, going by its name
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum CaveSize {
+    /// Named in lower case, and visited at most once (or twice, once per path, in part 2)
+    Small,
+    /// Named in upper case, and visited as often as a path likes
+    Large,
+    Start,
+    End,
+}
+
+impl FromStr for CaveSize {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "start" => Some(CaveSize::Start),
+            "end" => Some(CaveSize::End),
+            s if s.to_uppercase() == s => Some(CaveSize::Large),
+            s if s.to_lowercase() == s => Some(CaveSize::Small),
+            _ => None,
+        }
+        .ok_or(format!("Can't parse size from {s}"))
+    }
+}
+
+/// A cave, by the order it first turned up in the input
+#[derive(Eq, PartialEq, Debug, Clone, Copy, Hash, PartialOrd, Ord)]
+pub struct CaveId(u16);
+
+impl CaveId {
+    fn index(self) -> usize {
+        self.0.into()
+    }
+}
+
+#[derive(Eq, PartialEq, Debug, Clone)]
+struct Cave {
+    size: CaveSize,
+    paths: Vec<CaveId>,
+    /// What each of `paths` costs to take
+    weights: Vec<u32>,
+}
+
+/// Every cave, known by its [`CaveId`], with their names kept to one side
+#[derive(Debug, Clone, Default)]
+pub struct CaveSystem {
+    /// Each cave, by id
+    caves: Vec<Cave>,
+    /// Each cave's name, by id
+    names: Vec<String>,
+    ids: HashMap<String, CaveId>,
+}
+
+impl CaveSystem {
+    /// The id of the cave called `name`, adding it if it's new. `line` is only for errors.
+    fn intern(&mut self, name: &str, line: usize) -> Result<CaveId, CaveSystemError> {
+        if let Some(&id) = self.ids.get(name) {
+            return Ok(id);
+        }
+        let id = CaveId(
+            self.caves
+                .len()
+                .try_into()
+                .map_err(|_| CaveSystemError::TooManyCaves { line })?,
+        );
+        let size = name.parse().map_err(|_| CaveSystemError::BadName {
+            line,
+            name: name.to_string(),
+        })?;
+        self.caves.push(Cave {
+            size,
+            paths: Vec::new(),
+            weights: Vec::new(),
+        });
+        self.names.push(name.to_string());
+        self.ids.insert(name.to_string(), id);
+        Ok(id)
+    }
+
+    /// The cave called `name`, if there is one
+    pub fn id(&self, name: &str) -> Option<CaveId> {
+        self.ids.get(name).copied()
+    }
+
+    pub fn name(&self, id: CaveId) -> &str {
+        &self.names[id.index()]
+    }
+
+    pub fn size(&self, id: CaveId) -> CaveSize {
+        self.cave(id).size
+    }
+
+    /// The caves there's a passage to from `id`, once for each passage
+    pub fn neighbors(&self, id: CaveId) -> &[CaveId] {
+        &self.cave(id).paths
+    }
+
+    /// The same as [`neighbors`](Self::neighbors), with what each passage costs to take
+    pub fn passages(&self, id: CaveId) -> impl Iterator<Item = (CaveId, u32)> + '_ {
+        let cave = self.cave(id);
+        cave.paths.iter().copied().zip(cave.weights.iter().copied())
+    }
+
+    /// Every cave, in the order they first turned up in the input
+    pub fn caves(&self) -> impl Iterator<Item = CaveId> {
+        (0..self.caves.len() as u16).map(CaveId)
+    }
+
+    pub fn len(&self) -> usize {
+        self.caves.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.caves.is_empty()
+    }
+
+    fn cave(&self, id: CaveId) -> &Cave {
+        &self.caves[id.index()]
+    }
+
+    /// The caves as a graph for Graphviz's `dot`, with the start, the end, and large and
+    /// small caves each drawn differently, and each passage drawn once, labelled with its
+    /// cost if that isn't 1
+    /// ```rust
+    /// # use day12::*;
+    /// let input: Input = "start-A\nA-end".parse().unwrap();
+    /// assert_eq!(input.system().to_dot(), "\
+    /// graph caves {
+    ///     \"start\" [shape=diamond, style=filled, fillcolor=palegreen];
+    ///     \"A\" [shape=box, style=bold];
+    ///     \"end\" [shape=doublecircle, style=filled, fillcolor=salmon];
+    ///     \"start\" -- \"A\";
+    ///     \"A\" -- \"end\";
+    /// }
+    /// ");
+    /// ```
+    pub fn to_dot(&self) -> String {
+        let mut out = String::from("graph caves {\n");
+        for (name, cave) in self.names.iter().zip(&self.caves) {
+            let style = match cave.size {
+                CaveSize::Start => "shape=diamond, style=filled, fillcolor=palegreen",
+                CaveSize::End => "shape=doublecircle, style=filled, fillcolor=salmon",
+                CaveSize::Large => "shape=box, style=bold",
+                CaveSize::Small => "shape=ellipse",
+            };
+            writeln!(out, "    \"{}\" [{}];", name, style).expect("writing to a String can't fail");
+        }
+        for (from, cave) in self.caves.iter().enumerate() {
+            // Every passage is in both caves' paths, so only draw it from the first
+            let passages = cave.paths.iter().zip(&cave.weights);
+            for (to, weight) in passages.filter(|(to, _)| from < to.index()) {
+                write!(
+                    out,
+                    "    \"{}\" -- \"{}\"",
+                    self.names[from],
+                    self.name(*to)
+                )
+                .expect("writing to a String can't fail");
+                if *weight != 1 {
+                    write!(out, " [label=\"{}\"]", weight).expect("writing to a String can't fail");
+                }
+                out.push_str(";\n");
+            }
+        }
+        out.push_str("}\n");
+        out
+    }
+}
+
+impl From<Input> for CaveSystem {
+    fn from(input: Input) -> Self {
+        input.system
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Input {
+    system: CaveSystem,
+}
+
+impl Input {
+    pub fn system(&self) -> &CaveSystem {
+        &self.system
+    }
+}
+
+/// Why a cave system can't be solved
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CaveSystemError {
+    /// A cave named in neither all upper nor all lower case, on a 1-indexed line
+    BadName {
+        line: usize,
+        name: String,
+    },
+    /// A passage's cost after its `:` that isn't a whole number, on a 1-indexed line
+    BadWeight {
+        line: usize,
+        weight: String,
+    },
+    /// More caves by the 1-indexed `line` than a [`CaveId`] can number
+    TooManyCaves {
+        line: usize,
+    },
+    /// More small caves than a path can keep track of having visited
+    TooManySmallCaves {
+        found: usize,
+    },
+    MissingStart,
+    MissingEnd,
+    /// A passage between two large caves, which a path could go back and forth along forever
+    AdjacentLargeCaves {
+        line: usize,
+        from: String,
+        to: String,
+    },
+    /// A passage given again on `line`, either way round, after `first`
+    DuplicatePassage {
+        line: usize,
+        first: usize,
+        from: String,
+        to: String,
+    },
+}
+
+impl Display for CaveSystemError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::BadName { line, name } => write!(
+                f,
+                "line {}: can't tell whether `{}` is a large or small cave",
+                line, name
+            ),
+            Self::BadWeight { line, weight } => write!(
+                f,
+                "line {}: a passage can't cost `{}`, only a whole number",
+                line, weight
+            ),
+            Self::TooManyCaves { line } => {
+                write!(
+                    f,
+                    "line {}: more than {} caves",
+                    line,
+                    u16::MAX as usize + 1
+                )
+            }
+            Self::TooManySmallCaves { found } => write!(
+                f,
+                "{} small caves, but there can't be more than {}",
+                found,
+                u64::BITS
+            ),
+            Self::MissingStart => write!(f, "there's no `start` cave"),
+            Self::MissingEnd => write!(f, "there's no `end` cave"),
+            Self::AdjacentLargeCaves { line, from, to } => write!(
+                f,
+                "line {}: large caves `{}` and `{}` are joined, so there are endless paths",
+                line, from, to
+            ),
+            Self::DuplicatePassage {
+                line,
+                first,
+                from,
+                to,
+            } => write!(
+                f,
+                "line {}: `{}-{}` was already given on line {}",
+                line, from, to, first
+            ),
+        }
+    }
+}
+
+impl std::error::Error for CaveSystemError {}
+
+/// Parses one passage per line, `from-to`, or `from-to:cost` for one that costs more (or
+/// less) than 1 to take, skipping lines that aren't a passage. Then makes sure the paths
+/// through it can be counted.
+impl FromStr for Input {
+    type Err = CaveSystemError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut system = CaveSystem::default();
+        // The line each passage was first given on, by its caves in order
+        let mut passages = HashMap::new();
+
+        for (idx, line) in s.lines().enumerate() {
+            let line_no = idx + 1;
+            if let Some((from_name, to)) = line.split_once('-') {
+                let (to_name, weight) = match to.split_once(':') {
+                    Some((to_name, weight)) => (
+                        to_name,
+                        weight.parse().map_err(|_| CaveSystemError::BadWeight {
+                            line: line_no,
+                            weight: weight.to_string(),
+                        })?,
+                    ),
+                    None => (to, 1),
+                };
+                let from = system.intern(from_name, line_no)?;
+                let to = system.intern(to_name, line_no)?;
+                if system.size(from) == CaveSize::Large && system.size(to) == CaveSize::Large {
+                    return Err(CaveSystemError::AdjacentLargeCaves {
+                        line: line_no,
+                        from: from_name.to_string(),
+                        to: to_name.to_string(),
+                    });
+                }
+                if let Some(&first) = passages.get(&(from.min(to), from.max(to))) {
+                    return Err(CaveSystemError::DuplicatePassage {
+                        line: line_no,
+                        first,
+                        from: from_name.to_string(),
+                        to: to_name.to_string(),
+                    });
+                }
+                passages.insert((from.min(to), from.max(to)), line_no);
+                system.caves[from.index()].paths.push(to);
+                system.caves[from.index()].weights.push(weight);
+                system.caves[to.index()].paths.push(from);
+                system.caves[to.index()].weights.push(weight);
+            } else if !line.trim().is_empty() {
+                warn!(
+                    line = line_no,
+                    "skipping `{}`, which isn't a `from-to` path", line
+                );
+            }
+        }
+        if system.id("start").is_none() {
+            return Err(CaveSystemError::MissingStart);
+        }
+        if system.id("end").is_none() {
+            return Err(CaveSystemError::MissingEnd);
+        }
+        let small = system
+            .caves
+            .iter()
+            .filter(|cave| cave.size == CaveSize::Small)
+            .count();
+        if small > u64::BITS as usize {
+            return Err(CaveSystemError::TooManySmallCaves { found: small });
+        }
+        debug!(caves = system.caves.len(), "parsed cave system");
+        Ok(Self { system })
+    }
+}
+
+/// Counts paths through a [`CaveSystem`], remembering how many ways on there are from each
+/// state it's counted
+struct PathCounter<'a> {
+    system: &'a CaveSystem,
+    /// Each small cave's own bit in a mask of the small caves visited so far, zero for the rest
+    bits: Vec<u64>,
+    memo: HashMap<(CaveId, u64, bool), u64>,
+}
+
+impl<'a> PathCounter<'a> {
+    fn new(system: &'a CaveSystem) -> Self {
+        let mut small = 0;
+        let bits = system
+            .caves
+            .iter()
+            .map(|cave| match cave.size {
+                CaveSize::Small => {
+                    small += 1;
+                    1u64.checked_shl(small - 1)
+                        .expect("Parsing makes sure there aren't more than 64 small caves")
+                }
+                _ => 0,
+            })
+            .collect();
+        Self {
+            system,
+            bits,
+            memo: HashMap::new(),
+        }
+    }
+
+    /// What's been visited and revisited after stepping into `next`, or `None` if the rules
+    /// don't allow it
+    fn step(&self, next: CaveId, visited: u64, revisited: bool) -> Option<(u64, bool)> {
+        let bit = self.bits[next.index()];
+        match self.system.cave(next).size {
+            CaveSize::Start => None,
+            CaveSize::Small if visited & bit != 0 => (!revisited).then_some((visited, true)),
+            _ => Some((visited | bit, revisited)),
+        }
+    }
+
+    /// How many ways there are on to the end from `cave`, having visited the small caves in
+    /// `visited`, and revisited one of them already if `revisited`. Every way on from the
+    /// same place with the same caves behind it is the same, however it got there, so each
+    /// count is remembered.
+    fn count(&mut self, cave: CaveId, visited: u64, revisited: bool) -> u64 {
+        if self.system.cave(cave).size == CaveSize::End {
+            return 1;
+        }
+        if let Some(&paths) = self.memo.get(&(cave, visited, revisited)) {
+            return paths;
+        }
+        progress::advance(1);
+        let system = self.system;
+        let mut paths = 0;
+        for &next in &system.cave(cave).paths {
+            if let Some((visited, revisited)) = self.step(next, visited, revisited) {
+                paths += self.count(next, visited, revisited);
+            }
+        }
+        trace!(
+            cave = system.name(cave),
+            visited,
+            revisited,
+            paths,
+            "counted"
+        );
+        self.memo.insert((cave, visited, revisited), paths);
+        paths
+    }
+}
+
+fn start_of(system: &CaveSystem) -> CaveId {
+    system
+        .id("start")
+        .expect("Parsing makes sure there's a start")
+}
+
+/// How many paths there are from the start to the end, visiting small caves at most once,
+/// except for one that may be visited twice if `may_revisit`. Two large caves next to each
+/// other would make that endless, and the count never finishes.
+pub fn count_paths(system: &CaveSystem, may_revisit: bool) -> u64 {
+    let start = start_of(system);
+    let mut counter = PathCounter::new(system);
+    // There's no telling up front how many ways there'll be to reach each cave
+    progress::start(None);
+    let paths = counter.count(start, 0, !may_revisit);
+    debug!(states = counter.memo.len(), paths, "explored the caves");
+    paths
+}
+
+/// The same as [`count_paths`], but counting the paths through each of the start's
+/// neighbours at once. They don't share what they've counted, so this only pays off when
+/// there's a lot to count on every branch. Progress is reported as each branch finishes.
+#[cfg(feature = "parallel")]
+pub fn count_paths_parallel(system: &CaveSystem, may_revisit: bool) -> u64 {
+    use rayon::prelude::*;
+
+    let start = start_of(system);
+    let first = PathCounter::new(system);
+    let branches: Vec<(CaveId, u64, bool)> = system
+        .cave(start)
+        .paths
+        .iter()
+        .filter_map(|&next| {
+            let (visited, revisited) = first.step(next, 0, !may_revisit)?;
+            Some((next, visited, revisited))
+        })
+        .collect();
+    progress::start(Some(branches.len() as u64));
+    let (sender, finished) = std::sync::mpsc::channel();
+    let (paths, states) = std::thread::scope(|scope| {
+        let counting = scope.spawn(move || {
+            branches
+                .into_par_iter()
+                .map_with(sender, |sender, (cave, visited, revisited)| {
+                    let mut counter = PathCounter::new(system);
+                    let paths = counter.count(cave, visited, revisited);
+                    // The workers can't reach this thread's progress, so tell it when they're done
+                    let _ = sender.send(());
+                    (paths, counter.memo.len())
+                })
+                .reduce(|| (0, 0), |a, b| (a.0 + b.0, a.1 + b.1))
+        });
+        for () in finished {
+            progress::advance(1);
+        }
+        counting.join().expect("counting paths panicked")
+    });
+    debug!(states, paths, "explored the caves in parallel");
+    paths
+}
+
+pub fn solve_part1(input: Input) -> u64 {
+    count_paths(&input.into(), false)
+}
+
+pub fn solve_part2(input: Input) -> u64 {
+    count_paths(&input.into(), true)
+}
+
+/// Paths through the caves that visit small caves at most once
+pub fn part1(input: &str) -> Result<Answer, String> {
+    let input = input.parse::<Input>().map_err(|e| e.to_string())?;
+    Ok(solve_part1(input).into())
+}
+
+/// Paths through the caves that may visit a single small cave twice
+pub fn part2(input: &str) -> Result<Answer, String> {
+    let input = input.parse::<Input>().map_err(|e| e.to_string())?;
+    Ok(solve_part2(input).into())
+}
+
+aoc_common::register_day!(12);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const INPUT: &str = "\
+dc-end
+HN-start
+start-kj
+dc-start
+dc-HN
+LN-dc
+HN-end
+kj-sa
+kj-HN
+kj-dc";
+
+    #[test]
+    fn solve_part1() {
+        let input = INPUT.parse::<Input>().expect("Input should parse");
+        let result = super::solve_part1(input);
+        assert_eq!(result, 19);
+    }
+    #[test]
+    fn solve_part2() {
+        let input = INPUT.parse::<Input>().expect("Input should parse");
+        let result = super::solve_part2(input);
+        assert_eq!(result, 103);
+    }
+
+    #[test]
+    fn reports_progress() {
+        use aoc_common::progress::{with_progress, Event};
+        use std::sync::mpsc;
+
+        let input = INPUT.parse::<Input>().expect("Input should parse");
+        let (sender, events) = mpsc::channel();
+        with_progress(sender, || super::solve_part1(input));
+        let events: Vec<Event> = events.try_iter().collect();
+        assert_eq!(events[0], Event::Start(None));
+        // every state was a step, and there are fewer of those than paths
+        let steps = events.len() - 1;
+        assert!(0 < steps && steps < 19, "{}", steps);
+    }
+
+    #[test]
+    fn counts_the_other_examples() {
+        for (input, part1, part2) in [
+            ("start-A\nstart-b\nA-c\nA-b\nb-d\nA-end\nb-end", 10, 36),
+            (
+                "fs-end\nhe-DX\nfs-he\nstart-DX\npj-DX\nend-zg\nzg-sl\nzg-pj\npj-he\nRW-he\n\
+                 fs-DX\npj-RW\nzg-RW\nstart-pj\nhe-WI\nzg-he\npj-fs\nstart-RW",
+                226,
+                3509,
+            ),
+        ] {
+            let input = input.parse::<Input>().expect("Input should parse");
+            assert_eq!(super::solve_part1(input.clone()), part1);
+            assert_eq!(super::solve_part2(input), part2);
+        }
+    }
+
+    #[test]
+    fn walks_the_system_by_id() {
+        let input = INPUT.parse::<Input>().expect("Input should parse");
+        let system = input.system();
+        assert_eq!(system.len(), 7);
+        let names: Vec<&str> = system.caves().map(|id| system.name(id)).collect();
+        assert_eq!(names, ["dc", "end", "HN", "start", "kj", "LN", "sa"]);
+        let start = system.id("start").unwrap();
+        assert_eq!(system.size(start), CaveSize::Start);
+        let mut neighbors: Vec<&str> = system
+            .neighbors(start)
+            .iter()
+            .map(|&id| system.name(id))
+            .collect();
+        neighbors.sort_unstable();
+        assert_eq!(neighbors, ["HN", "dc", "kj"]);
+        assert!(CaveSystem::default().is_empty());
+    }
+
+    #[test]
+    fn rejects_caves_that_cant_be_counted() {
+        let error = |input: &str| input.parse::<Input>().unwrap_err();
+        assert_eq!(
+            error("start-a\na-End"),
+            CaveSystemError::BadName {
+                line: 2,
+                name: String::from("End")
+            }
+        );
+        assert_eq!(error("a-end"), CaveSystemError::MissingStart);
+        assert_eq!(error("start-a\n"), CaveSystemError::MissingEnd);
+        assert_eq!(error(""), CaveSystemError::MissingStart);
+        assert_eq!(
+            error("start-A\nA-B\nB-end"),
+            CaveSystemError::AdjacentLargeCaves {
+                line: 2,
+                from: String::from("A"),
+                to: String::from("B")
+            }
+        );
+        assert!(matches!(
+            error("start-A\nA-A\nA-end"),
+            CaveSystemError::AdjacentLargeCaves { line: 2, .. }
+        ));
+        assert_eq!(
+            error("start-a\na-end\nend-a"),
+            CaveSystemError::DuplicatePassage {
+                line: 3,
+                first: 2,
+                from: String::from("end"),
+                to: String::from("a")
+            }
+        );
+        assert_eq!(
+            error("start-a\na-end\nend-a").to_string(),
+            "line 3: `end-a` was already given on line 2"
+        );
+        assert_eq!(
+            error("start-a:x\na-end"),
+            CaveSystemError::BadWeight {
+                line: 1,
+                weight: String::from("x")
+            }
+        );
+        assert!(matches!(
+            error("start-a:-1\na-end"),
+            CaveSystemError::BadWeight { line: 1, .. }
+        ));
+        assert_eq!(
+            error("start-a:3\na-end\na-start:5"),
+            CaveSystemError::DuplicatePassage {
+                line: 3,
+                first: 1,
+                from: String::from("a"),
+                to: String::from("start")
+            }
+        );
+        let many: String = (0..65).map(|i| format!("start-x{}\n", i)).collect();
+        assert_eq!(
+            error(&(many + "x0-end")),
+            CaveSystemError::TooManySmallCaves { found: 65 }
+        );
+    }
+
+    #[test]
+    fn interns_each_cave_once() {
+        let system: CaveSystem = "start-A\nA-b\nb-start\nA-end"
+            .parse::<Input>()
+            .unwrap()
+            .into();
+        assert_eq!(system.caves.len(), 4);
+        let a = system.id("A").unwrap();
+        assert_eq!(system.name(a), "A");
+        assert_eq!(system.cave(a).size, CaveSize::Large);
+        let neighbors: Vec<&str> = system
+            .cave(a)
+            .paths
+            .iter()
+            .map(|&id| system.name(id))
+            .collect();
+        assert_eq!(neighbors, ["start", "b", "end"]);
+        assert_eq!(system.id("B"), None);
+    }
+
+    #[test]
+    fn weighs_passages_both_ways() {
+        let input = "start-a:5\na-end\nstart-end:0"
+            .parse::<Input>()
+            .expect("Input should parse");
+        let system = input.system();
+        let a = system.id("a").unwrap();
+        let passages: Vec<(&str, u32)> = system
+            .passages(a)
+            .map(|(id, weight)| (system.name(id), weight))
+            .collect();
+        assert_eq!(passages, [("start", 5), ("end", 1)]);
+        let dot = system.to_dot();
+        assert!(dot.contains("    \"start\" -- \"a\" [label=\"5\"];\n"));
+        assert!(dot.contains("    \"a\" -- \"end\";\n"));
+        assert!(dot.contains("    \"start\" -- \"end\" [label=\"0\"];\n"));
+    }
+
+    #[test]
+    fn draws_every_cave_and_passage_once() {
+        let input = INPUT.parse::<Input>().expect("Input should parse");
+        let dot = input.system().to_dot();
+        assert_eq!(dot.matches("shape=").count(), 7);
+        assert_eq!(dot.matches("shape=box").count(), 2);
+        assert_eq!(dot.matches("shape=ellipse").count(), 3);
+        assert_eq!(dot.matches(" -- ").count(), 10);
+        assert!(dot.contains("    \"end\" -- \"HN\";\n"));
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn counts_the_same_in_parallel() {
+        for input in [INPUT, super::INPUT] {
+            let system: CaveSystem = input.parse::<Input>().unwrap().into();
+            for may_revisit in [false, true] {
+                assert_eq!(
+                    count_paths_parallel(&system, may_revisit),
+                    count_paths(&system, may_revisit)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn revisits_one_small_cave_at_most() {
+        let input = "\
+start-a
+a-B
+B-end
+a-end"
+            .parse::<Input>()
+            .unwrap();
+        // start,a,end and start,a,B,end
+        assert_eq!(super::solve_part1(input.clone()), 2);
+        // and start,a,B,a,end and start,a,B,a,B,end
+        assert_eq!(super::solve_part2(input), 4);
+    }
+}
@@ -0,0 +1,102 @@
+//! The cheapest way from the start to the end, going by what each passage costs
+//!
+//! No passage costs less than nothing, so a cheapest path never has to go round in a loop,
+//! and a path that doesn't loop never visits a cave twice. That keeps to the small-cave rules
+//! of both parts without having to track what's been visited, so Dijkstra's algorithm can
+//! run over the caves themselves.
+
+use std::{cmp::Reverse, collections::BinaryHeap};
+
+use aoc_common::progress;
+use tracing::debug;
+
+use crate::{start_of, CaveId, CaveSize, CaveSystem};
+
+/// The lowest total cost of any path from the start to the end, and the caves along it,
+/// starting with the start. `None` if there's no way through.
+/// ```rust
+/// # use day12::*;
+/// let input: Input = "start-a:1\na-end:10\nstart-B:2\nB-end:2".parse().unwrap();
+/// let system = input.system();
+/// let (cost, path) = cheapest_path(system).unwrap();
+/// assert_eq!(cost, 4);
+/// let names: Vec<&str> = path.into_iter().map(|id| system.name(id)).collect();
+/// assert_eq!(names, ["start", "B", "end"]);
+/// ```
+pub fn cheapest_path(system: &CaveSystem) -> Option<(u64, Vec<CaveId>)> {
+    let start = start_of(system);
+    let mut costs = vec![u64::MAX; system.len()];
+    // The cave each one was most cheaply reached from
+    let mut previous: Vec<Option<CaveId>> = vec![None; system.len()];
+    let mut queue = BinaryHeap::from([Reverse((0, start))]);
+    costs[start.index()] = 0;
+
+    progress::start(Some(system.len() as u64));
+    while let Some(Reverse((cost, cave))) = queue.pop() {
+        if cost > costs[cave.index()] {
+            // Already reached more cheaply
+            continue;
+        }
+        progress::advance(1);
+        if system.size(cave) == CaveSize::End {
+            let mut path = vec![cave];
+            while let Some(from) = previous[path[path.len() - 1].index()] {
+                path.push(from);
+            }
+            path.reverse();
+            debug!(cost, caves = path.len(), "found the cheapest path");
+            return Some((cost, path));
+        }
+        for (next, weight) in system.passages(cave) {
+            let next_cost = cost + u64::from(weight);
+            // Paths end at the end, and never go back through the start
+            if system.size(next) != CaveSize::Start && next_cost < costs[next.index()] {
+                costs[next.index()] = next_cost;
+                previous[next.index()] = Some(cave);
+                queue.push(Reverse((next_cost, next)));
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{count_paths, Input};
+
+    use super::*;
+
+    fn names(system: &CaveSystem, path: &[CaveId]) -> Vec<String> {
+        path.iter().map(|&id| system.name(id).to_string()).collect()
+    }
+
+    #[test]
+    fn takes_the_fewest_passages_when_they_all_cost_1() {
+        let input: Input = crate::INPUT.parse().expect("Input should parse");
+        let system = input.system();
+        let (cost, path) = cheapest_path(system).unwrap();
+        assert_eq!(cost, path.len() as u64 - 1);
+        assert_eq!(path.first().copied(), system.id("start"));
+        assert_eq!(path.last().copied(), system.id("end"));
+    }
+
+    #[test]
+    fn goes_the_long_way_round_when_its_cheaper() {
+        let input: Input = "start-A:7\nA-end\nstart-b:1\nb-c:1\nc-d:1\nd-end:1\nA-d:0"
+            .parse()
+            .expect("Input should parse");
+        let system = input.system();
+        let (cost, path) = cheapest_path(system).unwrap();
+        assert_eq!(cost, 4);
+        assert_eq!(names(system, &path), ["start", "b", "c", "d", "end"]);
+        // Weights don't change how many paths there are: through A alone, A then d, A then d
+        // and back to A, and b, c and d with or without A after
+        assert_eq!(count_paths(system, false), 5);
+    }
+
+    #[test]
+    fn finds_nothing_when_the_end_is_cut_off() {
+        let input: Input = "start-a\nb-end".parse().expect("Input should parse");
+        assert_eq!(cheapest_path(input.system()), None);
+    }
+}
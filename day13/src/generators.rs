@@ -0,0 +1,41 @@
+//! Strategies for dots and fold instructions
+
+use aoc_common::strategies;
+use proptest::prelude::*;
+
+use crate::{Fold, Input, Point};
+
+pub fn point() -> impl Strategy<Value = Point> {
+    strategies::point(2000).prop_map(Point::from)
+}
+
+pub fn fold() -> impl Strategy<Value = Fold> {
+    prop_oneof![
+        (0..2000usize).prop_map(Fold::Horizontal),
+        (0..2000usize).prop_map(Fold::Vertical),
+    ]
+}
+
+proptest! {
+    #![proptest_config(strategies::config())]
+
+    #[test]
+    fn point_round_trips(point in point()) {
+        prop_assert_eq!(point.to_string().parse::<Point>(), Ok(point));
+    }
+
+    #[test]
+    fn fold_round_trips(fold in fold()) {
+        prop_assert_eq!(fold.to_string().parse::<Fold>(), Ok(fold));
+    }
+
+    #[test]
+    fn input_round_trips(
+        (points, dots) in strategies::lines_of(point()),
+        (folds, instructions) in strategies::lines_of(fold()),
+    ) {
+        let input = format!("{}\n\n{}\n", dots, instructions).parse::<Input>().unwrap();
+        prop_assert_eq!(input.points, points.into_iter().collect());
+        prop_assert_eq!(input.folds, folds);
+    }
+}
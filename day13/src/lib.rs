@@ -0,0 +1,336 @@
+use aoc_common::{answer::Answer, error::AocError};
+use aoc_parse::{
+    blank_line, coordinate, expected, finish, labeled, lines, literal,
+    nom::{
+        branch::alt,
+        sequence::{preceded, separated_pair},
+        Parser,
+    },
+    number, IResult,
+};
+use std::collections::HashSet;
+use std::fmt::Display;
+use std::str::FromStr;
+
+#[cfg(test)]
+mod generators;
+#[cfg(feature = "visualize")]
+pub mod viz;
+
+#[derive(Clone, Copy, PartialEq, Eq, Ord, PartialOrd, Debug, Hash)]
+struct Point {
+    x: u64,
+    y: u64,
+}
+
+impl From<(u64, u64)> for Point {
+    fn from((x, y): (u64, u64)) -> Self {
+        Point { x, y }
+    }
+}
+
+/// Written like the input, e.g. `6,10`
+impl Display for Point {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{},{}", self.x, self.y)
+    }
+}
+
+fn point(input: &str) -> IResult<'_, Point> {
+    coordinate.map(Point::from).parse(input)
+}
+
+impl FromStr for Point {
+    type Err = AocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        finish(s, point)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Fold {
+    Horizontal(usize),
+    Vertical(usize),
+}
+
+/// Written like the input, e.g. `fold along y=7`
+impl Display for Fold {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Horizontal(y) => write!(f, "fold along y={}", y),
+            Self::Vertical(x) => write!(f, "fold along x={}", x),
+        }
+    }
+}
+
+fn fold(input: &str) -> IResult<'_, Fold> {
+    preceded(
+        literal("fold along "),
+        expected(
+            "`x=` or `y=`",
+            alt((
+                labeled("y=", number).map(Fold::Horizontal),
+                labeled("x=", number).map(Fold::Vertical),
+            )),
+        ),
+    )(input)
+}
+
+impl FromStr for Fold {
+    type Err = AocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        finish(s, fold)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Grid(HashSet<Point>);
+impl Grid {
+    fn fold(self, fold: Fold) -> Self {
+        let fold_map: Box<dyn Fn(Point) -> Point> = match fold {
+            Fold::Horizontal(value) => Box::new(move |point: Point| -> Point {
+                if point.y > value as u64 {
+                    Point {
+                        y: (value as u64 - (point.y - value as u64)),
+                        ..point
+                    }
+                } else {
+                    point
+                }
+            }),
+            Fold::Vertical(value) => Box::new(move |point: Point| -> Point {
+                if point.x > value as u64 {
+                    Point {
+                        x: (value as u64 - (point.x - value as u64)),
+                        ..point
+                    }
+                } else {
+                    point
+                }
+            }),
+        };
+        Self(self.0.into_iter().map(fold_map).collect::<HashSet<Point>>())
+    }
+}
+
+impl Display for Grid {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let width = self
+            .0
+            .iter()
+            .max_by(|&a, &b| a.x.cmp(&b.x))
+            .map(|point| point.x + 1)
+            .unwrap();
+        // The height of the grid is the y of the furthest-down point plus 1
+        let height = self
+            .0
+            .iter()
+            .max_by(|&a, &b| a.y.cmp(&b.y))
+            .map(|point| point.y + 1)
+            .unwrap();
+
+        let mut lines: Vec<String> = Vec::new();
+        for y in 0..height {
+            let mut line = String::new();
+            for x in 0..width {
+                let ch = if self.0.contains(&Point { x, y }) {
+                    '#'
+                } else {
+                    '.'
+                };
+                line.push(ch);
+            }
+            lines.push(line);
+        }
+
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+impl From<Input> for Grid {
+    fn from(input: Input) -> Self {
+        Grid(input.points)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Input {
+    points: HashSet<Point>,
+    folds: Vec<Fold>,
+}
+
+/// The dots, a blank line, then the folds
+impl FromStr for Input {
+    type Err = AocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (points, folds) = finish(
+            s,
+            separated_pair(
+                lines(expected("a dot", point)),
+                expected("a blank line before the folds", blank_line),
+                lines(expected("a fold", fold)),
+            ),
+        )?;
+        Ok(Self {
+            points: points.into_iter().collect(),
+            folds,
+        })
+    }
+}
+
+pub fn solve_part1(input: Input) -> u64 {
+    let mut grid = Grid(input.points);
+    if let Some(fold) = input.folds.into_iter().next() {
+        grid = grid.fold(fold)
+    }
+
+    grid.0.len() as u64
+}
+
+pub fn solve_part2(input: Input) -> Grid {
+    let mut grid = Grid(input.points);
+    grid = input
+        .folds
+        .into_iter()
+        .fold(grid, |grid, fold| grid.fold(fold));
+    grid
+}
+
+pub const INPUT: &str = include_str!("input.txt");
+
+/// Dots visible after the first fold
+pub fn part1(input: &str) -> Result<Answer, String> {
+    let input = input.parse::<Input>().map_err(|e| e.to_string())?;
+    Ok(solve_part1(input).into())
+}
+
+/// The code spelled out by the dots after every fold
+pub fn part2(input: &str) -> Result<Answer, String> {
+    let input = input.parse::<Input>().map_err(|e| e.to_string())?;
+    Ok(Answer::Grid(solve_part2(input).to_string()))
+}
+
+aoc_common::register_day!(13);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const INPUT: &str = include_str!("test_input.txt");
+
+    #[test]
+    fn test_fold_once_simple() {
+        /*
+           | * * |
+           |   * |
+           | --- |
+           | *   |
+           |     |
+        */
+        let grid = Grid(HashSet::from([
+            (0, 0).into(),
+            (1, 0).into(),
+            (1, 1).into(),
+            (0, 3).into(),
+        ]));
+        /*
+           | * * |
+           | * * |
+        */
+        let expected = Grid(HashSet::from([
+            (0, 0).into(),
+            (1, 0).into(),
+            (1, 1).into(),
+            (0, 1).into(),
+        ]));
+        assert_eq!(grid.fold(Fold::Horizontal(2)), expected);
+    }
+
+    #[test]
+    fn test_fold_once_squish() {
+        /*
+           | * * |
+           | * * |
+           | --- |
+           | *   |
+           |     |
+        */
+        let grid = Grid(HashSet::from([
+            (0, 1).into(),
+            (0, 0).into(),
+            (1, 0).into(),
+            (1, 1).into(),
+            (0, 3).into(),
+        ]));
+        /*
+           | * * |
+           | * * |
+        */
+        let expected = Grid(HashSet::from([
+            (0, 0).into(),
+            (1, 0).into(),
+            (1, 1).into(),
+            (0, 1).into(),
+        ]));
+        assert_eq!(grid.fold(Fold::Horizontal(2)), expected);
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(
+            "6,10\n0,x\n\nfold along y=7".parse::<Input>().unwrap_err(),
+            AocError::parse(2, 3, "expected a number, found `x`")
+        );
+        assert_eq!(
+            "6,10\n\nfold along z=7".parse::<Input>().unwrap_err(),
+            AocError::parse(3, 12, "expected `x=` or `y=`, found `z`")
+        );
+        assert_eq!(
+            "6,10\nfold along y=7".parse::<Input>().unwrap_err(),
+            AocError::parse(2, 1, "expected a dot, found `fold`")
+        );
+        assert_eq!(
+            "6,10\n\n".parse::<Input>().unwrap_err(),
+            AocError::UnexpectedEnd(String::from("a blank line before the folds"))
+        );
+    }
+
+    #[test]
+    fn solve_part1() {
+        let input = INPUT.parse::<Input>().expect("Input must parse");
+        let result = super::solve_part1(input);
+
+        let expected = 17;
+        assert_eq!(result, expected)
+    }
+
+    #[test]
+    fn solve_part2() {
+        let input = INPUT.parse::<Input>().expect("Input must parse");
+        let result = super::solve_part2(input);
+
+        let expected = Grid(HashSet::from([
+            (0, 0).into(),
+            (1, 0).into(),
+            (2, 0).into(),
+            (3, 0).into(),
+            (4, 0).into(),
+            (0, 1).into(),
+            (4, 1).into(),
+            (0, 2).into(),
+            (4, 2).into(),
+            (0, 3).into(),
+            (4, 3).into(),
+            (0, 4).into(),
+            (1, 4).into(),
+            (2, 4).into(),
+            (3, 4).into(),
+            (4, 4).into(),
+        ]));
+
+        assert_eq!(result, expected);
+    }
+}
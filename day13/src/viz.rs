@@ -0,0 +1,58 @@
+//! The paper before folding and after each fold
+
+use std::collections::HashSet;
+
+use aoc_viz::{Color, Frame};
+
+use crate::{Fold, Grid, Input};
+
+fn frame(grid: &Grid, caption: String) -> Frame {
+    let width = grid.0.iter().map(|point| point.x + 1).max().unwrap_or(0);
+    let height = grid.0.iter().map(|point| point.y + 1).max().unwrap_or(0);
+    let mut frame = Frame::new(width as usize, height as usize).with_caption(caption);
+    for point in &grid.0 {
+        frame.set(point.x as usize, point.y as usize, '#', Some(Color::Yellow));
+    }
+    frame
+}
+
+/// One frame for the unfolded paper, then one per fold
+pub fn frames(input: Input) -> impl Iterator<Item = Frame> {
+    let Input { points, folds } = input;
+    let folds_count = folds.len();
+    let start = Grid(points);
+    let first = frame(
+        &start,
+        format!("{} dots, {} folds to go", start.0.len(), folds_count),
+    );
+    let after_folds = folds
+        .into_iter()
+        .enumerate()
+        .scan(start, move |grid, (idx, fold)| {
+            let caption = match fold {
+                Fold::Horizontal(y) => format!("fold {} of {} along y={}", idx + 1, folds_count, y),
+                Fold::Vertical(x) => format!("fold {} of {} along x={}", idx + 1, folds_count, x),
+            };
+            *grid = std::mem::replace(grid, Grid(HashSet::new())).fold(fold);
+            Some(frame(grid, caption))
+        });
+    std::iter::once(first).chain(after_folds)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_frames() {
+        let input: Input = "0,0\n2,0\n0,1\n\nfold along x=1".parse().unwrap();
+        let frames: Vec<String> = frames(input).map(|frame| frame.to_string()).collect();
+        assert_eq!(
+            frames,
+            vec![
+                "# #\n#  \n3 dots, 1 folds to go",
+                "#\n#\nfold 1 of 1 along x=1"
+            ]
+        );
+    }
+}
@@ -0,0 +1,363 @@
+use aoc_common::{answer::Answer, error::AocError};
+
+use std::{collections::HashMap, str::FromStr};
+
+pub const INPUT: &str = include_str!("input.txt");
+
+#[derive(Debug, Clone)]
+enum InsertionAction {
+    Add(u64),
+    Subtract(u64),
+}
+
+impl std::ops::Add for InsertionAction {
+    type Output = InsertionAction;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        match self {
+            InsertionAction::Add(lhs) => match rhs {
+                InsertionAction::Add(rhs) => InsertionAction::Add(lhs + rhs),
+                InsertionAction::Subtract(rhs) if lhs >= rhs => InsertionAction::Add(lhs - rhs),
+                InsertionAction::Subtract(rhs) if lhs < rhs => InsertionAction::Subtract(rhs - lhs),
+                InsertionAction::Subtract(_) => unreachable!(),
+            },
+            InsertionAction::Subtract(lhs) => match rhs {
+                InsertionAction::Add(rhs) if lhs > rhs => InsertionAction::Subtract(lhs - rhs),
+                InsertionAction::Add(rhs) if lhs <= rhs => InsertionAction::Add(rhs - lhs),
+                InsertionAction::Subtract(rhs) => InsertionAction::Subtract(rhs + lhs),
+                InsertionAction::Add(_) => unreachable!(),
+            },
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct PolymerPairCounter {
+    doubles: HashMap<(char, char), u64>,
+    singles: HashMap<char, u64>,
+    insertion_table: HashMap<(char, char), char>,
+}
+impl From<Input> for PolymerPairCounter {
+    fn from(input: Input) -> Self {
+        let mut doubles = HashMap::new();
+        let mut singles = HashMap::new();
+        let insertion_table = input.insertion_table;
+
+        input.polymer_template.chars().for_each(|ch| {
+            singles.entry(ch).and_modify(|e| *e += 1).or_insert(1);
+        });
+
+        let (cur, mut next) = (
+            input.polymer_template.chars(),
+            input.polymer_template.chars(),
+        );
+        next.next(); // advance the second iterator by one
+        for pair in cur.zip(next) {
+            doubles.entry(pair).and_modify(|e| *e += 1).or_insert(1);
+        }
+
+        Self {
+            doubles,
+            singles,
+            insertion_table,
+        }
+    }
+}
+impl PolymerPairCounter {
+    fn char_counts(self) -> PolymerCounter {
+        PolymerCounter(self.singles)
+    }
+    fn perform_insertions(&mut self) {
+        let mut double_insertion_actions: HashMap<(char, char), Vec<InsertionAction>> =
+            HashMap::new();
+        let mut single_insertion_actions: HashMap<char, Vec<InsertionAction>> = HashMap::new();
+        for (&(a, b), &insertion_char) in self.insertion_table.iter() {
+            if let Some(count) = self.doubles.get(&(a, b)) {
+                // Add the to-be-inserted character to the singles map
+                single_insertion_actions
+                    .entry(insertion_char)
+                    .and_modify(|e| e.push(InsertionAction::Add(*count)))
+                    .or_insert_with(|| vec![InsertionAction::Add(*count)]);
+                // Add the to-be-inserted character pairs to the doubles map
+                for tup in [(a, insertion_char), (insertion_char, b)].into_iter() {
+                    double_insertion_actions
+                        .entry(tup)
+                        .and_modify(|e| e.push(InsertionAction::Add(*count)))
+                        .or_insert_with(|| vec![InsertionAction::Add(*count)]);
+                }
+                // Remove the old pairs from the doubles map
+                double_insertion_actions
+                    .entry((a, b))
+                    .and_modify(|e| e.push(InsertionAction::Subtract(*count)))
+                    .or_insert_with(|| vec![InsertionAction::Subtract(*count)]);
+            }
+        }
+
+        // take the insert actions
+        double_insertion_actions
+            .into_iter()
+            .map(|(key, actions)| -> ((char, char), InsertionAction) {
+                (key, actions.into_iter().reduce(std::ops::Add::add).unwrap())
+            })
+            .for_each(|(key, action)| {
+                self.doubles
+                    .entry(key)
+                    .and_modify(|e| match action {
+                        InsertionAction::Add(value) => *e += value,
+                        InsertionAction::Subtract(value) => *e -= value,
+                    })
+                    .or_insert_with_key(|_| match action {
+                        InsertionAction::Add(value) => value,
+                        InsertionAction::Subtract(value) => {
+                            panic!("Can't remove {} from empty key {}{}", value, key.0, key.1);
+                        }
+                    });
+            });
+        single_insertion_actions
+            .into_iter()
+            .map(|(key, actions)| (key, actions.into_iter().reduce(std::ops::Add::add).unwrap()))
+            .for_each(|(key, action)| {
+                self.singles
+                    .entry(key)
+                    .and_modify(|e| match action {
+                        InsertionAction::Add(value) => *e += value,
+                        InsertionAction::Subtract(value) => *e -= value,
+                    })
+                    .or_insert_with_key(|_| match action {
+                        InsertionAction::Add(value) => value,
+                        InsertionAction::Subtract(value) => {
+                            panic!("Can't remove {} from empty key {}", value, key);
+                        }
+                    });
+            });
+
+        self.doubles.retain(|_, &mut value| value > 0);
+    }
+}
+
+struct PolymerCounter(HashMap<char, u64>);
+impl PolymerCounter {
+    fn most_common_count(&self) -> u64 {
+        *self
+            .0
+            .iter()
+            .max_by(|a, b| a.1.cmp(b.1))
+            .map(|(_, count)| count)
+            .unwrap()
+    }
+    fn least_common_count(&self) -> u64 {
+        *self
+            .0
+            .iter()
+            .min_by(|a, b| a.1.cmp(b.1))
+            .map(|(_, count)| count)
+            .unwrap()
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Input {
+    polymer_template: String,
+    insertion_table: HashMap<(char, char), char>,
+}
+
+/// Parses a rule like `CH -> B` into the pair it matches and the element it inserts
+fn parse_rule(line_no: usize, line: &str) -> Result<((char, char), char), AocError> {
+    let (from, to) = line.split_once(" -> ").ok_or_else(|| {
+        AocError::parse(
+            line_no,
+            1,
+            format!("expected a rule like `AB -> C`, found `{}`", line),
+        )
+    })?;
+    let pair = match from.chars().collect::<Vec<_>>()[..] {
+        [a, b] => (a, b),
+        _ => {
+            return Err(AocError::at_token(
+                line_no,
+                line,
+                from,
+                format!("expected a pair of elements, found `{}`", from),
+            ))
+        }
+    };
+    let insertion_character = match to.chars().collect::<Vec<_>>()[..] {
+        [c] => c,
+        _ => {
+            return Err(AocError::at_token(
+                line_no,
+                line,
+                to,
+                format!("expected a single element to insert, found `{}`", to),
+            ))
+        }
+    };
+    Ok((pair, insertion_character))
+}
+
+impl FromStr for Input {
+    type Err = AocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut lines = s.lines().enumerate().map(|(idx, line)| (idx + 1, line));
+        let polymer_template = match lines.next() {
+            Some((_, template)) if !template.trim().is_empty() => template.trim().to_string(),
+            Some((line_no, _)) => {
+                return Err(AocError::parse(
+                    line_no,
+                    1,
+                    "expected a polymer template, found an empty line",
+                ))
+            }
+            None => return Err(AocError::UnexpectedEnd(String::from("a polymer template"))),
+        };
+        // One blank line follows the template string
+        if let Some((line_no, line)) = lines.next() {
+            if !line.trim().is_empty() {
+                return Err(AocError::parse(
+                    line_no,
+                    1,
+                    format!(
+                        "expected a blank line after the polymer template, found `{}`",
+                        line
+                    ),
+                ));
+            }
+        }
+        let mut pair_insertion_table = HashMap::new();
+        for (line_no, line) in lines {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let (pair, insertion_character) = parse_rule(line_no, line)?;
+            pair_insertion_table.insert(pair, insertion_character);
+        }
+        Ok(Self {
+            polymer_template,
+            insertion_table: pair_insertion_table,
+        })
+    }
+}
+
+pub fn solve_part1(input: Input) -> u64 {
+    let mut polymer_counter: PolymerPairCounter = input.into();
+    for _ in 0..10 {
+        polymer_counter.perform_insertions();
+    }
+    let counter = polymer_counter.char_counts();
+    counter.most_common_count() - counter.least_common_count()
+}
+
+pub fn solve_part2(input: Input) -> u64 {
+    let mut polymer_counter: PolymerPairCounter = input.into();
+    for _ in 0..40 {
+        polymer_counter.perform_insertions();
+    }
+    let counter = polymer_counter.char_counts();
+    counter.most_common_count() - counter.least_common_count()
+}
+
+/// Most common minus least common element after 10 steps
+pub fn part1(input: &str) -> Result<Answer, String> {
+    let input = input.parse::<Input>().map_err(|e| e.to_string())?;
+    Ok(solve_part1(input).into())
+}
+
+/// Most common minus least common element after 40 steps
+pub fn part2(input: &str) -> Result<Answer, String> {
+    let input = input.parse::<Input>().map_err(|e| e.to_string())?;
+    Ok(solve_part2(input).into())
+}
+
+aoc_common::register_day!(14);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = include_str!("test_input.txt");
+
+    #[test]
+    #[allow(non_snake_case)]
+    fn perform_insertions_PolymerPairCounter() {
+        let mut polymer_pair_counter = PolymerPairCounter {
+            doubles: HashMap::from([(('N', 'N'), 1), (('N', 'C'), 1), (('C', 'B'), 1)]),
+            singles: HashMap::from([('N', 2), ('C', 1), ('B', 1)]),
+            insertion_table: HashMap::from([
+                (('C', 'B'), 'H'),
+                (('N', 'N'), 'C'),
+                (('N', 'C'), 'B'),
+            ]),
+        };
+        polymer_pair_counter.perform_insertions();
+
+        let expected = PolymerPairCounter {
+            doubles: HashMap::from([
+                (('N', 'C'), 1),
+                (('C', 'N'), 1),
+                (('N', 'B'), 1),
+                (('B', 'C'), 1),
+                (('C', 'H'), 1),
+                (('H', 'B'), 1),
+            ]),
+            singles: HashMap::from([('N', 2), ('C', 2), ('B', 2), ('H', 1)]),
+            insertion_table: HashMap::from([
+                (('C', 'B'), 'H'),
+                (('N', 'N'), 'C'),
+                (('N', 'C'), 'B'),
+            ]),
+        };
+
+        assert_eq!(polymer_pair_counter, expected);
+    }
+
+    #[test]
+    fn solve_part1() {
+        let input = INPUT.parse::<Input>().expect("Input must parse");
+        let result = super::solve_part1(input);
+
+        let expected = 1588;
+        assert_eq!(result, expected);
+    }
+    #[test]
+    fn solve_part2() {
+        let input = INPUT.parse::<Input>().expect("Input must parse");
+        let result = super::solve_part2(input);
+
+        let expected = 2188189693529;
+        assert_eq!(result, expected);
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert!("NN\n\nNN -> C\n".parse::<Input>().is_ok());
+        assert_eq!(
+            "".parse::<Input>().map(|_| ()),
+            Err(AocError::UnexpectedEnd(String::from("a polymer template")))
+        );
+        assert_eq!(
+            "NN\nNN -> C".parse::<Input>().map(|_| ()),
+            Err(AocError::parse(
+                2,
+                1,
+                "expected a blank line after the polymer template, found `NN -> C`"
+            ))
+        );
+        assert_eq!(
+            "NN\n\nNN => C".parse::<Input>().map(|_| ()),
+            Err(AocError::parse(
+                3,
+                1,
+                "expected a rule like `AB -> C`, found `NN => C`"
+            ))
+        );
+        assert_eq!(
+            "NN\n\nNN -> CB".parse::<Input>().map(|_| ()),
+            Err(AocError::parse(
+                3,
+                7,
+                "expected a single element to insert, found `CB`"
+            ))
+        );
+    }
+}
@@ -0,0 +1,42 @@
+//! Compare the search strategies, and time tiling the map, with
+//!
+//! ```text
+//! cargo bench -p day15
+//! ```
+
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use day15::{shortest_path_using, CostModel, Input, Strategy, INPUT};
+
+fn path(c: &mut Criterion) {
+    let input: Input = INPUT.parse().expect("bundled input parses");
+    let mut scaled = input.clone();
+    scaled.scale(5);
+    for (name, input) in [("part1", &input), ("part2", &scaled)] {
+        for strategy in [
+            Strategy::Buckets,
+            Strategy::Heap,
+            Strategy::Bidirectional,
+            Strategy::Petgraph,
+        ] {
+            c.bench_function(&format!("day15 {} {:?}", name, strategy), |b| {
+                b.iter(|| shortest_path_using(black_box(input), CostModel::default(), strategy))
+            });
+        }
+    }
+}
+
+fn scale(c: &mut Criterion) {
+    let input: Input = INPUT.parse().expect("bundled input parses");
+    c.bench_function("day15 scale 5", |b| {
+        b.iter(|| {
+            let mut scaled = black_box(&input).clone();
+            scaled.scale(5);
+            scaled
+        })
+    });
+}
+
+criterion_group!(benches, path, scale);
+criterion_main!(benches);
@@ -0,0 +1,134 @@
+//! A* from both corners at once, meeting in the middle
+//!
+//! Each search is guided by half the difference between the distances to the two corners,
+//! which keeps the two consistent with each other, so the first time they can't find anything
+//! shorter than the best meeting point so far, that's the answer. Everything's doubled to keep
+//! the halves whole.
+
+use std::{cmp::Reverse, collections::BinaryHeap};
+
+use aoc_common::progress;
+
+use crate::{
+    dijkstra::{ends, path, steps, UNREACHED},
+    CostModel, Input, Movement, Point,
+};
+
+/// One direction's search
+struct Search {
+    /// Lowest cost found so far to (or from) each point
+    costs: Vec<u64>,
+    /// The point each one was best reached from
+    previous: Vec<usize>,
+    /// Points by doubled cost plus their guide, cheapest first
+    queue: BinaryHeap<Reverse<(i64, usize)>>,
+}
+
+impl Search {
+    fn new(len: usize, from: usize, key: i64) -> Self {
+        let mut search = Self {
+            costs: vec![u64::MAX; len],
+            previous: vec![UNREACHED; len],
+            queue: BinaryHeap::from([Reverse((key, from))]),
+        };
+        search.costs[from] = 0;
+        search
+    }
+
+    /// The key of the next point to settle, dropping any that were reached more cheaply since
+    /// they were queued
+    fn peek(&mut self, key: impl Fn(u64, usize) -> i64) -> Option<i64> {
+        while let Some(&Reverse((next, index))) = self.queue.peek() {
+            if next == key(self.costs[index], index) {
+                return Some(next);
+            }
+            self.queue.pop();
+        }
+        None
+    }
+}
+
+/// Lowest total cost from the top left to the bottom right, and the points along the way,
+/// starting with the top left. `None` if the grid's empty.
+pub(crate) fn shortest_path(
+    input: &Input,
+    cost_model: CostModel,
+    movement: Movement,
+) -> Option<(u64, Vec<Point>)> {
+    let (start, end) = ends(input)?;
+    let min_risk = input.0.iter().min().copied().map_or(0, u32::from);
+    let scale = i64::from(cost_model.min_step_cost().min(min_risk));
+    let width = input.0.width();
+    let coords = |index: usize| ((index % width) as u32, (index / width) as u32);
+    let (end_x, end_y) = coords(end);
+    // Twice the forward search's guide: how far there is left to go, less how far it's come
+    let guide = |index: usize| {
+        let (x, y) = coords(index);
+        let to_go = movement.distance(end_x - x, end_y - y);
+        scale * (i64::from(to_go) - i64::from(movement.distance(x, y)))
+    };
+    let forward_key = |cost: u64, index: usize| 2 * cost as i64 + guide(index) - guide(start);
+    let backward_key = |cost: u64, index: usize| 2 * cost as i64 - guide(index) + guide(end);
+    // Doubled lengths of paths through a point are the two keys plus this
+    let offset = guide(start) - guide(end);
+
+    let mut forward = Search::new(input.0.len(), start, 0);
+    let mut backward = Search::new(input.0.len(), end, 0);
+    let mut best = if start == end { Some((0, start)) } else { None };
+
+    progress::start(Some(input.0.len() as u64));
+    while let (Some(ahead), Some(behind)) = (forward.peek(forward_key), backward.peek(backward_key))
+    {
+        if best.is_some_and(|(cost, _)| ahead + behind + offset >= 2 * cost as i64) {
+            break;
+        }
+        progress::advance(1);
+        if ahead <= behind {
+            let Reverse((_, index)) = forward.queue.pop().expect("just peeked");
+            let cost = forward.costs[index];
+            for (next, step) in steps(input, index, cost_model, movement) {
+                let next_cost = cost + u64::from(step);
+                if next_cost < forward.costs[next] {
+                    forward.costs[next] = next_cost;
+                    forward.previous[next] = index;
+                    forward
+                        .queue
+                        .push(Reverse((forward_key(next_cost, next), next)));
+                    if backward.costs[next] != u64::MAX {
+                        let through = next_cost + backward.costs[next];
+                        best = Some(best.map_or((through, next), |best| best.min((through, next))));
+                    }
+                }
+            }
+        } else {
+            let Reverse((_, index)) = backward.queue.pop().expect("just peeked");
+            let cost = backward.costs[index];
+            let to = input.point_at(index);
+            for (next, _) in steps(input, index, cost_model, movement) {
+                // Coming backwards, so it's the step from `next` into `index` that counts
+                let step = cost_model.cost(&input.point_at(next), &to);
+                let next_cost = cost + u64::from(step);
+                if next_cost < backward.costs[next] {
+                    backward.costs[next] = next_cost;
+                    backward.previous[next] = index;
+                    backward
+                        .queue
+                        .push(Reverse((backward_key(next_cost, next), next)));
+                    if forward.costs[next] != u64::MAX {
+                        let through = forward.costs[next] + next_cost;
+                        best = Some(best.map_or((through, next), |best| best.min((through, next))));
+                    }
+                }
+            }
+        }
+    }
+
+    let (cost, meeting) = best?;
+    let mut route = path(input, &forward.previous, start, meeting);
+    let mut index = meeting;
+    while index != end {
+        index = backward.previous[index];
+        route.push(input.point_at(index));
+    }
+    Some((cost, route))
+}
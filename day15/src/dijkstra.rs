@@ -0,0 +1,161 @@
+//! Dijkstra's algorithm straight over the grid, knowing each point by its index in the grid's
+//! flat storage, so there's no graph to build and no points to hash
+
+use std::{cmp::Reverse, collections::BinaryHeap};
+
+use aoc_common::progress;
+
+use crate::{CostModel, Input, Movement, Point};
+
+/// Marks a point that hasn't been reached yet
+pub(crate) const UNREACHED: usize = usize::MAX;
+
+impl Input {
+    pub(crate) fn point_at(&self, index: usize) -> Point {
+        let (x, y) = self.0.coords_of(index);
+        Point {
+            x: x as u32,
+            y: y as u32,
+            value: self.0[(x, y)].into(),
+        }
+    }
+}
+
+/// Where the search starts and ends, `None` if the grid's empty
+pub(crate) fn ends(input: &Input) -> Option<(usize, usize)> {
+    let start = input.0.index_of(0, 0)?;
+    // Rows are stored in order, so the bottom right comes last
+    Some((start, input.0.len() - 1))
+}
+
+/// Where a step can go from a point, across and down, without going diagonally
+const ORTHOGONAL: [(isize, isize); 4] = [(0, -1), (-1, 0), (1, 0), (0, 1)];
+/// Where a step can go from a point, diagonals included
+const DIAGONAL: [(isize, isize); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (-1, 0),
+    (1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+];
+
+/// The points `movement` can step to from `index`, with what it costs to step there
+pub(crate) fn steps<'a>(
+    input: &'a Input,
+    index: usize,
+    cost_model: CostModel,
+    movement: Movement,
+) -> impl Iterator<Item = (usize, u32)> + 'a {
+    let from = input.point_at(index);
+    let offsets: &[_] = match movement {
+        Movement::Orthogonal => &ORTHOGONAL,
+        Movement::Diagonal => &DIAGONAL,
+    };
+    offsets.iter().filter_map(move |&(dx, dy)| {
+        let x = (from.x as usize).checked_add_signed(dx)?;
+        let y = (from.y as usize).checked_add_signed(dy)?;
+        let next = input.0.index_of(x, y)?;
+        Some((next, cost_model.cost(&from, &input.point_at(next))))
+    })
+}
+
+/// The points from `start` to `end`, following `previous` back from the end
+pub(crate) fn path(input: &Input, previous: &[usize], start: usize, end: usize) -> Vec<Point> {
+    let mut path = vec![input.point_at(end)];
+    let mut index = end;
+    while index != start {
+        index = previous[index];
+        path.push(input.point_at(index));
+    }
+    path.reverse();
+    path
+}
+
+/// Lowest total cost from the top left to the bottom right, and the points along the way,
+/// starting with the top left. `None` if the grid's empty.
+pub(crate) fn shortest_path(
+    input: &Input,
+    cost_model: CostModel,
+    movement: Movement,
+) -> Option<(u64, Vec<Point>)> {
+    let (start, end) = ends(input)?;
+    let mut costs = vec![u64::MAX; input.0.len()];
+    // The point each one was best reached from
+    let mut previous = vec![UNREACHED; input.0.len()];
+    let mut queue = BinaryHeap::from([Reverse((0, start))]);
+    costs[start] = 0;
+
+    progress::start(Some(input.0.len() as u64));
+    while let Some(Reverse((cost, index))) = queue.pop() {
+        if cost > costs[index] {
+            // Already reached more cheaply
+            continue;
+        }
+        progress::advance(1);
+        if index == end {
+            break;
+        }
+        for (next, step) in steps(input, index, cost_model, movement) {
+            let next_cost = cost + u64::from(step);
+            if next_cost < costs[next] {
+                costs[next] = next_cost;
+                previous[next] = index;
+                queue.push(Reverse((next_cost, next)));
+            }
+        }
+    }
+    Some((costs[end], path(input, &previous, start, end)))
+}
+
+/// The same as [`shortest_path`], but with Dial's bucket queue in place of the heap, for when
+/// no step costs more than `max_step`. Points are taken out in order of cost by going through
+/// the costs one at a time, and everything waiting is never more than `max_step` beyond the
+/// current cost, so `max_step + 1` buckets can be reused round and round.
+pub(crate) fn shortest_path_buckets(
+    input: &Input,
+    cost_model: CostModel,
+    movement: Movement,
+    max_step: u32,
+) -> Option<(u64, Vec<Point>)> {
+    let (start, end) = ends(input)?;
+    let mut costs = vec![u64::MAX; input.0.len()];
+    let mut previous = vec![UNREACHED; input.0.len()];
+    let rounds = u64::from(max_step) + 1;
+    let bucket = |cost: u64| (cost % rounds) as usize;
+    let mut buckets = vec![Vec::new(); rounds as usize];
+    buckets[0].push(start);
+    costs[start] = 0;
+    let mut waiting = 1;
+
+    progress::start(Some(input.0.len() as u64));
+    let mut cost = 0;
+    'search: while waiting > 0 {
+        let current = bucket(cost);
+        while let Some(index) = buckets[current].pop() {
+            waiting -= 1;
+            if costs[index] != cost {
+                // Already reached more cheaply
+                continue;
+            }
+            progress::advance(1);
+            if index == end {
+                break 'search;
+            }
+            for (next, step) in steps(input, index, cost_model, movement) {
+                debug_assert!(step <= max_step, "a step cost more than {}", max_step);
+                let next_cost = cost + u64::from(step);
+                if next_cost < costs[next] {
+                    costs[next] = next_cost;
+                    previous[next] = index;
+                    buckets[bucket(next_cost)].push(next);
+                    waiting += 1;
+                }
+            }
+        }
+        cost += 1;
+    }
+    Some((costs[end], path(input, &previous, start, end)))
+}
@@ -0,0 +1,21 @@
+//! Estimates of the least risk left from a point to the end, for guiding
+//! [`shortest_path_with_heuristic`](crate::shortest_path_with_heuristic)
+
+use crate::Point;
+
+/// One for every step across and down to the end, whichever side of it the point is on. Risks
+/// are at least 1, so this never overestimates for the built-in cost models when moving
+/// orthogonally.
+/// ```rust
+/// # use day15::*;
+/// let at = |x, y| Point { x, y, value: 1 };
+/// assert_eq!(heuristic::manhattan(&at(1, 2), &at(4, 0)), 5);
+/// ```
+pub fn manhattan(point: &Point, end: &Point) -> u32 {
+    point.x.abs_diff(end.x) + point.y.abs_diff(end.y)
+}
+
+/// No estimate at all, which turns A* into Dijkstra's algorithm. Right for any cost model.
+pub fn zero(_point: &Point, _end: &Point) -> u32 {
+    0
+}
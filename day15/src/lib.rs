@@ -1,8 +1,38 @@
-use std::str::FromStr;
+use std::{
+    io::{self, BufRead},
+    str::FromStr,
+};
 
-use petgraph::IntoWeightedEdge;
+use aoc_common::{answer::Answer, error::AocError, progress};
+use grid2d::Grid;
+use petgraph::{algo::astar, csr::Csr, graphmap::DiGraphMap, IntoWeightedEdge};
 
-#[derive(Debug, Copy, Hash, Clone, PartialEq, Eq)]
+mod bidirectional;
+mod dijkstra;
+pub mod heuristic;
+mod query;
+#[cfg(feature = "visualize")]
+pub mod viz;
+
+pub use query::PathQuery;
+
+pub const INPUT: &str = include_str!("input.txt");
+
+/// Lowest total risk from the top left to the bottom right
+pub fn part1(input: &str) -> Result<Answer, String> {
+    let input = input.parse::<Input>().map_err(|e| e.to_string())?;
+    Ok(solve_part1(input).into())
+}
+
+/// Lowest total risk across the map tiled five times in each direction
+pub fn part2(input: &str) -> Result<Answer, String> {
+    let input = input.parse::<Input>().map_err(|e| e.to_string())?;
+    Ok(solve_part2(input).into())
+}
+
+aoc_common::register_day!(15);
+
+#[derive(Debug, Copy, Hash, Clone, Default, PartialEq, Eq)]
 pub struct Point {
     pub x: u32,
     pub y: u32,
@@ -11,22 +41,97 @@ pub struct Point {
 
 impl PartialOrd for Point {
     fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        match self.y.partial_cmp(&other.y) {
-            Some(core::cmp::Ordering::Equal) => {}
-            ord => return ord,
-        }
-        match self.x.partial_cmp(&other.x) {
-            Some(core::cmp::Ordering::Equal) => {}
-            ord => return ord,
-        }
-        self.value.partial_cmp(&other.value)
+        Some(self.cmp(other))
     }
 }
 
 impl Ord for Point {
     fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        // safety: there are no u32s that are not Ord
-        self.partial_cmp(other).unwrap()
+        (self.y, self.x, self.value).cmp(&(other.y, other.x, other.value))
+    }
+}
+
+/// How much it costs to step from one point to a neighbouring point
+#[derive(Debug, Clone, Copy, Default)]
+pub enum CostModel {
+    /// Pay the risk of the point being entered. This is the puzzle's rule.
+    #[default]
+    Enter,
+    /// Pay the risk of the point being left
+    Leave,
+    /// Pay the mean risk of both points, rounded down
+    Average,
+    /// Pay whatever the function says. Custom costs might be zero, so A* can't assume every
+    /// step costs at least 1.
+    Custom(fn(from: &Point, to: &Point) -> u32),
+}
+
+impl CostModel {
+    pub fn cost(&self, from: &Point, to: &Point) -> u32 {
+        match self {
+            Self::Enter => to.value,
+            Self::Leave => from.value,
+            Self::Average => (from.value + to.value) / 2,
+            Self::Custom(f) => f(from, to),
+        }
+    }
+
+    /// The least a single step is known to cost, for scaling distance heuristics.
+    /// Risk levels are 1 through 9, so built-in models always charge at least 1.
+    pub fn min_step_cost(&self) -> u32 {
+        match self {
+            Self::Enter | Self::Leave | Self::Average => 1,
+            Self::Custom(_) => 0,
+        }
+    }
+
+    /// The most a single step can cost on a map whose highest risk is `max_risk`, if that's
+    /// known
+    pub fn max_step_cost(&self, max_risk: u32) -> Option<u32> {
+        match self {
+            Self::Enter | Self::Leave | Self::Average => Some(max_risk),
+            Self::Custom(_) => None,
+        }
+    }
+}
+
+/// How to search for the safest path. They all find paths of the same risk, though not
+/// always the same path.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Strategy {
+    /// Dijkstra's algorithm over the grid, taking the cheapest point from a bucket for each
+    /// total risk. Only works when steps have a known most they can cost, so custom cost models
+    /// fall back to [`Strategy::Heap`]. It's quickest when risks are low, as in the puzzle.
+    #[default]
+    Buckets,
+    /// Dijkstra's algorithm over the grid, taking the cheapest point from a binary heap
+    Heap,
+    /// A* over the grid from both corners at once, stopping once they've met and there's
+    /// no shorter way left to find. It settles fewer points than the others, but does more
+    /// work for each.
+    Bidirectional,
+    /// petgraph's A*, on a graph built from the grid first
+    Petgraph,
+}
+
+/// Which neighbours a path can step to
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Movement {
+    /// Up, down, left and right. This is the puzzle's rule.
+    #[default]
+    Orthogonal,
+    /// Diagonally as well. A diagonal step is priced by the cost model like any other, so it
+    /// costs the risk of the point it enters with the puzzle's rule.
+    Diagonal,
+}
+
+impl Movement {
+    /// The fewest steps it takes to go `dx` across and `dy` down
+    pub fn distance(&self, dx: u32, dy: u32) -> u32 {
+        match self {
+            Self::Orthogonal => dx + dy,
+            Self::Diagonal => dx.max(dy),
+        }
     }
 }
 
@@ -49,86 +154,453 @@ impl Edge {
     }
 }
 
+/// Risk levels, one byte each, with each point's coordinates worked out from where it's kept
 #[derive(Debug, Clone, PartialEq, Eq)]
-pub struct Input(Vec<Point>);
+pub struct Input(Grid<u8>);
 
 impl Input {
-    fn get_point(&self, x: u32, y: u32) -> Option<&Point> {
-        self.0.iter().find(|&point| point.x == x && point.y == y)
+    fn get_point(&self, x: u32, y: u32) -> Option<Point> {
+        self.0.get(x as usize, y as usize).map(|&value| Point {
+            x,
+            y,
+            value: value.into(),
+        })
+    }
+
+    /// Changes the risk at `(x, y)`, returning what it was, or `None` if that's off the map.
+    /// [`PathQuery`] does this too, and knows when it needs to search again afterwards.
+    pub fn set_risk(&mut self, x: u32, y: u32, risk: u8) -> Option<u8> {
+        let cell = self.0.get_mut(x as usize, y as usize)?;
+        Some(std::mem::replace(cell, risk))
+    }
+
+    /// Every point, row by row
+    pub fn points(&self) -> impl Iterator<Item = Point> + '_ {
+        self.0.iter_coords().map(|((x, y), &value)| Point {
+            x: x as u32,
+            y: y as u32,
+            value: value.into(),
+        })
+    }
+
+    /// The map with only the points on `path` drawn, and a `.` everywhere else
+    /// ```rust
+    /// # use day15::*;
+    /// let input: Input = "19\n11".parse().unwrap();
+    /// let (_, path) = shortest_path(&input, CostModel::default());
+    /// assert_eq!(input.render_with_path(&path), "1.\n11\n");
+    /// ```
+    pub fn render_with_path(&self, path: &[Point]) -> String {
+        self.render(path, |digit, on_path| {
+            if on_path {
+                digit.to_string()
+            } else {
+                String::from(".")
+            }
+        })
+    }
+
+    /// The whole map, with the points on `path` in bold green and the rest dimmed, for a
+    /// terminal that understands ANSI colours
+    pub fn render_with_path_in_color(&self, path: &[Point]) -> String {
+        self.render(path, |digit, on_path| {
+            if on_path {
+                format!("\x1b[1;32m{}\x1b[0m", digit)
+            } else {
+                format!("\x1b[2m{}\x1b[0m", digit)
+            }
+        })
+    }
+
+    /// Each row of the map on a line, drawing each point with `draw(digit, on_path)`
+    fn render(&self, path: &[Point], draw: impl Fn(char, bool) -> String) -> String {
+        let mut on_path = Grid::from_fn(self.get_width(), self.get_height(), |_, _| false);
+        for point in path {
+            if let Some(cell) = on_path.get_mut(point.x as usize, point.y as usize) {
+                *cell = true;
+            }
+        }
+        let mut out = String::new();
+        for point in self.points() {
+            let digit = char::from_digit(point.value, 10).unwrap_or('?');
+            out.push_str(&draw(digit, on_path[(point.x as usize, point.y as usize)]));
+            if point.x as usize == self.get_width() - 1 {
+                out.push('\n');
+            }
+        }
+        out
     }
 
     /// Width as a 1-indexed usize
     fn get_width(&self) -> usize {
-        self.0
-            .iter()
-            .map(|point| point.x as usize + 1)
-            .max()
-            .unwrap_or(0)
+        self.0.width()
     }
 
     /// Height as a 1-indexed usize
     fn get_height(&self) -> usize {
-        self.0
-            .iter()
-            .map(|point| point.y as usize + 1)
-            .max()
-            .unwrap_or(0)
+        self.0.height()
     }
 
     pub fn into_edges(self) -> Vec<Edge> {
-        self.0
-            .iter()
+        self.edges(Movement::Orthogonal)
+    }
+
+    /// The same as [`Input::into_edges`], between the neighbours `movement` can step to
+    /// ```rust
+    /// # use day15::*;
+    /// let input: Input = "12\n34".parse().unwrap();
+    /// assert_eq!(input.clone().into_edges_moving(Movement::Orthogonal).len(), 4);
+    /// assert_eq!(input.into_edges_moving(Movement::Diagonal).len(), 6);
+    /// ```
+    pub fn into_edges_moving(self, movement: Movement) -> Vec<Edge> {
+        self.edges(movement)
+    }
+
+    /// Every pair of neighbours, once each, pointing down or right, and diagonally down
+    /// either way if `movement` allows it
+    fn edges(&self, movement: Movement) -> Vec<Edge> {
+        self.points()
             .flat_map(|point| {
+                let diagonals = match movement {
+                    Movement::Orthogonal => [None, None],
+                    Movement::Diagonal => [
+                        self.get_point(point.x + 1, point.y + 1),
+                        point
+                            .x
+                            .checked_sub(1)
+                            .and_then(|x| self.get_point(x, point.y + 1)),
+                    ],
+                };
                 [
                     self.get_point(point.x, point.y + 1),
                     self.get_point(point.x + 1, point.y),
                 ]
-                .map(|dest| -> Option<Edge> {
-                    dest.map(|dest_point| Edge::new(*point, *dest_point))
-                })
                 .into_iter()
+                .chain(diagonals)
                 .flatten()
+                .map(move |dest| Edge::new(point, dest))
             })
             .collect()
     }
 
+    /// Every edge in both directions, weighted by `cost_model`
+    pub fn into_weighted_edges(self, cost_model: CostModel) -> Vec<(Point, Point, u32)> {
+        self.weighted_edges(cost_model, Movement::Orthogonal)
+    }
+
+    /// The same as [`Input::into_weighted_edges`], between the neighbours `movement` can step to
+    pub fn into_weighted_edges_moving(
+        self,
+        cost_model: CostModel,
+        movement: Movement,
+    ) -> Vec<(Point, Point, u32)> {
+        self.weighted_edges(cost_model, movement)
+    }
+
+    fn weighted_edges(
+        &self,
+        cost_model: CostModel,
+        movement: Movement,
+    ) -> Vec<(Point, Point, u32)> {
+        self.edges(movement)
+            .into_iter()
+            .flat_map(|Edge((a, b))| {
+                [
+                    (a, b, cost_model.cost(&a, &b)),
+                    (b, a, cost_model.cost(&b, &a)),
+                ]
+            })
+            .collect()
+    }
+
+    /// A graph with an edge each way between every pair of neighbours, weighted by the risk
+    /// of the point it enters
+    pub fn into_directed_graph(self) -> DiGraphMap<Point, u32> {
+        self.to_graphmap(CostModel::Enter, Movement::Orthogonal)
+    }
+
+    /// A graph keyed by the points themselves, for running other petgraph algorithms on the
+    /// map. There's an edge each way between every pair of neighbours `movement` allows, and
+    /// the edge from `a` to `b` weighs `cost_model.cost(a, b)`, what it costs to step from
+    /// `a` into `b`. With the puzzle's rule that's the risk of `b`.
+    /// ```rust
+    /// # use day15::*;
+    /// let input: Input = "12\n34".parse().unwrap();
+    /// let graph = input.to_graphmap(CostModel::Leave, Movement::Orthogonal);
+    /// let [a, b, ..] = input.points().collect::<Vec<_>>()[..] else { unreachable!() };
+    /// assert_eq!(graph.edge_weight(a, b), Some(&1));
+    /// assert_eq!(graph.edge_weight(b, a), Some(&2));
+    /// ```
+    pub fn to_graphmap(&self, cost_model: CostModel, movement: Movement) -> DiGraphMap<Point, u32> {
+        DiGraphMap::from_edges(self.weighted_edges(cost_model, movement))
+    }
+
+    /// The same graph as [`Input::to_graphmap`], in compressed sparse rows, which is much
+    /// quicker to build and search for a big map. Node `y * width + x` is the point at
+    /// `(x, y)`, and its weight is that [`Point`].
+    /// ```rust
+    /// # use day15::*;
+    /// use petgraph::{algo::dijkstra, visit::EdgeCount};
+    ///
+    /// let input: Input = "116\n138\n213".parse().unwrap();
+    /// let graph = input.to_csr(CostModel::default(), Movement::Orthogonal);
+    /// assert_eq!((graph.node_count(), graph.edge_count()), (9, 24));
+    /// assert_eq!(graph[8], Point { x: 2, y: 2, value: 3 });
+    /// assert_eq!(dijkstra(&graph, 0, Some(8), |edge| *edge.weight())[&8], 7);
+    /// ```
+    pub fn to_csr(&self, cost_model: CostModel, movement: Movement) -> Csr<Point, u32> {
+        // Steps come out in order of where they go, so the edges are sorted as they're made
+        let edges: Vec<(u32, u32, u32)> = (0..self.0.len())
+            .flat_map(|index| {
+                dijkstra::steps(self, index, cost_model, movement)
+                    .map(move |(next, cost)| (index as u32, next as u32, cost))
+            })
+            .collect();
+        let mut graph = Csr::from_sorted_edges(&edges).expect("edges are made in order");
+        // A map of one point has no edges to bring its node in
+        while graph.node_count() < self.0.len() {
+            graph.add_node(Point::default());
+        }
+        for index in 0..self.0.len() {
+            graph[index as u32] = self.point_at(index);
+        }
+        graph
+    }
+
+    /// Tiles the map `times` times in each direction, the puzzle's way: each tile's risks are
+    /// one higher than the tile above or to the left, wrapping from 9 back round to 1. The
+    /// original tile is left as it is.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `times` is 0, since a map can't be empty.
     pub fn scale(&mut self, times: usize) {
+        self.scale_with(times, times, |value, dx, dy| match dx + dy {
+            0 => value,
+            offset => ((value as usize + offset - 1) % 9 + 1) as u8,
+        });
+    }
+
+    /// Tiles the map `times_x` times across and `times_y` times down, with
+    /// `risk(value, dx, dy)` giving each risk in the tile `dx` across and `dy` down from the
+    /// value it had in the original. With the `parallel` feature, the new rows are filled in
+    /// on every core.
+    /// ```rust
+    /// # use day15::*;
+    /// let mut input: Input = "12".parse().unwrap();
+    /// input.scale_with(2, 3, |value, dx, dy| value + 2 * dx as u8 + dy as u8);
+    /// assert_eq!(input, "1234\n2345\n3456".parse().unwrap());
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `times_x` or `times_y` is 0, since a map can't be empty.
+    pub fn scale_with(
+        &mut self,
+        times_x: usize,
+        times_y: usize,
+        risk: impl Fn(u8, usize, usize) -> u8 + Sync,
+    ) {
+        assert!(
+            times_x > 0 && times_y > 0,
+            "a map must be tiled at least once each way"
+        );
         let height = self.get_height();
         let width = self.get_width();
-        let mut new_points = Vec::new();
-        for &point in self.0.iter() {
-            for scalar_y in 0..times {
-                for scalar_x in 0..times {
-                    // If both scalars are 0, that's just the original point
-                    if scalar_y == 0 && scalar_x == 0 {
-                        continue;
-                    }
-                    let new_point = Point {
-                        x: point.x + (width * scalar_x) as u32,
-                        y: point.y + (height * scalar_y) as u32,
-                        value: (point.value + scalar_x as u32 + scalar_y as u32 - 1) % 9 + 1,
-                    };
-                    new_points.push(new_point);
+        let mut cells = vec![0; width * times_x * height * times_y];
+        // Each new row is its row of the original, once for every tile across
+        let fill = |(y, row): (usize, &mut [u8])| {
+            let original = self.0.row(y % height).expect("y is within a tile");
+            for (dx, tile) in row.chunks_exact_mut(width).enumerate() {
+                for (cell, &value) in tile.iter_mut().zip(original) {
+                    *cell = risk(value, dx, y / height);
                 }
             }
+        };
+        #[cfg(feature = "parallel")]
+        {
+            use rayon::prelude::*;
+            cells
+                .par_chunks_exact_mut(width * times_x)
+                .enumerate()
+                .for_each(fill);
         }
-        self.0.extend(new_points.into_iter());
+        #[cfg(not(feature = "parallel"))]
+        {
+            cells
+                .chunks_exact_mut(width * times_x)
+                .enumerate()
+                .for_each(fill);
+        }
+        self.0 = Grid::new(width * times_x, height * times_y, cells)
+            .expect("there's a cell for every point");
+    }
+
+    /// Reads a map a line at a time, for maps too big to hold as a string as well. Takes the
+    /// same maps as [`FromStr`], and a map that doesn't parse is an
+    /// [`InvalidData`](io::ErrorKind::InvalidData) error wrapping the same [`AocError`].
+    /// ```rust
+    /// # use day15::*;
+    /// let input = Input::from_reader("116\r\n138\n".as_bytes()).unwrap();
+    /// assert_eq!(input, "116\n138".parse().unwrap());
+    /// let err = Input::from_reader("116\n1x8".as_bytes()).unwrap_err();
+    /// assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+    /// assert_eq!(err.to_string(), "line 2, column 2: expected a digit, found 'x'");
+    /// ```
+    pub fn from_reader<R: BufRead>(reader: R) -> io::Result<Self> {
+        let mut error = None;
+        let lines = reader
+            .lines()
+            .map_while(|line| line.map_err(|err| error = Some(err)).ok());
+        let grid = Grid::from_lines(lines);
+        if let Some(err) = error {
+            return Err(err);
+        }
+        grid.map_err(AocError::from)
+            .and_then(Self::from_grid)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))
+    }
+
+    /// Any grid but an empty one
+    fn from_grid(grid: Grid<u8>) -> Result<Self, AocError> {
+        if grid.is_empty() {
+            return Err(AocError::UnexpectedEnd(String::from(
+                "a map of risk levels",
+            )));
+        }
+        Ok(Input(grid))
     }
 }
+/// A rectangle of digits, any width and height, so long as every row is as long as the first
+/// and there's at least one point
 impl FromStr for Input {
-    type Err = ();
+    type Err = AocError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut acc = Vec::new();
-        for (y, line) in (0..).zip(s.lines()) {
-            for (x, ch) in (0..).zip(line.chars()) {
-                let value = ch.to_digit(10).ok_or(())?;
-                acc.push(Point { x, y, value });
-            }
+        Self::from_grid(s.parse()?)
+    }
+}
+
+pub fn solve_part1(input: Input) -> u64 {
+    solve_with(input, CostModel::default())
+}
+
+/// Lowest total risk of any path from the top left to the bottom right
+pub fn solve_with(input: Input, cost_model: CostModel) -> u64 {
+    shortest_path(&input, cost_model).0
+}
+
+/// Lowest total risk from the top left to the bottom right, and the points along the way,
+/// starting with the top left
+pub fn shortest_path(input: &Input, cost_model: CostModel) -> (u64, Vec<Point>) {
+    shortest_path_using(input, cost_model, Strategy::default())
+}
+
+/// The same as [`shortest_path`], searching the way `strategy` says
+pub fn shortest_path_using(
+    input: &Input,
+    cost_model: CostModel,
+    strategy: Strategy,
+) -> (u64, Vec<Point>) {
+    shortest_path_moving(input, cost_model, strategy, Movement::default())
+}
+
+/// The same as [`shortest_path_using`], stepping to the neighbours `movement` allows
+/// ```rust
+/// # use day15::*;
+/// let input: Input = "19\n91".parse().unwrap();
+/// let search = |movement| {
+///     shortest_path_moving(&input, CostModel::default(), Strategy::default(), movement).0
+/// };
+/// assert_eq!(search(Movement::Orthogonal), 10);
+/// assert_eq!(search(Movement::Diagonal), 1);
+/// ```
+pub fn shortest_path_moving(
+    input: &Input,
+    cost_model: CostModel,
+    strategy: Strategy,
+    movement: Movement,
+) -> (u64, Vec<Point>) {
+    let max_risk = input.points().map(|point| point.value).max().unwrap_or(0);
+    let found = match (strategy, cost_model.max_step_cost(max_risk)) {
+        (Strategy::Buckets, Some(max_step)) => {
+            dijkstra::shortest_path_buckets(input, cost_model, movement, max_step)
         }
+        (Strategy::Buckets | Strategy::Heap, _) => {
+            dijkstra::shortest_path(input, cost_model, movement)
+        }
+        (Strategy::Bidirectional, _) => bidirectional::shortest_path(input, cost_model, movement),
+        (Strategy::Petgraph, _) => {
+            let min_step = cost_model.min_step_cost();
+            return astar_path(input, cost_model, movement, |point, end| {
+                movement.distance(point.x.abs_diff(end.x), point.y.abs_diff(end.y)) * min_step
+            });
+        }
+    };
+    found.expect("There must be a path from start to end")
+}
 
-        Ok(Input(acc))
-    }
+/// The same as [`shortest_path`], but by building a petgraph graph and running its A* on it,
+/// which takes longer. Short for [`shortest_path_using`] with [`Strategy::Petgraph`].
+pub fn shortest_path_petgraph(input: &Input, cost_model: CostModel) -> (u64, Vec<Point>) {
+    shortest_path_using(input, cost_model, Strategy::Petgraph)
+}
+
+/// The same as [`shortest_path`], using petgraph's A* guided by `heuristic(point, end)`, its
+/// estimate of the least risk left from `point` to the bottom right. If it ever guesses too
+/// high, the path found might not be the safest. [`heuristic::zero`] never does, and makes
+/// the search Dijkstra's algorithm.
+/// ```rust
+/// # use day15::*;
+/// let input: Input = "116\n138\n213".parse().unwrap();
+/// for guess in [heuristic::manhattan, heuristic::zero] {
+///     assert_eq!(shortest_path_with_heuristic(&input, CostModel::default(), guess).0, 7);
+/// }
+/// ```
+pub fn shortest_path_with_heuristic(
+    input: &Input,
+    cost_model: CostModel,
+    heuristic: impl Fn(&Point, &Point) -> u32,
+) -> (u64, Vec<Point>) {
+    astar_path(input, cost_model, Movement::default(), heuristic)
+}
+
+fn astar_path(
+    input: &Input,
+    cost_model: CostModel,
+    movement: Movement,
+    heuristic: impl Fn(&Point, &Point) -> u32,
+) -> (u64, Vec<Point>) {
+    let start = input
+        .get_point(0, 0)
+        .expect("(0, 0) must be contained in the graph");
+    let end = input
+        .get_point(input.get_width() as u32 - 1, input.get_height() as u32 - 1)
+        .expect("The bottom right must be contained in the graph");
+    let graph = input.to_graphmap(cost_model, movement);
+
+    // A* checks whether each point it takes off its queue is the goal, which comes to about once
+    // a point if it has to search the whole map
+    progress::start(Some(graph.node_count() as u64));
+    let (distance, path) = astar(
+        &graph,
+        start,
+        |point| {
+            progress::advance(1);
+            point == end
+        },
+        |(_, _, &weight)| weight,
+        |point| heuristic(&point, &end),
+    )
+    .expect("There must be a path from start to end");
+
+    (distance as u64, path)
+}
+
+pub fn solve_part2(mut input: Input) -> u64 {
+    input.scale(5);
+
+    solve_part1(input)
 }
 
 #[cfg(test)]
@@ -157,14 +629,99 @@ mod tests {
         assert_eq!(input.get_width(), 6);
         assert_eq!(input.0.len(), 24);
 
-        let mut expected = expected_scaled_values
+        let expected = expected_scaled_values
+            .parse::<Input>()
+            .expect("Expected output must parse");
+        assert_eq!(input, expected);
+    }
+
+    #[test]
+    #[should_panic(expected = "a map must be tiled at least once each way")]
+    fn test_scale_rejects_no_tiles_across() {
+        let mut input = "12\n34".parse::<Input>().expect("Input must parse");
+        input.scale_with(0, 2, |value, _, _| value);
+    }
+
+    #[test]
+    #[should_panic(expected = "a map must be tiled at least once each way")]
+    fn test_scale_rejects_no_tiles_down() {
+        let mut input = "12\n34".parse::<Input>().expect("Input must parse");
+        input.scale_with(2, 0, |value, _, _| value);
+    }
+
+    #[test]
+    fn test_scale_keeps_zero_risks_in_the_original_tile() {
+        let mut input = "10\n11".parse::<Input>().expect("Input must parse");
+        input.scale(2);
+        let expected = "1021\n1122\n2132\n2233"
             .parse::<Input>()
             .expect("Expected output must parse");
-        input.0.sort();
-        expected.0.sort();
         assert_eq!(input, expected);
     }
 
+    #[test]
+    fn test_rejects_ragged_and_empty_maps() {
+        assert_eq!(
+            "123\n45\n678".parse::<Input>().unwrap_err().to_string(),
+            "line 2, column 3: expected 3 digits like the first line, found 2"
+        );
+        assert!("".parse::<Input>().is_err());
+        assert!("\n\n".parse::<Input>().is_err());
+        assert!("12\n3x".parse::<Input>().is_err());
+    }
+
+    #[test]
+    fn test_from_reader_matches_parse() {
+        for map in [
+            include_str!("test_input.txt"),
+            "1163751\n1381373\n",
+            "1\n2\n3",
+        ] {
+            assert_eq!(
+                Input::from_reader(map.as_bytes()).unwrap(),
+                map.parse::<Input>().unwrap()
+            );
+        }
+        for map in ["123\n45\n678", "", "\n\n", "12\n3x"] {
+            assert_eq!(
+                Input::from_reader(map.as_bytes()).unwrap_err().to_string(),
+                map.parse::<Input>().unwrap_err().to_string(),
+                "{:?}",
+                map
+            );
+        }
+    }
+
+    #[test]
+    fn test_non_square_maps() {
+        let wide = "1163751\n1381373"
+            .parse::<Input>()
+            .expect("Input must parse");
+        assert_eq!((wide.get_width(), wide.get_height()), (7, 2));
+        assert_eq!(wide.clone().into_edges().len(), 6 * 2 + 7);
+        assert_eq!(solve_part1(wide), 24);
+
+        let tall = "11\n91\n19\n11\n91"
+            .parse::<Input>()
+            .expect("Input must parse");
+        assert_eq!(solve_part1(tall.clone()), 13);
+        let mut scaled = tall;
+        scaled.scale(2);
+        assert_eq!((scaled.get_width(), scaled.get_height()), (4, 10));
+        assert_eq!(scaled.get_point(3, 9).map(|point| point.value), Some(3));
+    }
+
+    #[test]
+    fn test_render_with_path() {
+        let input = "131\n191\n111".parse::<Input>().expect("Input must parse");
+        let (_, path) = shortest_path(&input, CostModel::default());
+        assert_eq!(input.render_with_path(&path), "1..\n1..\n111\n");
+        let colored = input.render_with_path_in_color(&path);
+        assert_eq!(colored.matches("\x1b[1;32m").count(), 5);
+        assert_eq!(colored.matches("\x1b[2m").count(), 4);
+        assert_eq!(colored.lines().count(), 3);
+    }
+
     #[test]
     fn test_edge_weighting() {
         let a = Point {
@@ -210,18 +767,257 @@ mod tests {
             y: 1,
             value: 4,
         };
-        let input = Input(vec![a, b, c, d]);
-
-        for (got, expected) in input.into_edges().into_iter().zip(
-            [
-                Edge::new(a, c),
-                Edge::new(a, b),
-                Edge::new(b, d),
-                Edge::new(c, d),
-            ]
-            .into_iter(),
-        ) {
+        let input = "12\n34".parse::<Input>().expect("Input must parse");
+        assert_eq!(input.points().collect::<Vec<_>>(), vec![a, b, c, d]);
+
+        for (got, expected) in input.into_edges().into_iter().zip([
+            Edge::new(a, c),
+            Edge::new(a, b),
+            Edge::new(b, d),
+            Edge::new(c, d),
+        ]) {
             assert_eq!(got, expected);
         }
     }
+
+    #[test]
+    fn test_graph_adapters_agree() {
+        let mut input = include_str!("test_input.txt")
+            .parse::<Input>()
+            .expect("Input must parse");
+        input.scale(2);
+        for movement in [Movement::Orthogonal, Movement::Diagonal] {
+            let map = input.to_graphmap(CostModel::Average, movement);
+            let csr = input.to_csr(CostModel::Average, movement);
+            assert_eq!(map.node_count(), csr.node_count());
+            assert_eq!(map.edge_count(), csr.edge_count());
+            for (from, to, &weight) in map.all_edges() {
+                let from = input.0.index_of(from.x as usize, from.y as usize).unwrap() as u32;
+                let to = input.0.index_of(to.x as usize, to.y as usize).unwrap() as u32;
+                assert!(csr
+                    .edges(from)
+                    .any(|edge| edge.target() == to && *edge.weight() == weight));
+            }
+        }
+        let one = "5".parse::<Input>().expect("Input must parse");
+        let csr = one.to_csr(CostModel::default(), Movement::Orthogonal);
+        assert_eq!(csr.node_count(), 1);
+        assert_eq!(csr[0].value, 5);
+    }
+
+    #[test]
+    fn test_into_directed_graph() {
+        let input = "12\n34".parse::<Input>().expect("Input must parse");
+        let [a, b, c, d]: [Point; 4] = input.points().collect::<Vec<_>>().try_into().unwrap();
+        let graph = input.into_directed_graph();
+        assert_eq!(graph.node_count(), 4);
+        assert_eq!(graph.edge_count(), 8);
+        assert_eq!(graph.edge_weight(a, b), Some(&2));
+        assert_eq!(graph.edge_weight(b, a), Some(&1));
+        assert_eq!(graph.edge_weight(c, d), Some(&4));
+        assert_eq!(graph.edge_weight(d, b), Some(&2));
+        assert_eq!(graph.edge_weight(a, d), None);
+    }
+
+    #[test]
+    fn test_cost_model() {
+        let a = Point {
+            x: 0,
+            y: 0,
+            value: 2,
+        };
+        let b = Point {
+            x: 1,
+            y: 0,
+            value: 7,
+        };
+        assert_eq!(CostModel::default().cost(&a, &b), 7);
+        assert_eq!(CostModel::Enter.cost(&a, &b), 7);
+        assert_eq!(CostModel::Leave.cost(&a, &b), 2);
+        assert_eq!(CostModel::Average.cost(&a, &b), 4);
+        assert_eq!(CostModel::Average.cost(&b, &a), 4);
+        assert_eq!(CostModel::Custom(|_, _| 1).cost(&a, &b), 1);
+    }
+
+    #[test]
+    fn test_into_weighted_edges() {
+        let input = "12".parse::<Input>().expect("Input must parse");
+        let (a, b) = (
+            input.points().next().unwrap(),
+            input.points().nth(1).unwrap(),
+        );
+        assert_eq!(
+            input.clone().into_weighted_edges(CostModel::Enter),
+            vec![(a, b, 2), (b, a, 1)]
+        );
+        assert_eq!(
+            input.into_weighted_edges(CostModel::Leave),
+            vec![(a, b, 1), (b, a, 2)]
+        );
+    }
+}
+
+#[cfg(test)]
+mod solve_tests {
+    use super::*;
+
+    const INPUT: &str = include_str!("test_input.txt");
+
+    #[test]
+    fn solve_part1() {
+        let input = INPUT.parse::<Input>().expect("Input must parse");
+
+        let part1 = super::solve_part1(input);
+        let expected = 40;
+        assert_eq!(part1, expected);
+    }
+    #[test]
+    fn solve_part2() {
+        let input = INPUT.parse::<Input>().expect("Input must parse");
+
+        let part2 = super::solve_part2(input);
+        let expected = 315;
+        assert_eq!(part2, expected);
+    }
+
+    #[test]
+    fn reports_progress() {
+        use aoc_common::progress::{with_progress, Event};
+        use std::sync::mpsc;
+
+        let input = INPUT.parse::<Input>().expect("Input must parse");
+        let (sender, events) = mpsc::channel();
+        with_progress(sender, || super::solve_part1(input));
+        let events: Vec<Event> = events.try_iter().collect();
+        assert_eq!(events[0], Event::Start(Some(100)));
+        assert!(events[1..].iter().all(|event| *event == Event::Advance(1)));
+        assert!(events.len() > 1);
+    }
+
+    #[test]
+    fn shortest_path_is_a_route() {
+        let input = INPUT.parse::<Input>().expect("Input must parse");
+        let (risk, path) = shortest_path(&input, CostModel::default());
+        assert_eq!(risk, 40);
+        assert_eq!((path[0].x, path[0].y), (0, 0));
+        let end = path.last().unwrap();
+        assert_eq!((end.x, end.y), (9, 9));
+        for step in path.windows(2) {
+            let (from, to) = (step[0], step[1]);
+            assert_eq!(from.x.abs_diff(to.x) + from.y.abs_diff(to.y), 1);
+            assert_eq!(input.get_point(to.x, to.y), Some(to));
+        }
+        let entered: u32 = path[1..].iter().map(|point| point.value).sum();
+        assert_eq!(u64::from(entered), risk);
+    }
+
+    #[test]
+    fn strategies_agree() {
+        let mut input = INPUT.parse::<Input>().expect("Input must parse");
+        input.scale(2);
+        for cost_model in [
+            CostModel::Enter,
+            CostModel::Leave,
+            CostModel::Average,
+            CostModel::Custom(|from, to| from.value.abs_diff(to.value)),
+        ] {
+            let expected = shortest_path_using(&input, cost_model, Strategy::Petgraph).0;
+            for strategy in [Strategy::Buckets, Strategy::Heap, Strategy::Bidirectional] {
+                let (risk, path) = shortest_path_using(&input, cost_model, strategy);
+                assert_eq!(risk, expected, "{:?}", strategy);
+                let walked: u32 = path
+                    .windows(2)
+                    .map(|step| cost_model.cost(&step[0], &step[1]))
+                    .sum();
+                assert_eq!(u64::from(walked), risk);
+            }
+        }
+    }
+
+    #[test]
+    fn strategies_agree_on_high_risks() {
+        let mut input = INPUT.parse::<Input>().expect("Input must parse");
+        input.scale_with(2, 1, |value, dx, _| value * (dx as u8 * 20 + 1));
+        let expected = shortest_path_using(&input, CostModel::Enter, Strategy::Petgraph).0;
+        for strategy in [Strategy::Buckets, Strategy::Heap, Strategy::Bidirectional] {
+            assert_eq!(
+                shortest_path_using(&input, CostModel::Enter, strategy).0,
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn heuristics_agree() {
+        let mut input = INPUT.parse::<Input>().expect("Input must parse");
+        input.scale(5);
+        let halved = |point: &Point, end: &Point| heuristic::manhattan(point, end) / 2;
+        for heuristic in [heuristic::manhattan, heuristic::zero] {
+            assert_eq!(
+                shortest_path_with_heuristic(&input, CostModel::default(), heuristic).0,
+                315
+            );
+        }
+        // A weaker guess is slower but finds the same risk, so long as it's never too high
+        assert_eq!(
+            shortest_path_with_heuristic(&input, CostModel::default(), halved).0,
+            315
+        );
+    }
+
+    #[test]
+    fn strategies_agree_moving_diagonally() {
+        let mut input = INPUT.parse::<Input>().expect("Input must parse");
+        input.scale(2);
+        let orthogonal = shortest_path(&input, CostModel::default()).0;
+        for cost_model in [CostModel::Enter, CostModel::Leave, CostModel::Average] {
+            let expected =
+                shortest_path_moving(&input, cost_model, Strategy::Petgraph, Movement::Diagonal).0;
+            for strategy in [Strategy::Buckets, Strategy::Heap, Strategy::Bidirectional] {
+                let (risk, path) =
+                    shortest_path_moving(&input, cost_model, strategy, Movement::Diagonal);
+                assert_eq!(risk, expected, "{:?}", strategy);
+                for step in path.windows(2) {
+                    let (from, to) = (step[0], step[1]);
+                    assert_eq!(from.x.abs_diff(to.x).max(from.y.abs_diff(to.y)), 1);
+                }
+            }
+        }
+        let diagonal = shortest_path_moving(
+            &input,
+            CostModel::default(),
+            Strategy::default(),
+            Movement::Diagonal,
+        );
+        assert!(diagonal.0 < orthogonal);
+        assert!(diagonal
+            .1
+            .windows(2)
+            .any(|step| step[0].x != step[1].x && step[0].y != step[1].y));
+    }
+
+    #[test]
+    fn solve_with_cost_models() {
+        let input = INPUT.parse::<Input>().expect("Input must parse");
+        assert_eq!(solve_with(input.clone(), CostModel::Enter), 40);
+        assert_eq!(
+            solve_with(input.clone(), CostModel::Custom(|_, to| to.value)),
+            40
+        );
+
+        // Leaving every cell from start to end costs the same as entering every cell on the
+        // way back, so flip the grid end-to-end and compare.
+        let flipped = INPUT
+            .lines()
+            .rev()
+            .map(|line| line.chars().rev().collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n")
+            .parse::<Input>()
+            .expect("Input must parse");
+        assert_eq!(
+            solve_with(input, CostModel::Leave),
+            solve_with(flipped, CostModel::Enter)
+        );
+    }
 }
@@ -1,69 +1,41 @@
-use petgraph::algo::astar;
-use petgraph::graphmap::UnGraphMap;
+use aoc_common::{args::Args, timing::PeakAlloc};
+use day15::*;
 
-const INPUT: &str = include_str!("input.txt");
-
-fn solve_part1(input: day15::Input) -> u64 {
-    let graph = UnGraphMap::from_edges(input.into_edges());
-    let start = graph
-        .nodes()
-        .find(|point| point.x == 0 && point.y == 0)
-        .expect("(0, 0) must be contained in the graph");
-    let end = graph
-        .nodes()
-        .reduce(|acc, point| {
-            if (point.x, point.y) > (acc.x, acc.y) {
-                point
-            } else {
-                acc
-            }
-        })
-        .unwrap();
-
-    let (distance, _) = astar(
-        &graph,
-        start,
-        |point| point == end,
-        |(_, dest, _)| dest.value,
-        |point| end.y - point.y + end.x - point.x,
-    )
-    .expect("There must be a path from start to end");
-
-    distance as u64
-}
-
-fn solve_part2(mut input: day15::Input) -> u64 {
-    input.scale(5);
-
-    solve_part1(input)
-}
+#[global_allocator]
+static ALLOC: PeakAlloc = PeakAlloc;
 
 fn main() {
-    let input = INPUT.parse::<day15::Input>().unwrap();
-    let part1 = solve_part1(input.clone());
+    let args = Args::from_env();
+    let raw = args.read_input(INPUT);
+    let mut timer = args.timer();
+    let input = timer.measure("parse", || raw.parse::<Input>().unwrap());
+    if args.visualize {
+        show_path(&input);
+    }
+    let part1 = timer.measure("part1", || solve_part1(input.clone()));
     println!("part1: {}", part1);
-    let part2 = solve_part2(input);
+    let part2 = timer.measure("part2", || solve_part2(input));
     println!("part2: {}", part2);
+    timer.report();
 }
 
-#[cfg(test)]
-mod tests {
-    const INPUT: &str = include_str!("test_input.txt");
-
-    #[test]
-    fn solve_part1() {
-        let input = INPUT.parse::<day15::Input>().expect("Input must parse");
+/// Animates the safest path being drawn over the map
+#[cfg(feature = "visualize")]
+fn show_path(input: &Input) {
+    aoc_viz::Player::new(std::time::Duration::from_millis(30))
+        .play(viz::frames(input.clone()))
+        .expect("Failed to draw to the terminal");
+}
 
-        let part1 = super::solve_part1(input);
-        let expected = 40;
-        assert_eq!(part1, expected);
-    }
-    #[test]
-    fn solve_part2() {
-        let input = INPUT.parse::<day15::Input>().expect("Input must parse");
+/// Prints the map with the safest path picked out, in colour if it's going to a terminal
+#[cfg(not(feature = "visualize"))]
+fn show_path(input: &Input) {
+    use std::io::IsTerminal;
 
-        let part2 = super::solve_part2(input);
-        let expected = 315;
-        assert_eq!(part2, expected);
+    let (_, path) = shortest_path(input, CostModel::default());
+    if std::io::stdout().is_terminal() {
+        print!("{}", input.render_with_path_in_color(&path));
+    } else {
+        print!("{}", input.render_with_path(&path));
     }
 }
@@ -0,0 +1,158 @@
+//! Asking for the safest path again and again while changing the map in between
+
+use crate::{shortest_path_moving, CostModel, Input, Movement, Point, Strategy};
+
+/// A map and how to search it, remembering the last path found until a change to the map
+/// could make a different path safer
+/// ```rust
+/// # use day15::*;
+/// let input: Input = "116\n138\n213".parse().unwrap();
+/// let mut query = PathQuery::new(input, CostModel::default());
+/// assert_eq!(query.shortest_path().0, 7);
+/// assert_eq!(query.set_risk(0, 1, 9), Some(1));
+/// assert_eq!(query.shortest_path().0, 8);
+/// ```
+#[derive(Debug, Clone)]
+pub struct PathQuery {
+    input: Input,
+    cost_model: CostModel,
+    strategy: Strategy,
+    movement: Movement,
+    /// The last path found, if nothing's changed since that could beat it
+    found: Option<(u64, Vec<Point>)>,
+}
+
+impl PathQuery {
+    pub fn new(input: Input, cost_model: CostModel) -> Self {
+        Self {
+            input,
+            cost_model,
+            strategy: Strategy::default(),
+            movement: Movement::default(),
+            found: None,
+        }
+    }
+
+    /// Searches with `strategy` from now on
+    pub fn with_strategy(mut self, strategy: Strategy) -> Self {
+        self.strategy = strategy;
+        self.found = None;
+        self
+    }
+
+    /// Steps to the neighbours `movement` allows from now on
+    pub fn with_movement(mut self, movement: Movement) -> Self {
+        self.movement = movement;
+        self.found = None;
+        self
+    }
+
+    pub fn input(&self) -> &Input {
+        &self.input
+    }
+
+    /// Changes the risk at `(x, y)`, returning what it was, or `None` if that's off the map.
+    ///
+    /// Only steps to or from that point change cost, so the last path stays the safest if the
+    /// risk went up somewhere off it, and the next query doesn't need to search. That only
+    /// holds for the built-in cost models, which never charge less for a higher risk, so a
+    /// custom one searches again after any change.
+    pub fn set_risk(&mut self, x: u32, y: u32, risk: u8) -> Option<u8> {
+        let previous = self.input.set_risk(x, y, risk)?;
+        let still_safest = match &self.found {
+            Some((_, path)) => {
+                risk == previous
+                    || (risk > previous
+                        && !matches!(self.cost_model, CostModel::Custom(_))
+                        && !path.iter().any(|point| (point.x, point.y) == (x, y)))
+            }
+            None => false,
+        };
+        if !still_safest {
+            self.found = None;
+        }
+        Some(previous)
+    }
+
+    /// Lowest total risk from the top left to the bottom right, and the points along the way,
+    /// searching only if the map has changed in a way that matters since last time
+    pub fn shortest_path(&mut self) -> &(u64, Vec<Point>) {
+        let Self {
+            input,
+            cost_model,
+            strategy,
+            movement,
+            found,
+        } = self;
+        found.get_or_insert_with(|| shortest_path_moving(input, *cost_model, *strategy, *movement))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::mpsc;
+
+    use aoc_common::progress::with_progress;
+
+    use super::*;
+
+    const INPUT: &str = include_str!("test_input.txt");
+
+    /// Whether asking `query` for its path now makes it search
+    fn searches(query: &mut PathQuery) -> bool {
+        let (sender, events) = mpsc::channel();
+        with_progress(sender, || {
+            query.shortest_path();
+        });
+        events.try_iter().next().is_some()
+    }
+
+    #[test]
+    fn searches_again_only_when_it_matters() {
+        let input: Input = INPUT.parse().expect("Input must parse");
+        let mut query = PathQuery::new(input, CostModel::default());
+        assert!(searches(&mut query));
+        assert!(!searches(&mut query));
+
+        // Raising the risk off the path can't make another path safer
+        let (_, path) = query.shortest_path().clone();
+        assert!(!path.iter().any(|point| (point.x, point.y) == (9, 0)));
+        assert_eq!(query.set_risk(9, 0, 9), Some(2));
+        assert!(!searches(&mut query));
+        assert_eq!(query.shortest_path().0, 40);
+
+        // Lowering it might
+        query.set_risk(9, 0, 1);
+        assert!(searches(&mut query));
+
+        // So might raising it on the path
+        let on_path = query.shortest_path().1[1];
+        query.set_risk(on_path.x, on_path.y, 9);
+        assert!(searches(&mut query));
+        let mut changed = INPUT.parse::<Input>().expect("Input must parse");
+        changed.set_risk(9, 0, 1);
+        changed.set_risk(on_path.x, on_path.y, 9);
+        assert_eq!(
+            query.shortest_path().0,
+            shortest_path_moving(
+                &changed,
+                CostModel::default(),
+                Strategy::default(),
+                Movement::default()
+            )
+            .0
+        );
+
+        assert_eq!(query.set_risk(10, 0, 1), None);
+        assert!(!searches(&mut query));
+    }
+
+    #[test]
+    fn custom_costs_always_search_again() {
+        let input: Input = INPUT.parse().expect("Input must parse");
+        let mut query = PathQuery::new(input, CostModel::Custom(|_, to| 10 - to.value));
+        query.shortest_path();
+        query.set_risk(9, 0, 9);
+        assert!(searches(&mut query));
+    }
+}
@@ -0,0 +1,54 @@
+//! The part 1 map with the safest path drawn over it, a stretch at a time
+
+use aoc_viz::{Color, Frame};
+
+use crate::{shortest_path, CostModel, Input};
+
+/// How many frames it takes to draw the whole path, however long it is
+const FRAMES: usize = 100;
+
+/// One frame per stretch of the path, ending with all of it drawn
+pub fn frames(input: Input) -> impl Iterator<Item = Frame> {
+    let mut map = Frame::new(input.get_width(), input.get_height());
+    for point in input.points() {
+        let digit = char::from_digit(point.value, 10).unwrap_or('?');
+        map.set(
+            point.x as usize,
+            point.y as usize,
+            digit,
+            Some(Color::DarkGrey),
+        );
+    }
+    let (total, path) = shortest_path(&input, CostModel::default());
+    let stretch = path.len().div_ceil(FRAMES).max(1);
+    let ends = (stretch..path.len()).step_by(stretch).chain([path.len()]);
+    ends.map(move |end| {
+        let mut frame = map.clone();
+        for point in &path[..end] {
+            let digit = char::from_digit(point.value, 10).unwrap_or('?');
+            frame.set(
+                point.x as usize,
+                point.y as usize,
+                digit,
+                Some(Color::Green),
+            );
+        }
+        // The starting point isn't entered, so its risk doesn't count
+        let risk: u32 = path[1..end].iter().map(|point| point.value).sum();
+        frame.with_caption(format!("risk {} of {}", risk, total))
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_frames_end_with_the_whole_path() {
+        let input: Input = "19\n11".parse().unwrap();
+        let frames: Vec<String> = frames(input).map(|frame| frame.to_string()).collect();
+        assert_eq!(frames.last().unwrap(), "19\n11\nrisk 2 of 2");
+        assert_eq!(frames.len(), 3);
+        assert_eq!(frames[0], "19\n11\nrisk 0 of 2");
+    }
+}
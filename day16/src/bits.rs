@@ -0,0 +1,219 @@
+//! Reading a transmission a few bits at a time, straight out of the bytes it arrived in, and
+//! writing one back
+
+use alloc::vec::Vec;
+
+/// Reads numbers of any width up to 64 bits from bytes, most significant bit first
+#[derive(Debug, Clone)]
+pub struct BitReader<'a> {
+    bytes: &'a [u8],
+    /// The next bit to read, counting from the start of `bytes`
+    position: usize,
+    /// One past the last bit this reader may read
+    end: usize,
+}
+
+impl<'a> BitReader<'a> {
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Self::with_len(bytes, bytes.len() * 8)
+    }
+
+    /// Reads only the first `len` bits of `bytes`, for when the last byte is partly padding
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bytes` holds fewer than `len` bits.
+    pub fn with_len(bytes: &'a [u8], len: usize) -> Self {
+        assert!(
+            len <= bytes.len() * 8,
+            "{} bytes hold fewer than {} bits",
+            bytes.len(),
+            len
+        );
+        Self {
+            bytes,
+            position: 0,
+            end: len,
+        }
+    }
+
+    /// How many bits have been read
+    pub fn position(&self) -> usize {
+        self.position
+    }
+
+    /// How many bits are left to read
+    pub fn remaining(&self) -> usize {
+        self.end - self.position
+    }
+
+    /// Reads the next `count` bits as a number, or `None` without reading anything if there
+    /// aren't that many left
+    ///
+    /// # Panics
+    ///
+    /// Panics if `count` is more than 64.
+    pub fn read(&mut self, count: usize) -> Option<u64> {
+        assert!(count <= 64, "can't read {} bits into a u64", count);
+        if count > self.remaining() {
+            return None;
+        }
+        let mut value = 0;
+        let mut left = count;
+        while left > 0 {
+            let offset = self.position % 8;
+            let take = left.min(8 - offset);
+            let byte = self.bytes[self.position / 8];
+            let bits = byte >> (8 - offset - take) & 0xFF >> (8 - take);
+            value = value << take | u64::from(bits);
+            self.position += take;
+            left -= take;
+        }
+        Some(value)
+    }
+
+    /// Whether every bit left is a zero, like the padding at the end of a transmission
+    pub fn is_padding(&self) -> bool {
+        let mut rest = self.clone();
+        while rest.remaining() > 0 {
+            if rest.read(rest.remaining().min(64)) != Some(0) {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Splits off the next `len` bits as a reader of their own, skipping this one past them
+    pub fn split(&mut self, len: usize) -> Option<Self> {
+        if len > self.remaining() {
+            return None;
+        }
+        let split = Self {
+            end: self.position + len,
+            ..self.clone()
+        };
+        self.position += len;
+        Some(split)
+    }
+}
+
+/// Writes numbers of any width up to 64 bits into bytes, most significant bit first, for a
+/// [`BitReader`] to read back
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BitWriter {
+    bytes: Vec<u8>,
+    len: usize,
+}
+
+impl BitWriter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// How many bits have been written
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Writes the lowest `count` bits of `value`
+    ///
+    /// # Panics
+    ///
+    /// Panics if `count` is more than 64.
+    pub fn write(&mut self, value: u64, count: usize) {
+        assert!(count <= 64, "a u64 doesn't have {} bits", count);
+        for shift in (0..count).rev() {
+            if self.len.is_multiple_of(8) {
+                self.bytes.push(0);
+            }
+            let bit = (value >> shift & 1) as u8;
+            self.bytes[self.len / 8] |= bit << (7 - self.len % 8);
+            self.len += 1;
+        }
+    }
+
+    /// The bits written so far, with the last byte padded out with zeros
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    /// A reader over just the bits written so far
+    pub fn reader(&self) -> BitReader<'_> {
+        BitReader::with_len(&self.bytes, self.len)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn reads_across_bytes() {
+        let mut reader = BitReader::new(&[0xD2, 0xFE, 0x28]);
+        assert_eq!(reader.read(3), Some(6));
+        assert_eq!(reader.read(3), Some(4));
+        assert_eq!(reader.read(5), Some(0b10111));
+        assert_eq!(reader.position(), 11);
+        assert_eq!(reader.read(13), Some(0b1111000101000));
+        assert_eq!(reader.read(1), None);
+    }
+
+    #[test]
+    fn reads_whole_words() {
+        let bytes = [0xAB; 9];
+        let mut reader = BitReader::new(&bytes);
+        assert_eq!(reader.read(4), Some(0xA));
+        assert_eq!(reader.read(64), Some(0xBABA_BABA_BABA_BABA));
+        assert_eq!(reader.read(0), Some(0));
+        assert_eq!(reader.remaining(), 4);
+    }
+
+    #[test]
+    fn stops_at_its_length() {
+        let mut reader = BitReader::with_len(&[0xFF], 5);
+        assert_eq!(reader.read(6), None);
+        assert_eq!(reader.position(), 0);
+        assert_eq!(reader.read(5), Some(0b11111));
+        assert_eq!(reader.remaining(), 0);
+    }
+
+    #[test]
+    fn splits_off_a_run_of_bits() {
+        let mut reader = BitReader::new(&[0b1010_0110, 0b1100_0000]);
+        reader.read(2);
+        let mut inner = reader.split(8).unwrap();
+        assert_eq!(reader.position(), 10);
+        assert_eq!(inner.read(9), None);
+        assert_eq!(inner.read(8), Some(0b1001_1011));
+        assert_eq!(reader.read(6), Some(0));
+        assert!(reader.split(1).is_none());
+    }
+
+    #[test]
+    fn knows_padding() {
+        let mut reader = BitReader::with_len(&[0b1000_0000, 0, 0xFF], 12);
+        assert!(!reader.is_padding());
+        reader.read(1);
+        assert!(reader.is_padding());
+        assert!(BitReader::new(&[]).is_padding());
+    }
+
+    #[test]
+    fn writes_what_it_reads() {
+        let mut writer = BitWriter::new();
+        writer.write(6, 3);
+        writer.write(4, 3);
+        writer.write(0b10111, 5);
+        writer.write(u64::MAX, 64);
+        assert_eq!(writer.len(), 75);
+        assert_eq!(writer.as_bytes()[..2], [0xD2, 0xFF]);
+        let mut reader = writer.reader();
+        assert_eq!(reader.read(11), Some(0b11010010111));
+        assert_eq!(reader.read(64), Some(u64::MAX));
+        assert_eq!(reader.remaining(), 0);
+    }
+}
@@ -0,0 +1,182 @@
+//! `day16 decode|eval|stats|tree FILE`, for looking at any transmission rather than solving
+//! the puzzle
+
+use aoc_common::input::InputSource;
+use day16::*;
+
+use crate::repl::tree;
+
+pub const USAGE: &str = "decode|eval|stats|tree FILE
+
+  decode FILE  print each packet in the hex transmission as an expression
+  eval FILE    print the value of each packet
+  stats FILE   count the packets, their kinds and their versions
+  tree FILE    print each packet's subpacket tree
+  decode       explore transmissions interactively
+
+FILE may be `-` to read stdin.";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Command {
+    Decode,
+    Eval,
+    Stats,
+    Tree,
+}
+
+impl Command {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "decode" => Some(Self::Decode),
+            "eval" => Some(Self::Eval),
+            "stats" => Some(Self::Stats),
+            "tree" => Some(Self::Tree),
+            _ => None,
+        }
+    }
+}
+
+const OPERATORS: [OperatorType; 7] = [
+    OperatorType::Sum,
+    OperatorType::Product,
+    OperatorType::Minimum,
+    OperatorType::Maximum,
+    OperatorType::GreaterThan,
+    OperatorType::LessThan,
+    OperatorType::EqualTo,
+];
+
+/// What `command` prints for the hex transmission `hex`, or why it can't
+pub fn report(command: Command, hex: &str) -> Result<String, String> {
+    let transmission = Transmission::from_hex_str(hex).map_err(|e| e.to_string())?;
+    let packets = transmission
+        .packets()
+        .collect::<Result<Vec<Packet>, _>>()
+        .map_err(|e| e.to_string())?;
+    if packets.is_empty() {
+        return Err(String::from("the transmission holds no packets"));
+    }
+    let lines: Vec<String> = match command {
+        Command::Decode => packets.iter().map(ToString::to_string).collect(),
+        Command::Eval => packets
+            .iter()
+            .map(|packet| {
+                packet
+                    .try_value()
+                    .map_or_else(|e| format!("error: {}", e), |value| value.to_string())
+            })
+            .collect(),
+        Command::Tree => packets
+            .iter()
+            .map(|packet| tree(packet).trim_end().to_string())
+            .collect(),
+        Command::Stats => vec![stats(&packets)],
+    };
+    Ok(lines.join("\n"))
+}
+
+fn stats(packets: &[Packet]) -> String {
+    let all = || packets.iter().flat_map(Packet::walk);
+    let literals = all()
+        .filter(|(_, packet)| packet.operator_type().is_none())
+        .count();
+    let operators: Vec<String> = OPERATORS
+        .iter()
+        .map(|&op_type| {
+            let count = all()
+                .filter(|(_, packet)| packet.operator_type() == Some(op_type))
+                .count();
+            (op_type, count)
+        })
+        .filter(|&(_, count)| count > 0)
+        .map(|(op_type, count)| format!("{} {}", op_type, count))
+        .collect();
+    let mut versions = [0; 8];
+    for packet in packets {
+        for (version, count) in packet.version_histogram().into_iter().enumerate() {
+            versions[version] += count;
+        }
+    }
+    let versions: Vec<String> = versions
+        .iter()
+        .enumerate()
+        .filter(|&(_, &count)| count > 0)
+        .map(|(version, count)| format!("v{} {}", version, count))
+        .collect();
+    format!(
+        "top level:   {}\npackets:     {}\nliterals:    {}\noperators:   {}\ndeepest:     {}\nversion sum: {}\nversions:    {}",
+        packets.len(),
+        all().count(),
+        literals,
+        operators.join(", "),
+        all().map(|(depth, _)| depth).max().unwrap_or(0),
+        packets.iter().map(Packet::version_sum).sum::<u64>(),
+        versions.join(", ")
+    )
+}
+
+/// Runs `command` on the transmission in `path`, exiting if it can't
+pub fn run(command: Command, path: &str) {
+    let hex = match InputSource::from_path(path).read("") {
+        Ok(hex) => hex,
+        Err(e) => {
+            eprintln!("couldn't read {}: {}", path, e);
+            std::process::exit(1);
+        }
+    };
+    match report(command, &hex) {
+        Ok(report) => println!("{}", report),
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reports_each_packet() {
+        let hex = "9C0141080250320F1802104A08\n";
+        assert_eq!(
+            report(Command::Decode, "9C0141080250320F1802104A08").unwrap(),
+            "(= (+ 1 3) (* 2 2))"
+        );
+        assert_eq!(report(Command::Eval, "D2FE2E97F140").unwrap(), "2021\n2021");
+        assert!(report(Command::Tree, hex)
+            .unwrap()
+            .starts_with("* v4 operator = (80 bits)\n  [0] v2 operator + (2 subpackets)\n"));
+    }
+
+    #[test]
+    fn counts_everything() {
+        assert_eq!(
+            report(Command::Stats, "620080001611562C8802118E34").unwrap(),
+            "top level:   1
+packets:     7
+literals:    4
+operators:   + 3
+deepest:     2
+version sum: 12
+versions:    v0 3, v1 1, v3 2, v5 1"
+        );
+    }
+
+    #[test]
+    fn reports_bad_transmissions() {
+        assert_eq!(
+            report(Command::Decode, "D2FE2X"),
+            Err(String::from("'X' at character 5 isn't a hex digit"))
+        );
+        assert_eq!(
+            report(Command::Eval, "\n"),
+            Err(String::from("the transmission holds no packets"))
+        );
+        assert_eq!(
+            report(Command::Stats, "D2FE"),
+            Err(String::from("no whole packet at bit 0"))
+        );
+    }
+}
@@ -0,0 +1,233 @@
+//! Writing packets as expressions like `max(1, sum(2, 3))`, for putting together
+//! transmissions by hand
+
+use alloc::{string::String, vec::Vec};
+use core::{fmt::Display, str::FromStr};
+
+use crate::{OperatorType, Packet};
+
+/// Where an expression stopped making sense
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExprError {
+    /// Counting characters, not bytes, from 0
+    pub position: usize,
+    pub expected: &'static str,
+    /// `None` at the end of the expression
+    pub found: Option<char>,
+}
+impl Display for ExprError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "expected {} at character {}, ",
+            self.expected, self.position
+        )?;
+        match self.found {
+            Some(c) => write!(f, "found {:?}", c),
+            None => write!(f, "found the end"),
+        }
+    }
+}
+#[cfg(feature = "std")]
+impl std::error::Error for ExprError {}
+
+/// The names operators go by, with `minimum` and `maximum` spelled out too
+const OPERATORS: [(&str, OperatorType); 9] = [
+    ("product", OperatorType::Product),
+    ("minimum", OperatorType::Minimum),
+    ("maximum", OperatorType::Maximum),
+    ("sum", OperatorType::Sum),
+    ("min", OperatorType::Minimum),
+    ("max", OperatorType::Maximum),
+    ("gt", OperatorType::GreaterThan),
+    ("lt", OperatorType::LessThan),
+    ("eq", OperatorType::EqualTo),
+];
+
+struct Parser {
+    chars: Vec<char>,
+    position: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.position).copied()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.position += 1;
+        }
+    }
+
+    fn error(&self, expected: &'static str) -> ExprError {
+        ExprError {
+            position: self.position,
+            expected,
+            found: self.peek(),
+        }
+    }
+
+    fn expect(&mut self, c: char, expected: &'static str) -> Result<(), ExprError> {
+        self.skip_whitespace();
+        if self.peek() != Some(c) {
+            return Err(self.error(expected));
+        }
+        self.position += 1;
+        Ok(())
+    }
+
+    fn expr(&mut self) -> Result<Packet, ExprError> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some(c) if c.is_ascii_digit() => self.literal(),
+            Some(c) if c.is_ascii_alphabetic() => self.operator(),
+            _ => Err(self.error("a number or an operator")),
+        }
+    }
+
+    fn literal(&mut self) -> Result<Packet, ExprError> {
+        let start = self.position;
+        let mut value: u128 = 0;
+        while let Some(digit) = self.peek().and_then(|c| c.to_digit(10)) {
+            value = value
+                .checked_mul(10)
+                .and_then(|value| value.checked_add(digit.into()))
+                .ok_or(ExprError {
+                    position: start,
+                    expected: "a number below 2^128",
+                    found: self.chars.get(start).copied(),
+                })?;
+            self.position += 1;
+        }
+        Ok(Packet::literal(0, value))
+    }
+
+    fn operator(&mut self) -> Result<Packet, ExprError> {
+        let start = self.position;
+        while self.peek().is_some_and(|c| c.is_ascii_alphanumeric()) {
+            self.position += 1;
+        }
+        let name: String = self.chars[start..self.position].iter().collect();
+        let op_type = OPERATORS
+            .iter()
+            .find(|(known, _)| *known == name)
+            .map(|&(_, op_type)| op_type)
+            .ok_or(ExprError {
+                position: start,
+                expected: "an operator: sum, product, min, max, gt, lt or eq",
+                found: self.chars.get(start).copied(),
+            })?;
+        self.expect('(', "`(`")?;
+        let mut body = Vec::new();
+        match op_type {
+            OperatorType::GreaterThan | OperatorType::LessThan | OperatorType::EqualTo => {
+                body.push(self.expr()?);
+                self.expect(',', "`,` before the second operand")?;
+                body.push(self.expr()?);
+            }
+            _ => loop {
+                body.push(self.expr()?);
+                self.skip_whitespace();
+                if self.peek() != Some(',') {
+                    break;
+                }
+                self.position += 1;
+            },
+        }
+        self.expect(')', "`)`")?;
+        Ok(Packet::operator(0, op_type, body))
+    }
+}
+
+/// Parses an expression like `max(1, sum(2, 3))`, with every version 0. Operators are
+/// `sum`, `product`, `min`, `max`, `gt`, `lt` and `eq`, taking at least one operand, or
+/// exactly two for the comparisons.
+/// ```rust
+/// use day16::Packet;
+/// let packet: Packet = "eq(sum(1, 3), product(2, 2))".parse().unwrap();
+/// assert_eq!(packet.to_string(), "(= (+ 1 3) (* 2 2))");
+/// assert_eq!(packet.value(), 1);
+/// let bits = packet.to_bits().unwrap();
+/// assert_eq!(Packet::new(&bits), packet);
+/// ```
+impl FromStr for Packet {
+    type Err = ExprError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parser = Parser {
+            chars: s.chars().collect(),
+            position: 0,
+        };
+        let packet = parser.expr()?;
+        parser.skip_whitespace();
+        if parser.peek().is_some() {
+            return Err(parser.error("the end"));
+        }
+        Ok(packet)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parses_nested_expressions() {
+        let packet: Packet = " max( 1,sum(2 ,3) )\n".parse().unwrap();
+        assert_eq!(
+            packet,
+            Packet::operator(
+                0,
+                OperatorType::Maximum,
+                vec![
+                    Packet::literal(0, 1),
+                    Packet::operator(
+                        0,
+                        OperatorType::Sum,
+                        vec![Packet::literal(0, 2), Packet::literal(0, 3)]
+                    ),
+                ]
+            )
+        );
+        assert_eq!(packet.value(), 5);
+        assert_eq!(
+            "minimum(7)".parse::<Packet>().unwrap().to_string(),
+            "(min 7)"
+        );
+        let max = u128::MAX.to_string();
+        assert_eq!(max.parse::<Packet>(), Ok(Packet::literal(0, u128::MAX)));
+    }
+
+    #[test]
+    fn reports_where_it_went_wrong() {
+        let error = |s: &str| s.parse::<Packet>().unwrap_err();
+        assert_eq!(
+            error("sum(1, 2"),
+            ExprError {
+                position: 8,
+                expected: "`)`",
+                found: None
+            }
+        );
+        assert_eq!(
+            error("gt(1, 2, 3)").to_string(),
+            "expected `)` at character 7, found ','"
+        );
+        assert_eq!(
+            error("lt(1)").to_string(),
+            "expected `,` before the second operand at character 4, found ')'"
+        );
+        assert_eq!(
+            error("sum()").to_string(),
+            "expected a number or an operator at character 4, found ')'"
+        );
+        assert_eq!(error("mean(1)").position, 0);
+        assert_eq!(error("1 2").expected, "the end");
+        assert_eq!(error("").found, None);
+        assert_eq!(
+            error("340282366920938463463374607431768211456").expected,
+            "a number below 2^128"
+        );
+    }
+}
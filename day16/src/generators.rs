@@ -0,0 +1,92 @@
+//! Strategies for packets
+
+use aoc_common::strategies;
+use proptest::prelude::*;
+
+use crate::{BitWriter, LengthType, MessageType, OperatorType, Packet, PacketRef};
+
+const OPERATORS: [OperatorType; 7] = [
+    OperatorType::Sum,
+    OperatorType::Product,
+    OperatorType::Minimum,
+    OperatorType::Maximum,
+    OperatorType::GreaterThan,
+    OperatorType::LessThan,
+    OperatorType::EqualTo,
+];
+
+/// An operator over `body`, giving its length either way
+fn operator(version: u8, op_type: OperatorType, by_count: bool, body: Vec<Packet>) -> Packet {
+    let mut packet = Packet::operator(version, op_type, body);
+    if !by_count {
+        let bits = packet.body.iter().map(Packet::bit_len).sum();
+        packet.message_type = MessageType::Operator(LengthType::TotalLengthInBits(bits), op_type);
+    }
+    packet
+}
+
+/// Literals and operators nested a few deep. Comparisons always get exactly two operands.
+pub fn packet() -> impl Strategy<Value = Packet> {
+    let literal =
+        (0..8u8, any::<u128>()).prop_map(|(version, value)| Packet::literal(version, value));
+    strategies::tree(literal, |subpacket| {
+        (
+            0..8u8,
+            prop::sample::select(OPERATORS.to_vec()),
+            any::<bool>(),
+            prop::collection::vec(subpacket, 2..4),
+        )
+            .prop_map(|(version, op_type, by_count, mut body)| {
+                if matches!(
+                    op_type,
+                    OperatorType::GreaterThan | OperatorType::LessThan | OperatorType::EqualTo
+                ) {
+                    body.truncate(2);
+                }
+                operator(version, op_type, by_count, body)
+            })
+    })
+}
+
+proptest! {
+    #![proptest_config(strategies::config())]
+
+    #[test]
+    fn hex_round_trips(packet in packet()) {
+        let hex = packet.to_hex_string().unwrap();
+        prop_assert_eq!(Packet::from_hex_str(&hex), Some(packet));
+    }
+
+    #[test]
+    fn reads_no_further_than_its_own_bits(packet in packet(), rest in "[01]{1,8}") {
+        let bits = format!("{}{}", packet.to_bits().unwrap(), rest);
+        let mut iter = bits.chars();
+        prop_assert_eq!(Packet::from_iterator(&mut iter), Some(packet));
+        prop_assert_eq!(iter.collect::<String>(), rest);
+    }
+
+    #[test]
+    fn knows_its_length(packet in packet()) {
+        prop_assert_eq!(packet.to_bits().unwrap().len(), packet.bit_len());
+    }
+
+    #[test]
+    fn has_consistent_length_fields(packet in packet()) {
+        prop_assert_eq!(packet.validate(), Ok(()));
+    }
+
+    #[test]
+    fn reads_the_same_in_place(packet in packet()) {
+        let mut bits = BitWriter::new();
+        packet.write_bits(&mut bits).unwrap();
+        let packet_ref = PacketRef::from_reader(&mut bits.reader()).unwrap();
+        prop_assert_eq!(packet_ref.bits(), 0..bits.len());
+        prop_assert_eq!(packet_ref.version_sum(), packet.version_sum());
+        prop_assert_eq!(packet_ref.to_packet(), packet);
+    }
+
+    #[test]
+    fn rejects_non_hex(hex in "[0-9A-F]{0,6}[G-Zg-z.,][0-9A-F]{0,6}") {
+        prop_assert_eq!(Packet::from_hex_str(&hex), None);
+    }
+}
@@ -1,11 +1,126 @@
-use std::iter::Sum;
+//! Decodes the BITS transmission. Only the puzzle input and the runner's `part1`/`part2` need
+//! `std`, so with `default-features = false` the decoder runs anywhere there's an allocator.
+
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+#[cfg(feature = "std")]
+use aoc_common::answer::Answer;
+use core::{convert::Infallible, fmt::Display, iter::Sum, str::FromStr};
+
+mod bits;
+mod expr;
+#[cfg(test)]
+mod generators;
+mod packet_ref;
+mod transmission;
+
+pub use bits::{BitReader, BitWriter};
+pub use expr::ExprError;
+pub use packet_ref::{PacketRef, SubpacketRefs};
+pub use transmission::{Malformed, NotHex, Packets, Transmission};
+
+#[cfg(feature = "std")]
+pub const INPUT: &str = include_str!("input.txt");
+
+/// The first packet in the input, or why there isn't one
+#[cfg(feature = "std")]
+fn parse_input(input: &str) -> Result<Packet, String> {
+    let transmission = Transmission::from_hex_str(input).map_err(|e| e.to_string())?;
+    let packet = transmission
+        .packets()
+        .next()
+        .ok_or_else(|| String::from("Input holds no packets"))?;
+    packet.map_err(|e| e.to_string())
+}
+
+/// Sum of the version numbers of every packet
+#[cfg(feature = "std")]
+pub fn part1(input: &str) -> Result<Answer, String> {
+    Ok(parse_input(input)?.version_sum().into())
+}
+
+/// Value of the outermost packet
+#[cfg(feature = "std")]
+pub fn part2(input: &str) -> Result<Answer, String> {
+    parse_input(input)?
+        .try_value()
+        .map(Answer::from)
+        .map_err(|e| e.to_string())
+}
+
+#[cfg(feature = "std")]
+aoc_common::register_day!(16);
+
+#[derive(Debug, Clone)]
+pub struct Input(String);
+impl Display for Input {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+impl FromStr for Input {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Input(s.to_string()))
+    }
+}
+
+pub fn solve_part1(input: Input) -> u64 {
+    let packet = Packet::from_hex_str(&input.to_string()).expect("Input must parse");
+    packet.versions().sum()
+}
+
+pub fn solve_part2(input: Input) -> u64 {
+    let packet = Packet::from_hex_str(&input.to_string()).expect("Input must parse");
+    packet.value()
+}
+
+/// Reads with `read` from the `0`s and `1`s that `iterator` yields, then moves `iterator` past
+/// just the bits that were read
+fn read_chars<I, T>(iterator: &mut I, read: impl FnOnce(&mut BitReader) -> Option<T>) -> Option<T>
+where
+    I: Iterator<Item = char> + Clone,
+{
+    let mut bits = BitWriter::new();
+    for bit in iterator.clone().map_while(|c| c.to_digit(2)) {
+        bits.write(u64::from(bit), 1);
+    }
+    let mut reader = bits.reader();
+    let value = read(&mut reader);
+    if reader.position() > 0 {
+        iterator.nth(reader.position() - 1);
+    }
+    value
+}
 
 #[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub struct PacketVersion(u8); // three bits
 impl PacketVersion {
-    pub fn from_iterator<I: Iterator<Item = char>>(iterator: &mut I) -> Option<Self> {
-        let digits = iterator.take(3).collect::<String>();
-        Some(PacketVersion(u8::from_str_radix(&digits, 2).ok()?))
+    pub fn from_reader(reader: &mut BitReader) -> Option<Self> {
+        Some(PacketVersion(reader.read(3)? as u8))
+    }
+
+    pub fn from_iterator<I: Iterator<Item = char> + Clone>(iterator: &mut I) -> Option<Self> {
+        read_chars(iterator, Self::from_reader)
+    }
+}
+impl Display for PacketVersion {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+impl From<PacketVersion> for u8 {
+    fn from(version: PacketVersion) -> Self {
+        version.0
     }
 }
 impl Sum<PacketVersion> for u64 {
@@ -13,29 +128,27 @@ impl Sum<PacketVersion> for u64 {
         iter.fold(0, |acc, next| acc + next.0 as u64)
     }
 }
+/// The longest body, in bits, that an operator's 15-bit length field can give
+const MAX_TOTAL_LENGTH: usize = (1 << 15) - 1;
+/// The most subpackets an operator's 11-bit count field can give
+const MAX_SUBPACKET_COUNT: usize = (1 << 11) - 1;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum LengthType {
     TotalLengthInBits(usize),
     SubpacketCount(usize),
 }
 impl LengthType {
-    pub fn from_iterator<I: Iterator<Item = char>>(iterator: &mut I) -> Self {
-        let type_id = iterator.next().expect("This shouldn't be able to fail");
-        match type_id {
-            '0' => {
-                let num_bytes = usize::from_str_radix(&iterator.take(15).collect::<String>(), 2)
-                    .expect("This shouldn't be able to fail");
-                Self::TotalLengthInBits(num_bytes)
-            }
-            '1' => {
-                let subpacket_count =
-                    usize::from_str_radix(&iterator.take(11).collect::<String>(), 2)
-                        .expect("This shouldn't be able to fail");
-                Self::SubpacketCount(subpacket_count)
-            }
-            _ => unreachable!(),
+    pub fn from_reader(reader: &mut BitReader) -> Option<Self> {
+        match reader.read(1)? {
+            0 => Some(Self::TotalLengthInBits(reader.read(15)? as usize)),
+            _ => Some(Self::SubpacketCount(reader.read(11)? as usize)),
         }
     }
+
+    pub fn from_iterator<I: Iterator<Item = char> + Clone>(iterator: &mut I) -> Option<Self> {
+        read_chars(iterator, Self::from_reader)
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -61,39 +174,136 @@ impl OperatorType {
             _ => None,
         }
     }
+
+    fn type_id(self) -> u8 {
+        match self {
+            Self::Sum => 0,
+            Self::Product => 1,
+            Self::Minimum => 2,
+            Self::Maximum => 3,
+            Self::GreaterThan => 5,
+            Self::LessThan => 6,
+            Self::EqualTo => 7,
+        }
+    }
+}
+impl Display for OperatorType {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let symbol = match self {
+            Self::Sum => "+",
+            Self::Product => "*",
+            Self::Minimum => "min",
+            Self::Maximum => "max",
+            Self::GreaterThan => ">",
+            Self::LessThan => "<",
+            Self::EqualTo => "=",
+        };
+        write!(f, "{}", symbol)
+    }
 }
 
-pub struct TypeId(u8); // three bits
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum MessageType {
-    Literal(u64),
+    /// Up to 128 bits, though only values that fit in a `u64` can be evaluated
+    Literal(u128),
     Operator(LengthType, OperatorType),
 }
 impl MessageType {
-    pub fn from_iterator<I: Iterator<Item = char>>(iterator: &mut I) -> Option<Self> {
-        let digits = iterator.take(3).collect::<String>();
-        let type_id = u8::from_str_radix(&digits, 2).ok()?;
+    /// `None` if the bits run out, or a literal is too big for a `u128`
+    pub fn from_reader(reader: &mut BitReader) -> Option<Self> {
+        let type_id = reader.read(3)? as u8;
         match type_id {
             4 => {
-                let mut s = String::new();
+                let mut value: u128 = 0;
                 loop {
-                    if let Some('1') = iterator.next() {
-                        s.extend(iterator.take(4));
-                    } else {
-                        s.extend(iterator.take(4));
+                    let more = reader.read(1)? == 1;
+                    value = value.checked_mul(16)? | u128::from(reader.read(4)?);
+                    if !more {
                         break;
                     }
                 }
-                Some(Self::Literal(u64::from_str_radix(&s, 2).ok()?))
+                Some(Self::Literal(value))
             }
             x => {
-                let length_type = LengthType::from_iterator(iterator);
+                let length_type = LengthType::from_reader(reader)?;
                 let operator_type = OperatorType::from_type_id(x)?;
                 Some(Self::Operator(length_type, operator_type))
             }
         }
     }
+
+    pub fn from_iterator<I: Iterator<Item = char> + Clone>(iterator: &mut I) -> Option<Self> {
+        read_chars(iterator, Self::from_reader)
+    }
+}
+
+/// Why a packet's expression can't be evaluated
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EvalError {
+    /// A comparison without exactly two operands, or any other operator without at least one
+    Arity {
+        operator: OperatorType,
+        operands: usize,
+    },
+    /// The result doesn't fit in a `u64`
+    Overflow(OperatorType),
+    /// A literal too big for a `u64`
+    BigLiteral(u128),
+}
+impl Display for EvalError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::Arity { operator, operands } => {
+                let needs = match operator {
+                    OperatorType::GreaterThan | OperatorType::LessThan | OperatorType::EqualTo => {
+                        "exactly 2 operands"
+                    }
+                    _ => "at least 1 operand",
+                };
+                write!(f, "`{}` needs {}, found {}", operator, needs, operands)
+            }
+            Self::Overflow(operator) => write!(f, "`{}` overflowed a u64", operator),
+            Self::BigLiteral(value) => write!(f, "literal {} doesn't fit in a u64", value),
+        }
+    }
+}
+#[cfg(feature = "std")]
+impl std::error::Error for EvalError {}
+
+/// An operator whose length field doesn't match its body
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LengthMismatch {
+    /// Which subpacket to take at each level to get from the outermost packet to this one
+    pub path: Vec<usize>,
+    pub declared: LengthType,
+    /// What the field should say, of the same kind as `declared`
+    pub actual: LengthType,
+}
+impl Display for LengthMismatch {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        if self.path.is_empty() {
+            write!(f, "the outermost packet")?;
+        } else {
+            let path: Vec<String> = self.path.iter().map(ToString::to_string).collect();
+            write!(f, "subpacket {}", path.join("."))?;
+        }
+        match (self.declared, self.actual) {
+            (LengthType::SubpacketCount(declared), LengthType::SubpacketCount(actual)) => {
+                write!(f, " says it has {} subpackets but has {}", declared, actual)
+            }
+            (LengthType::TotalLengthInBits(declared), LengthType::TotalLengthInBits(actual)) => {
+                write!(
+                    f,
+                    " says its subpackets take {} bits but they take {}",
+                    declared, actual
+                )
+            }
+            (declared, actual) => write!(f, " says {:?} but has {:?}", declared, actual),
+        }
+    }
 }
+#[cfg(feature = "std")]
+impl std::error::Error for LengthMismatch {}
 
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct Packet {
@@ -102,59 +312,172 @@ pub struct Packet {
     pub body: Vec<Packet>,
 }
 
+/// Pretty-prints the packet as an s-expression, e.g. `(= (+ 1 3) (* 2 2))`
+impl Display for Packet {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self.message_type {
+            MessageType::Literal(v) => write!(f, "{}", v),
+            MessageType::Operator(_, op_type) => {
+                write!(f, "({}", op_type)?;
+                for subpacket in self.subpackets() {
+                    write!(f, " {}", subpacket)?;
+                }
+                write!(f, ")")
+            }
+        }
+    }
+}
+
 impl Packet {
+    /// A literal packet holding `value`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `version` doesn't fit in 3 bits.
+    pub fn literal(version: u8, value: u128) -> Self {
+        assert!(version < 8, "version {} doesn't fit in 3 bits", version);
+        Self {
+            version: PacketVersion(version),
+            message_type: MessageType::Literal(value),
+            body: Vec::new(),
+        }
+    }
+
+    /// An operator over `body`, which counts its subpackets if there are few enough to, or
+    /// else gives their length in bits.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `version` doesn't fit in 3 bits.
+    pub fn operator(version: u8, op_type: OperatorType, body: Vec<Packet>) -> Self {
+        assert!(version < 8, "version {} doesn't fit in 3 bits", version);
+        let length_type = if body.len() <= MAX_SUBPACKET_COUNT {
+            LengthType::SubpacketCount(body.len())
+        } else {
+            LengthType::TotalLengthInBits(body.iter().map(Packet::bit_len).sum())
+        };
+        Self {
+            version: PacketVersion(version),
+            message_type: MessageType::Operator(length_type, op_type),
+            body,
+        }
+    }
+
+    /// Parses a packet from a string of `0`s and `1`s.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the bits aren't a whole packet.
     pub fn new(s: &str) -> Self {
         Self::from_iterator(&mut s.chars()).unwrap()
     }
 
+    /// Evaluates the expression the packet describes.
+    ///
+    /// # Panics
+    ///
+    /// Panics if [`Packet::try_value`] fails.
     pub fn value(&self) -> u64 {
-        match self.message_type {
-            MessageType::Literal(v) => v,
-            MessageType::Operator(_, op_type) => match op_type {
-                OperatorType::Sum => self.subpackets().fold(0, |acc, next| acc + next.value()),
-                OperatorType::Product => self.subpackets().fold(1, |acc, next| acc * next.value()),
-                OperatorType::Minimum => self.subpackets().fold(u64::MAX, |acc, next| {
-                    let value = next.value();
-                    if value < acc {
-                        value
-                    } else {
-                        acc
-                    }
-                }),
-                OperatorType::Maximum => self.subpackets().fold(u64::MIN, |acc, next| {
-                    let value = next.value();
-                    if value > acc {
-                        value
-                    } else {
-                        acc
-                    }
-                }),
-                OperatorType::GreaterThan => {
-                    if let &[a, b] = self.subpackets().collect::<Vec<&Packet>>().as_slice() {
-                        (a.value() > b.value()).into()
-                    } else {
-                        panic!("This should provably not happen");
-                    }
-                }
-                OperatorType::LessThan => {
-                    if let &[a, b] = self.subpackets().collect::<Vec<&Packet>>().as_slice() {
-                        (a.value() < b.value()).into()
-                    } else {
-                        panic!("This should provably not happen");
-                    }
+        self.try_value().unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Evaluates the expression the packet describes, or says why it can't be: an operator
+    /// with the wrong number of operands, or arithmetic that overflows a `u64`
+    /// ```rust
+    /// use day16::{EvalError, OperatorType, Packet};
+    /// let packet = Packet::operator(0, OperatorType::LessThan, vec![Packet::literal(0, 1)]);
+    /// assert_eq!(
+    ///     packet.try_value(),
+    ///     Err(EvalError::Arity { operator: OperatorType::LessThan, operands: 1 })
+    /// );
+    /// ```
+    pub fn try_value(&self) -> Result<u64, EvalError> {
+        let operator = match self.message_type {
+            MessageType::Literal(v) => {
+                return u64::try_from(v).map_err(|_| EvalError::BigLiteral(v))
+            }
+            MessageType::Operator(_, op_type) => op_type,
+        };
+        let values = self
+            .subpackets()
+            .map(Packet::try_value)
+            .collect::<Result<Vec<u64>, _>>()?;
+        let arity = EvalError::Arity {
+            operator,
+            operands: values.len(),
+        };
+        let overflow = EvalError::Overflow(operator);
+        match (operator, values.as_slice()) {
+            (OperatorType::GreaterThan, &[a, b]) => Ok((a > b).into()),
+            (OperatorType::LessThan, &[a, b]) => Ok((a < b).into()),
+            (OperatorType::EqualTo, &[a, b]) => Ok((a == b).into()),
+            (OperatorType::GreaterThan | OperatorType::LessThan | OperatorType::EqualTo, _) => {
+                Err(arity)
+            }
+            (_, []) => Err(arity),
+            (OperatorType::Sum, values) => values
+                .iter()
+                .try_fold(0u64, |acc, &next| acc.checked_add(next))
+                .ok_or(overflow),
+            (OperatorType::Product, values) => values
+                .iter()
+                .try_fold(1u64, |acc, &next| acc.checked_mul(next))
+                .ok_or(overflow),
+            (OperatorType::Minimum, values) => values.iter().copied().min().ok_or(arity),
+            (OperatorType::Maximum, values) => values.iter().copied().max().ok_or(arity),
+        }
+    }
+
+    /// Reads one packet and everything in it, leaving `reader` just past its last bit.
+    ///
+    /// Returns `None` if the bits run out part way through a packet, or describe one that
+    /// can't be evaluated, like a comparison without exactly two operands.
+    /// ```rust
+    /// use day16::{BitReader, Packet};
+    /// let mut reader = BitReader::new(&[0xD2, 0xFE, 0x28]);
+    /// let packet = Packet::from_reader(&mut reader).unwrap();
+    /// assert_eq!(packet.to_string(), "2021");
+    /// assert_eq!(reader.remaining(), 3);
+    /// ```
+    pub fn from_reader(reader: &mut BitReader) -> Option<Self> {
+        let version = PacketVersion::from_reader(reader)?;
+        let message_type = MessageType::from_reader(reader)?;
+        let body = match message_type {
+            MessageType::Literal(_) => Vec::new(),
+            MessageType::Operator(LengthType::TotalLengthInBits(bits), _) => {
+                let mut body_bits = reader.split(bits)?;
+                let mut subpackets = Vec::new();
+                while body_bits.remaining() > 0 {
+                    subpackets.push(Packet::from_reader(&mut body_bits)?);
                 }
-                OperatorType::EqualTo => {
-                    if let &[a, b] = self.subpackets().collect::<Vec<&Packet>>().as_slice() {
-                        (a.value() == b.value()).into()
-                    } else {
-                        panic!("This should provably not happen");
-                    }
+                subpackets
+            }
+            MessageType::Operator(LengthType::SubpacketCount(count), _) => {
+                let mut subpackets = Vec::with_capacity(count);
+                for _ in 0..count {
+                    subpackets.push(Packet::from_reader(reader)?);
                 }
-            },
+                subpackets
+            }
+        };
+        if let MessageType::Operator(
+            _,
+            OperatorType::GreaterThan | OperatorType::LessThan | OperatorType::EqualTo,
+        ) = message_type
+        {
+            if body.len() != 2 {
+                return None;
+            }
         }
+        Some(Self {
+            version,
+            message_type,
+            body,
+        })
     }
 
-    /// Consume from an iterator until a valid packet is formed, but no further
+    /// Consume from an iterator of `0`s and `1`s until a valid packet is formed, but no
+    /// further. `None` in the same cases as [`Packet::from_reader`].
     /// ```rust
     /// use day16::Packet;
     /// // Two sets of packets
@@ -169,42 +492,185 @@ impl Packet {
     /// assert_eq!(packet1, Packet::new("110100101111111000101"));
     /// assert_eq!(packet2, Packet::new("110100101111111000101000"));
     /// ```
-    pub fn from_iterator<I: Iterator<Item = char>>(iterator: &mut I) -> Option<Self> {
-        let version = PacketVersion::from_iterator(iterator)?;
-        let message_type = MessageType::from_iterator(iterator)?;
-        let body = match message_type {
-            MessageType::Literal(_) => Vec::new(),
-            MessageType::Operator(LengthType::TotalLengthInBits(bits), _) => {
-                let bytes = &mut iterator
-                    .by_ref()
-                    .take(bits)
-                    .collect::<Vec<char>>()
-                    .into_iter();
-                let mut subpackets = Vec::new();
-                while let Some(packet) = Packet::from_iterator(bytes) {
-                    subpackets.push(packet);
+    pub fn from_iterator<I: Iterator<Item = char> + Clone>(iterator: &mut I) -> Option<Self> {
+        read_chars(iterator, Self::from_reader)
+    }
+
+    /// The length field [`Packet::write_bits`] gives an operator: the kind it already has if
+    /// its body fits, or else the other kind. `None` for a literal, or a body that fits neither.
+    fn length_field(&self) -> Option<LengthType> {
+        let MessageType::Operator(length_type, _) = self.message_type else {
+            return None;
+        };
+        let by_count = (self.body.len() <= MAX_SUBPACKET_COUNT)
+            .then_some(LengthType::SubpacketCount(self.body.len()));
+        let by_bits = || {
+            let bits = self.body.iter().map(Packet::bit_len).sum();
+            (bits <= MAX_TOTAL_LENGTH).then_some(LengthType::TotalLengthInBits(bits))
+        };
+        match length_type {
+            LengthType::TotalLengthInBits(_) => by_bits().or(by_count),
+            LengthType::SubpacketCount(_) => by_count.or_else(by_bits),
+        }
+    }
+
+    /// Checks every operator's length field against its body, for packets that were put
+    /// together or edited by hand rather than parsed. Lengths in bits are what
+    /// [`Packet::bit_len`] gives the subpackets.
+    /// ```rust
+    /// use day16::{LengthType, OperatorType, Packet};
+    /// let mut packet = Packet::operator(0, OperatorType::Sum, vec![Packet::literal(0, 1)]);
+    /// assert_eq!(packet.validate(), Ok(()));
+    /// packet.body.push(Packet::literal(0, 2));
+    /// let mismatches = packet.validate().unwrap_err();
+    /// assert_eq!(mismatches[0].declared, LengthType::SubpacketCount(1));
+    /// assert_eq!(
+    ///     mismatches[0].to_string(),
+    ///     "the outermost packet says it has 1 subpackets but has 2"
+    /// );
+    /// ```
+    pub fn validate(&self) -> Result<(), Vec<LengthMismatch>> {
+        let mut mismatches = Vec::new();
+        self.collect_mismatches(&mut Vec::new(), &mut mismatches);
+        if mismatches.is_empty() {
+            Ok(())
+        } else {
+            Err(mismatches)
+        }
+    }
+
+    /// Adds this packet's mismatch, then those of everything beneath it, outermost first
+    fn collect_mismatches(&self, path: &mut Vec<usize>, mismatches: &mut Vec<LengthMismatch>) {
+        if let MessageType::Operator(declared, _) = self.message_type {
+            let actual = match declared {
+                LengthType::TotalLengthInBits(_) => {
+                    LengthType::TotalLengthInBits(self.body.iter().map(Packet::bit_len).sum())
                 }
-                subpackets
+                LengthType::SubpacketCount(_) => LengthType::SubpacketCount(self.body.len()),
+            };
+            if declared != actual {
+                mismatches.push(LengthMismatch {
+                    path: path.clone(),
+                    declared,
+                    actual,
+                });
             }
+        }
+        for (index, subpacket) in self.subpackets().enumerate() {
+            path.push(index);
+            subpacket.collect_mismatches(path, mismatches);
+            path.pop();
+        }
+    }
 
-            MessageType::Operator(LengthType::SubpacketCount(count), _) => {
-                let mut subpackets = Vec::with_capacity(count);
-                for _ in 0..count {
-                    if let Some(packet) = Packet::from_iterator(iterator) {
-                        subpackets.push(packet);
+    /// How many bits [`Packet::write_bits`] writes for this packet
+    pub fn bit_len(&self) -> usize {
+        match self.message_type {
+            MessageType::Literal(value) => 6 + 5 * literal_groups(value),
+            MessageType::Operator(..) => {
+                let field = match self.length_field() {
+                    Some(LengthType::TotalLengthInBits(_)) => 15,
+                    Some(LengthType::SubpacketCount(_)) | None => 11,
+                };
+                7 + field + self.body.iter().map(Packet::bit_len).sum::<usize>()
+            }
+        }
+    }
+
+    /// Writes the packet the way it'd arrive in a transmission, the reverse of
+    /// [`Packet::from_reader`]. Lengths are worked out from the body rather than trusted.
+    ///
+    /// Returns `None`, having written part of the packet, if an operator has more than 2047
+    /// subpackets and more than 32767 bits of them, which no length field can describe.
+    pub fn write_bits(&self, writer: &mut BitWriter) -> Option<()> {
+        writer.write(self.version.0.into(), 3);
+        match self.message_type {
+            MessageType::Literal(value) => {
+                writer.write(4, 3);
+                for group in (0..literal_groups(value)).rev() {
+                    writer.write((group > 0).into(), 1);
+                    writer.write((value >> (group * 4) & 0xF) as u64, 4);
+                }
+            }
+            MessageType::Operator(_, op_type) => {
+                writer.write(op_type.type_id().into(), 3);
+                match self.length_field()? {
+                    LengthType::TotalLengthInBits(bits) => {
+                        writer.write(0, 1);
+                        writer.write(bits as u64, 15);
+                    }
+                    LengthType::SubpacketCount(count) => {
+                        writer.write(1, 1);
+                        writer.write(count as u64, 11);
                     }
                 }
-                subpackets
+                for subpacket in self.subpackets() {
+                    subpacket.write_bits(writer)?;
+                }
             }
-        };
-        Some(Self {
-            version,
-            message_type,
-            body,
-        })
+        }
+        Some(())
+    }
+
+    /// The packet as `0`s and `1`s, which [`Packet::new`] reads back. `None` in the same
+    /// cases as [`Packet::write_bits`].
+    /// ```rust
+    /// use day16::{OperatorType, Packet};
+    /// let packet = Packet::operator(1, OperatorType::LessThan, vec![
+    ///     Packet::literal(0, 4),
+    ///     Packet::literal(2, 8),
+    /// ]);
+    /// let bits = packet.to_bits().unwrap();
+    /// assert_eq!(bits, "0011101000000000100001000010001010001000");
+    /// assert_eq!(Packet::new(&bits), packet);
+    /// ```
+    pub fn to_bits(&self) -> Option<String> {
+        let mut writer = BitWriter::new();
+        self.write_bits(&mut writer)?;
+        let mut reader = writer.reader();
+        Some(
+            (0..writer.len())
+                .map(|_| match reader.read(1) {
+                    Some(1) => '1',
+                    _ => '0',
+                })
+                .collect(),
+        )
     }
 
-    /// Construct from a hex str
+    /// The packet as a hex transmission, with zeros padding out the last digit. `None` in the
+    /// same cases as [`Packet::write_bits`].
+    /// ```rust
+    /// use day16::Packet;
+    /// let packet = Packet::from_hex_str("38006F45291200").unwrap();
+    /// assert_eq!(packet.to_hex_string().unwrap(), "38006F4529120");
+    /// assert_eq!(Packet::literal(6, 2021).to_hex_string().unwrap(), "D2FE28");
+    /// ```
+    pub fn to_hex_string(&self) -> Option<String> {
+        let mut writer = BitWriter::new();
+        self.write_bits(&mut writer)?;
+        let mut hex: String = writer
+            .as_bytes()
+            .iter()
+            .map(|byte| format!("{:02X}", byte))
+            .collect();
+        hex.truncate(writer.len().div_ceil(4));
+        Some(hex)
+    }
+
+    /// Decodes a transmission given as its raw bytes rather than hex text, like a binary file
+    /// or a network buffer. Bits after the packet are ignored, as padding.
+    /// ```rust
+    /// use day16::Packet;
+    /// let packet = Packet::from_bytes(&[0xD2, 0xFE, 0x28]).unwrap();
+    /// assert_eq!(packet, Packet::from_hex_str("D2FE28").unwrap());
+    /// ```
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        Packet::from_reader(&mut BitReader::new(bytes))
+    }
+
+    /// Construct from a hex str, which may be lowercase and have whitespace in it. `None` if
+    /// it has anything else, or doesn't start with a whole packet.
     /// ```rust
     /// use day16::Packet;
     /// let packet = Packet::from_hex_str("D2FE28").unwrap();
@@ -212,18 +678,17 @@ impl Packet {
     /// assert_eq!(packet, expected)
     /// ```
     pub fn from_hex_str(hexstr: &str) -> Option<Self> {
-        let mapper = |c: char| -> Option<String> { c.to_digit(16).map(|d| format!("{:04b}", d)) };
-        let s = hexstr
-            .chars()
-            .map(mapper)
-            .collect::<Option<Vec<String>>>()?
-            .join("");
-        // let n = u64::from_str_radix(hexstr, 16).ok()?;
-        // let mut s = format!("{:b}", n);
-        // while s.len() % 4 > 0 {
-        //     s = format!("0{}", s);
-        // }
-        Some(Packet::new(&s))
+        Packet::from_reader(&mut Transmission::from_hex_str(hexstr).ok()?.reader())
+    }
+
+    /// The sum of the versions of this packet and every packet beneath it
+    /// ```rust
+    /// use day16::Packet;
+    /// let packet = Packet::from_hex_str("8A004A801A8002F478").unwrap();
+    /// assert_eq!(packet.version_sum(), 16);
+    /// ```
+    pub fn version_sum(&self) -> u64 {
+        self.versions().sum()
     }
 
     /// An iterator over the subpackets
@@ -267,17 +732,111 @@ impl Packet {
     /// }
     /// ```
     pub fn traverse_subpackets(&self) -> impl Iterator<Item = &Packet> {
-        let mut flattened: Vec<&Packet> = Vec::new();
-        for subpacket in self.subpackets() {
-            flattened.push(subpacket);
-            if !subpacket.body.is_empty() {
-                flattened.extend(subpacket.traverse_subpackets())
-            }
+        self.walk().skip(1).map(|(_, packet)| packet)
+    }
+
+    /// This packet and every packet beneath it, each with how many levels down it is,
+    /// outermost first and each subpacket's own subpackets before its next sibling
+    /// ```rust
+    /// use day16::Packet;
+    /// let packet: Packet = "sum(1, max(2, 3), 4)".parse().unwrap();
+    /// let depths: Vec<(usize, String)> = packet
+    ///     .walk()
+    ///     .map(|(depth, packet)| (depth, packet.to_string()))
+    ///     .collect();
+    /// assert_eq!(depths[..3], [
+    ///     (0, String::from("(+ 1 (max 2 3) 4)")),
+    ///     (1, String::from("1")),
+    ///     (1, String::from("(max 2 3)")),
+    /// ]);
+    /// assert_eq!(depths[3], (2, String::from("2")));
+    /// ```
+    pub fn walk(&self) -> Walk<'_> {
+        Walk {
+            stack: vec![(0, self)],
+        }
+    }
+
+    /// The packets, this one included, that `predicate` picks out, in the order
+    /// [`Packet::walk`] gives
+    /// ```rust
+    /// use day16::{OperatorType, Packet};
+    /// let packet: Packet = "product(sum(1, 2), sum(3, min(4)))".parse().unwrap();
+    /// let sums = packet.find(|packet| packet.operator_type() == Some(OperatorType::Sum));
+    /// assert_eq!(sums.map(Packet::value).collect::<Vec<_>>(), vec![3, 7]);
+    /// ```
+    pub fn find<F>(&self, mut predicate: F) -> impl Iterator<Item = &Packet>
+    where
+        F: FnMut(&Packet) -> bool,
+    {
+        self.walk()
+            .map(|(_, packet)| packet)
+            .filter(move |packet| predicate(packet))
+    }
+
+    /// The packets exactly `depth` levels down, with this one at depth 0
+    pub fn at_depth(&self, depth: usize) -> impl Iterator<Item = &Packet> {
+        self.walk()
+            .filter(move |&(at, _)| at == depth)
+            .map(|(_, packet)| packet)
+    }
+
+    /// The versions of this packet and every packet beneath it
+    pub fn versions(&self) -> impl Iterator<Item = PacketVersion> + '_ {
+        self.walk().map(|(_, packet)| packet.version)
+    }
+
+    /// How many packets, this one included, have each version
+    /// ```rust
+    /// use day16::Packet;
+    /// let packet = Packet::from_hex_str("8A004A801A8002F478").unwrap();
+    /// assert_eq!(packet.version_histogram(), [0, 1, 0, 0, 1, 1, 1, 0]);
+    /// ```
+    pub fn version_histogram(&self) -> [usize; 8] {
+        let mut histogram = [0; 8];
+        for version in self.versions() {
+            histogram[usize::from(version.0)] += 1;
         }
-        flattened.into_iter()
+        histogram
+    }
+
+    /// What an operator does, or `None` for a literal
+    pub fn operator_type(&self) -> Option<OperatorType> {
+        match self.message_type {
+            MessageType::Literal(_) => None,
+            MessageType::Operator(_, op_type) => Some(op_type),
+        }
+    }
+}
+
+/// Every packet in a tree, from [`Packet::walk`]
+#[derive(Debug, Clone)]
+pub struct Walk<'a> {
+    /// What's left to visit, next on top
+    stack: Vec<(usize, &'a Packet)>,
+}
+
+impl<'a> Iterator for Walk<'a> {
+    type Item = (usize, &'a Packet);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (depth, packet) = self.stack.pop()?;
+        self.stack.extend(
+            packet
+                .body
+                .iter()
+                .rev()
+                .map(|subpacket| (depth + 1, subpacket)),
+        );
+        Some((depth, packet))
     }
 }
 
+/// How many 4-bit groups a literal needs to hold `value`, at least one even for zero
+fn literal_groups(value: u128) -> usize {
+    ((128 - value.leading_zeros() as usize).div_ceil(4)).max(1)
+}
+
 #[cfg(test)]
 mod solve_tests {
     use super::*;
@@ -292,11 +851,7 @@ mod solve_tests {
     #[test]
     fn test_sum_versions() {
         fn sum_versions(packet: Packet) -> u64 {
-            vec![&packet]
-                .into_iter()
-                .chain(packet.traverse_subpackets())
-                .map(|packet| packet.version)
-                .sum::<u64>()
+            packet.versions().sum()
         }
 
         assert_eq!(sum_versions(Packet::from_hex_str(INPUT[0]).unwrap()), 16);
@@ -308,10 +863,7 @@ mod solve_tests {
     #[test]
     fn test_count_packets() {
         fn count_packets(packet: Packet) -> usize {
-            vec![&packet]
-                .into_iter()
-                .chain(packet.traverse_subpackets())
-                .count()
+            packet.walk().count()
         }
         let packet = Packet::from_hex_str(INPUT[0]).expect("Input must parse");
         assert_eq!(count_packets(packet), 4);
@@ -343,6 +895,28 @@ mod packet_tests {
             Packet::new("100010100000000001001010100000000001101010000000000000101111010001111000");
         assert_eq!(packet, expected);
     }
+    #[test]
+    fn rejects_malformed_packets() {
+        // Runs out of bits in the length type of an operator
+        assert_eq!(Packet::from_hex_str("0"), None);
+        assert_eq!(Packet::from_hex_str(""), None);
+        // A literal whose last group is cut short
+        assert_eq!(Packet::from_iterator(&mut "1101001000001".chars()), None);
+        // A literal with 33 groups doesn't fit in a u128
+        let huge = format!("110100{}{}", "11111".repeat(32), "01111");
+        assert_eq!(Packet::from_iterator(&mut huge.chars()), None);
+        // Says it has two subpackets but only has one
+        assert_eq!(
+            Packet::from_iterator(&mut "0000001000000000101101001000001000".chars()),
+            None
+        );
+        // A comparison with a single operand
+        assert_eq!(
+            Packet::from_iterator(&mut "0001011000000000011101001000001000".chars()),
+            None
+        );
+    }
+
     #[test]
     fn from_iterator() {
         let s = String::from("00111000000000000110111101000101001010010001001000000000");
@@ -483,4 +1057,204 @@ mod packet_tests {
         // = (+ 1 3) (* 2 2)
         assert_eq!(packet.value(), 1);
     }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn encodes_the_puzzle_input() {
+        let packet = Packet::from_hex_str(INPUT.trim()).unwrap();
+        let hex = packet.to_hex_string().unwrap();
+        assert!(INPUT.starts_with(&hex));
+    }
+
+    #[test]
+    fn encodes_big_bodies_with_the_other_length_field() {
+        let many = |count| vec![Packet::literal(0, 1); count];
+        let packet = Packet::operator(0, OperatorType::Sum, many(2048));
+        assert_eq!(
+            packet.message_type,
+            MessageType::Operator(LengthType::TotalLengthInBits(2048 * 11), OperatorType::Sum)
+        );
+        assert_eq!(
+            Packet::from_hex_str(&packet.to_hex_string().unwrap()),
+            Some(packet)
+        );
+
+        let mut packet = Packet::operator(0, OperatorType::Sum, many(3000));
+        assert_eq!(packet.to_bits(), None);
+        packet.body.truncate(10);
+        assert_eq!(
+            packet.to_bits().map(|bits| bits.len()),
+            Some(7 + 15 + 10 * 11)
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn validates_length_fields() {
+        let packet = Packet::from_hex_str(INPUT.trim()).unwrap();
+        assert_eq!(packet.validate(), Ok(()));
+
+        // (+ 1 (* 2 3)) with the product's body measured in bits
+        let mut product = Packet::operator(
+            0,
+            OperatorType::Product,
+            vec![Packet::literal(0, 2), Packet::literal(0, 3)],
+        );
+        product.message_type =
+            MessageType::Operator(LengthType::TotalLengthInBits(22), OperatorType::Product);
+        let mut packet =
+            Packet::operator(0, OperatorType::Sum, vec![Packet::literal(0, 1), product]);
+        assert_eq!(packet.validate(), Ok(()));
+
+        packet.body[1].body.pop();
+        packet.body.push(Packet::literal(0, 4));
+        let mismatches = packet.validate().unwrap_err();
+        assert_eq!(
+            mismatches,
+            vec![
+                LengthMismatch {
+                    path: vec![],
+                    declared: LengthType::SubpacketCount(2),
+                    actual: LengthType::SubpacketCount(3),
+                },
+                LengthMismatch {
+                    path: vec![1],
+                    declared: LengthType::TotalLengthInBits(22),
+                    actual: LengthType::TotalLengthInBits(11),
+                },
+            ]
+        );
+        assert_eq!(
+            mismatches[1].to_string(),
+            "subpacket 1 says its subpackets take 22 bits but they take 11"
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn reports_bad_input() {
+        assert_eq!(part1("8a004a801a8002f478\n"), Ok(16u64.into()));
+        assert_eq!(
+            part1("8A00\n4Z"),
+            Err(String::from("'Z' at character 6 isn't a hex digit"))
+        );
+        assert_eq!(part1("  \n"), Err(String::from("Input holds no packets")));
+        assert_eq!(part2("D2FE"), Err(String::from("no whole packet at bit 0")));
+    }
+
+    #[test]
+    fn queries_by_depth_and_type() {
+        let packet = Packet::from_hex_str("620080001611562C8802118E34").unwrap();
+        assert_eq!(packet.at_depth(0).count(), 1);
+        assert_eq!(packet.at_depth(2).count(), 4);
+        assert_eq!(packet.at_depth(3).count(), 0);
+        let literals: Vec<&Packet> = packet
+            .find(|packet| packet.operator_type().is_none())
+            .collect();
+        assert_eq!(literals, packet.at_depth(2).collect::<Vec<_>>());
+        assert_eq!(packet.version_histogram().iter().sum::<usize>(), 7);
+        assert_eq!(
+            packet.find(|packet| u8::from(packet.version) == 3).count(),
+            packet.version_histogram()[3]
+        );
+    }
+
+    #[test]
+    fn from_bytes() {
+        let packet = Packet::from_hex_str("8A004A801A8002F478").unwrap();
+        let bytes = [0x8A, 0x00, 0x4A, 0x80, 0x1A, 0x80, 0x02, 0xF4, 0x78];
+        assert_eq!(Packet::from_bytes(&bytes), Some(packet));
+        assert_eq!(Packet::from_bytes(&bytes[..8]), None);
+        assert_eq!(Packet::from_bytes(&[]), None);
+    }
+
+    #[test]
+    fn checked_value() {
+        let literal = |value| Packet::literal(0, value);
+        let operator = |op_type, body| Packet::operator(0, op_type, body);
+        assert_eq!(
+            operator(
+                OperatorType::EqualTo,
+                vec![literal(1), literal(1), literal(1)]
+            )
+            .try_value(),
+            Err(EvalError::Arity {
+                operator: OperatorType::EqualTo,
+                operands: 3
+            })
+        );
+        assert_eq!(
+            operator(OperatorType::Minimum, vec![]).try_value(),
+            Err(EvalError::Arity {
+                operator: OperatorType::Minimum,
+                operands: 0
+            })
+        );
+        let product = operator(
+            OperatorType::Product,
+            vec![literal(1 << 32), literal(1 << 32)],
+        );
+        assert_eq!(
+            product.try_value(),
+            Err(EvalError::Overflow(OperatorType::Product))
+        );
+        // An error anywhere below spoils the whole expression
+        let sum = operator(OperatorType::Sum, vec![literal(1), product.clone()]);
+        assert_eq!(
+            sum.try_value().unwrap_err().to_string(),
+            "`*` overflowed a u64"
+        );
+        assert_eq!(
+            operator(
+                OperatorType::Sum,
+                vec![literal(u64::MAX.into()), literal(0)]
+            )
+            .try_value(),
+            Ok(u64::MAX)
+        );
+        #[cfg(feature = "std")]
+        assert_eq!(
+            part2(&product.to_hex_string().unwrap()),
+            Err(String::from("`*` overflowed a u64"))
+        );
+    }
+
+    #[test]
+    fn big_literals() {
+        // 17 groups, one more than a u64 holds
+        let bits = format!("110100{}{}", "11111".repeat(16), "01111");
+        let packet = Packet::new(&bits);
+        let value = (1 << 68) - 1;
+        assert_eq!(packet.message_type, MessageType::Literal(value));
+        assert_eq!(packet.to_bits(), Some(bits));
+        assert_eq!(packet.try_value(), Err(EvalError::BigLiteral(value)));
+        let comparison = Packet::operator(
+            0,
+            OperatorType::GreaterThan,
+            vec![packet, Packet::literal(0, 1)],
+        );
+        assert_eq!(
+            comparison.try_value().unwrap_err().to_string(),
+            "literal 295147905179352825855 doesn't fit in a u64"
+        );
+        assert_eq!(comparison.version_sum(), 6);
+        let max = Packet::literal(0, u128::MAX);
+        assert_eq!(Packet::new(&max.to_bits().unwrap()), max);
+    }
+
+    #[test]
+    #[should_panic(expected = "`<` needs exactly 2 operands, found 1")]
+    fn value_panics_when_unevaluable() {
+        Packet::operator(0, OperatorType::LessThan, vec![Packet::literal(0, 1)]).value();
+    }
+
+    #[test]
+    fn display() {
+        let packet = Packet::from_hex_str("9C0141080250320F1802104A08").expect("Input must parse");
+        assert_eq!(packet.to_string(), "(= (+ 1 3) (* 2 2))");
+        let packet = Packet::from_hex_str("D2FE28").expect("Input must parse");
+        assert_eq!(packet.to_string(), "2021");
+        let packet = Packet::from_hex_str("880086C3E88112").expect("Input must parse");
+        assert_eq!(packet.to_string(), "(min 7 8 9)");
+    }
 }
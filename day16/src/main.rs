@@ -1,42 +1,37 @@
-use std::{convert::Infallible, str::FromStr};
-
+use aoc_common::{args::Args, timing::PeakAlloc};
 use day16::*;
 
-const INPUT: &str = include_str!("input.txt");
+#[global_allocator]
+static ALLOC: PeakAlloc = PeakAlloc;
 
-#[derive(Debug, Clone)]
-struct Input(String);
-impl ToString for Input {
-    fn to_string(&self) -> String {
-        self.0.clone()
-    }
-}
-impl FromStr for Input {
-    type Err = Infallible;
+mod cli;
+mod repl;
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        Ok(Input(s.to_string()))
+fn main() {
+    let command_args: Vec<String> = std::env::args().skip(1).collect();
+    if let Some(command) = command_args
+        .first()
+        .and_then(|name| cli::Command::from_name(name))
+    {
+        match (command, &command_args[1..]) {
+            (cli::Command::Decode, []) => repl::run(std::io::stdin().lock(), std::io::stdout())
+                .expect("Failed to talk to terminal"),
+            (_, [path]) => cli::run(command, path),
+            _ => {
+                eprintln!("usage: day16 {}", cli::USAGE);
+                std::process::exit(2);
+            }
+        }
+        return;
     }
-}
 
-fn solve_part1(input: Input) -> u64 {
-    let packet = Packet::from_hex_str(&input.to_string()).expect("Input must parse");
-    vec![&packet]
-        .into_iter()
-        .chain(packet.traverse_subpackets())
-        .map(|packet| packet.version)
-        .sum::<u64>()
-}
-
-fn solve_part2(input: Input) -> u64 {
-    let packet = Packet::from_hex_str(&input.to_string()).expect("Input must parse");
-    packet.value()
-}
-
-fn main() {
-    let input = INPUT.parse::<Input>().expect("Input must parse");
-    let part1 = solve_part1(input.clone());
+    let args = Args::from_env();
+    let raw = args.read_input(INPUT);
+    let mut timer = args.timer();
+    let input = timer.measure("parse", || raw.parse::<Input>().expect("Input must parse"));
+    let part1 = timer.measure("part1", || solve_part1(input.clone()));
     println!("part1: {part1}");
-    let part2 = solve_part2(input);
+    let part2 = timer.measure("part2", || solve_part2(input));
     println!("part2: {part2}");
+    timer.report();
 }
@@ -0,0 +1,183 @@
+//! Packets read in place, for looking over transmissions too big to want a copy of
+
+use core::ops::Range;
+
+use crate::{BitReader, LengthType, MessageType, OperatorType, Packet, PacketVersion};
+
+/// A packet that's still in the bytes it arrived in. Only its header is decoded; subpackets
+/// are read from the bytes each time they're asked for, so nothing is allocated.
+///
+/// Going over every subpacket beneath a packet reads each one once per packet above it, so
+/// deep trees are slower to walk than with [`Packet`], which reads them once in all.
+/// ```rust
+/// use day16::{Packet, PacketRef};
+/// let bytes = [0x38, 0x00, 0x6F, 0x45, 0x29, 0x12, 0x00];
+/// let packet = PacketRef::from_bytes(&bytes).unwrap();
+/// assert_eq!(packet.bits(), 0..49);
+/// let literals: Vec<_> = packet.subpackets().map(|subpacket| subpacket.bits()).collect();
+/// assert_eq!(literals, vec![22..33, 33..49]);
+/// assert_eq!(packet.to_packet(), Packet::from_bytes(&bytes).unwrap());
+/// ```
+#[derive(Debug, Clone)]
+pub struct PacketRef<'a> {
+    pub version: PacketVersion,
+    pub message_type: MessageType,
+    /// Where the packet starts and ends, in bits from the start of the bytes
+    bits: Range<usize>,
+    /// Just the subpackets' bits
+    body: BitReader<'a>,
+    subpacket_count: usize,
+}
+
+impl<'a> PacketRef<'a> {
+    /// Finds the end of one packet and checks everything in it can be read, leaving `reader`
+    /// just past its last bit. `None` in the same cases as [`Packet::from_reader`].
+    pub fn from_reader(reader: &mut BitReader<'a>) -> Option<Self> {
+        let start = reader.position();
+        let version = PacketVersion::from_reader(reader)?;
+        let message_type = MessageType::from_reader(reader)?;
+        let mut body = reader.clone();
+        let subpacket_count = match message_type {
+            MessageType::Literal(_) => 0,
+            MessageType::Operator(LengthType::TotalLengthInBits(bits), _) => {
+                let mut body_bits = reader.split(bits)?;
+                let mut count = 0;
+                while body_bits.remaining() > 0 {
+                    PacketRef::from_reader(&mut body_bits)?;
+                    count += 1;
+                }
+                count
+            }
+            MessageType::Operator(LengthType::SubpacketCount(count), _) => {
+                for _ in 0..count {
+                    PacketRef::from_reader(reader)?;
+                }
+                count
+            }
+        };
+        if let MessageType::Operator(
+            _,
+            OperatorType::GreaterThan | OperatorType::LessThan | OperatorType::EqualTo,
+        ) = message_type
+        {
+            if subpacket_count != 2 {
+                return None;
+            }
+        }
+        let body = body.split(reader.position() - body.position())?;
+        Some(Self {
+            version,
+            message_type,
+            bits: start..reader.position(),
+            body,
+            subpacket_count,
+        })
+    }
+
+    /// The packet at the start of `bytes`, ignoring any bits after it
+    pub fn from_bytes(bytes: &'a [u8]) -> Option<Self> {
+        Self::from_reader(&mut BitReader::new(bytes))
+    }
+
+    /// Where the packet starts and ends, in bits from the start of its bytes
+    pub fn bits(&self) -> Range<usize> {
+        self.bits.clone()
+    }
+
+    /// The packets directly beneath this one
+    pub fn subpackets(&self) -> SubpacketRefs<'a> {
+        SubpacketRefs {
+            body: self.body.clone(),
+            left: self.subpacket_count,
+        }
+    }
+
+    /// The sum of the versions of this packet and every packet beneath it
+    pub fn version_sum(&self) -> u64 {
+        u64::from(self.version.0)
+            + self
+                .subpackets()
+                .map(|subpacket| subpacket.version_sum())
+                .sum::<u64>()
+    }
+
+    /// Decodes the whole packet into one of its own
+    pub fn to_packet(&self) -> Packet {
+        Packet {
+            version: self.version,
+            message_type: self.message_type,
+            body: self
+                .subpackets()
+                .map(|subpacket| subpacket.to_packet())
+                .collect(),
+        }
+    }
+}
+
+impl From<PacketRef<'_>> for Packet {
+    fn from(packet: PacketRef<'_>) -> Self {
+        packet.to_packet()
+    }
+}
+
+/// The packets directly beneath a [`PacketRef`]
+#[derive(Debug, Clone)]
+pub struct SubpacketRefs<'a> {
+    body: BitReader<'a>,
+    left: usize,
+}
+
+impl<'a> Iterator for SubpacketRefs<'a> {
+    type Item = PacketRef<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.left == 0 {
+            return None;
+        }
+        self.left -= 1;
+        Some(PacketRef::from_reader(&mut self.body).expect("checked when the parent was read"))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.left, Some(self.left))
+    }
+}
+
+impl ExactSizeIterator for SubpacketRefs<'_> {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::Transmission;
+
+    #[test]
+    fn reads_in_place() {
+        let transmission = Transmission::from_hex_str("8A004A801A8002F478").unwrap();
+        let packet = PacketRef::from_reader(&mut transmission.reader()).unwrap();
+        assert_eq!(packet.version_sum(), 16);
+        assert_eq!(packet.bits(), 0..69);
+        let mut depth = 0;
+        let mut current = packet.clone();
+        while let Some(subpacket) = current.subpackets().next() {
+            assert_eq!(current.subpackets().len(), 1);
+            current = subpacket;
+            depth += 1;
+        }
+        assert_eq!(depth, 3);
+        assert_eq!(current.message_type, MessageType::Literal(15));
+        assert_eq!(
+            Packet::from(packet),
+            Packet::from_hex_str("8A004A801A8002F478").unwrap()
+        );
+    }
+
+    #[test]
+    fn rejects_what_packet_rejects() {
+        // Says it has two subpackets but only has one
+        let mut bits = crate::BitWriter::new();
+        bits.write(0b0000001000000000101101001000001000, 34);
+        assert!(PacketRef::from_reader(&mut bits.reader()).is_none());
+        assert!(PacketRef::from_bytes(&[0x8A, 0x00, 0x4A, 0x80]).is_none());
+        assert!(PacketRef::from_bytes(&[]).is_none());
+    }
+}
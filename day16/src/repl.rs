@@ -0,0 +1,207 @@
+use std::io::{self, BufRead, Write};
+
+use day16::*;
+
+const HELP: &str = "\
+<hex>     decode a transmission and make it the current packet
+show      summarize the current packet
+tree      print the current packet's subpacket tree
+cd <n>    drill into the current packet's nth subpacket (0-indexed)
+cd ..     go back up one level
+top       go back to the outermost packet
+help      print this message
+quit      exit";
+
+/// Interactive state: the transmission being inspected and where in it we are
+#[derive(Default)]
+pub struct Session {
+    root: Option<Packet>,
+    path: Vec<usize>,
+}
+
+impl Session {
+    fn current(&self) -> Option<&Packet> {
+        let mut packet = self.root.as_ref()?;
+        for &idx in &self.path {
+            packet = packet.body.get(idx)?;
+        }
+        Some(packet)
+    }
+
+    fn location(&self) -> String {
+        std::iter::once(String::from("top"))
+            .chain(self.path.iter().map(|idx| idx.to_string()))
+            .collect::<Vec<_>>()
+            .join("/")
+    }
+
+    /// Handles one line of input, returning what to print or `None` to exit
+    pub fn handle(&mut self, line: &str) -> Option<String> {
+        let mut words = line.split_whitespace();
+        let output = match (words.next(), words.next()) {
+            (None, _) => String::new(),
+            (Some("quit" | "exit"), _) => return None,
+            (Some("help"), _) => HELP.to_string(),
+            (Some("show"), _) => self.show(),
+            (Some("tree"), _) => match self.current() {
+                Some(packet) => tree(packet).trim_end().to_string(),
+                None => no_packet(),
+            },
+            (Some("top"), _) => {
+                self.path.clear();
+                self.show()
+            }
+            (Some("cd"), Some("..")) => {
+                self.path.pop();
+                self.show()
+            }
+            (Some("cd"), Some(idx)) => self.descend(idx),
+            (Some(hex), None) => match Packet::from_hex_str(hex) {
+                Some(packet) => {
+                    self.root = Some(packet);
+                    self.path.clear();
+                    self.show()
+                }
+                None => format!("{:?} is neither a command nor a hex transmission", hex),
+            },
+            (Some(word), Some(_)) => format!("Unknown command {:?}, try `help`", word),
+        };
+        Some(output)
+    }
+
+    fn descend(&mut self, idx: &str) -> String {
+        let Some(current) = self.current() else {
+            return no_packet();
+        };
+        match idx.parse::<usize>() {
+            Ok(idx) if idx < current.body.len() => {
+                self.path.push(idx);
+                self.show()
+            }
+            _ => format!(
+                "{:?} is not a subpacket; this packet has {} subpacket(s)",
+                idx,
+                current.body.len()
+            ),
+        }
+    }
+
+    fn show(&self) -> String {
+        let Some(packet) = self.current() else {
+            return no_packet();
+        };
+        format!(
+            "at:          {}\nversion sum: {}\nvalue:       {}\nexpression:  {}\n{}",
+            self.location(),
+            packet.version_sum(),
+            packet
+                .try_value()
+                .map_or_else(|e| e.to_string(), |value| value.to_string()),
+            packet,
+            tree(packet).trim_end()
+        )
+    }
+}
+
+fn no_packet() -> String {
+    String::from("No packet yet, paste a hex transmission first")
+}
+
+/// The packet and its subpackets, one per line, indented by depth
+pub fn tree(packet: &Packet) -> String {
+    fn walk(packet: &Packet, label: String, depth: usize, out: &mut String) {
+        let description = match packet.message_type {
+            MessageType::Literal(v) => format!("literal {}", v),
+            MessageType::Operator(LengthType::TotalLengthInBits(bits), op_type) => {
+                format!("operator {} ({} bits)", op_type, bits)
+            }
+            MessageType::Operator(LengthType::SubpacketCount(count), op_type) => {
+                format!("operator {} ({} subpackets)", op_type, count)
+            }
+        };
+        out.push_str(&format!(
+            "{}{} v{} {}\n",
+            "  ".repeat(depth),
+            label,
+            packet.version,
+            description
+        ));
+        for (idx, subpacket) in packet.subpackets().enumerate() {
+            walk(subpacket, format!("[{}]", idx), depth + 1, out);
+        }
+    }
+    let mut out = String::new();
+    walk(packet, String::from("*"), 0, &mut out);
+    out
+}
+
+pub fn run<R: BufRead, W: Write>(input: R, mut output: W) -> io::Result<()> {
+    let mut session = Session::default();
+    writeln!(output, "Paste a hex transmission, or `help` for commands")?;
+    write!(output, "> ")?;
+    output.flush()?;
+    for line in input.lines() {
+        match session.handle(&line?) {
+            Some(response) => writeln!(output, "{}", response)?,
+            None => break,
+        }
+        write!(output, "> ")?;
+        output.flush()?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn session_navigation() {
+        let mut session = Session::default();
+        assert_eq!(session.handle("show"), Some(no_packet()));
+
+        let shown = session.handle("9C0141080250320F1802104A08").unwrap();
+        assert!(shown.contains("expression:  (= (+ 1 3) (* 2 2))"));
+        assert!(shown.contains("value:       1"));
+
+        let shown = session.handle("cd 1").unwrap();
+        assert!(shown.contains("at:          top/1"));
+        assert!(shown.contains("expression:  (* 2 2)"));
+        assert!(shown.contains("value:       4"));
+
+        let shown = session.handle("cd 0").unwrap();
+        assert!(shown.contains("at:          top/1/0"));
+        assert!(shown.contains("value:       2"));
+
+        assert!(session.handle("cd 0").unwrap().contains("not a subpacket"));
+
+        assert!(session
+            .handle("cd ..")
+            .unwrap()
+            .contains("at:          top/1"));
+        assert!(session
+            .handle("top")
+            .unwrap()
+            .contains("at:          top\n"));
+        assert_eq!(session.handle("quit"), None);
+    }
+
+    #[test]
+    fn tree_output() {
+        let packet = Packet::from_hex_str("38006F45291200").unwrap();
+        assert_eq!(
+            tree(&packet),
+            "* v1 operator < (27 bits)\n  [0] v6 literal 10\n  [1] v2 literal 20\n"
+        );
+    }
+
+    #[test]
+    fn run_reads_until_quit() {
+        let input = "D2FE28\nquit\nshow\n";
+        let mut output = Vec::new();
+        run(input.as_bytes(), &mut output).unwrap();
+        let output = String::from_utf8(output).unwrap();
+        assert!(output.contains("value:       2021"));
+        assert_eq!(output.matches("> ").count(), 2);
+    }
+}
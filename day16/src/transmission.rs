@@ -0,0 +1,225 @@
+//! Transmissions holding several packets one after another
+
+use alloc::vec::Vec;
+use core::{fmt::Display, iter::FusedIterator};
+
+use crate::{BitReader, BitWriter, Packet};
+
+/// The bits of a transmission, which can hold any number of packets back to back, with zeros
+/// padding out the end
+/// ```rust
+/// use day16::{Packet, Transmission};
+/// let transmission = Transmission::from_bits("110100101111111000101110100101111111000101000").unwrap();
+/// let packets: Vec<Packet> = transmission.packets().collect::<Result<_, _>>().unwrap();
+/// assert_eq!(
+///     packets,
+///     vec![Packet::new("110100101111111000101"), Packet::new("110100101111111000101000")]
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Transmission {
+    bytes: Vec<u8>,
+    len: usize,
+}
+
+impl Transmission {
+    pub fn from_bytes(bytes: &[u8]) -> Self {
+        Self {
+            bytes: bytes.to_vec(),
+            len: bytes.len() * 8,
+        }
+    }
+
+    /// Hex like the puzzle input, in either case, ignoring whitespace like the newline at the
+    /// end of a file
+    /// ```rust
+    /// use day16::{NotHex, Transmission};
+    /// let transmission = Transmission::from_hex_str("d2fe\n28\n").unwrap();
+    /// assert_eq!(transmission, Transmission::from_hex_str("D2FE28").unwrap());
+    /// assert_eq!(
+    ///     Transmission::from_hex_str("D2 FE,28"),
+    ///     Err(NotHex { position: 5, found: ',' })
+    /// );
+    /// ```
+    pub fn from_hex_str(hexstr: &str) -> Result<Self, NotHex> {
+        let nibbles = hexstr
+            .chars()
+            .enumerate()
+            .filter(|(_, c)| !c.is_whitespace())
+            .map(|(position, found)| {
+                found
+                    .to_digit(16)
+                    .map(|digit| digit as u8)
+                    .ok_or(NotHex { position, found })
+            })
+            .collect::<Result<Vec<u8>, _>>()?;
+        // An odd number of digits leaves the last byte half empty, which `len` leaves out
+        let bytes = nibbles
+            .chunks(2)
+            .map(|pair| pair[0] << 4 | pair.get(1).copied().unwrap_or(0))
+            .collect();
+        Ok(Self {
+            bytes,
+            len: nibbles.len() * 4,
+        })
+    }
+
+    /// `0`s and `1`s, or `None` if there's anything else
+    pub fn from_bits(bits: &str) -> Option<Self> {
+        let mut writer = BitWriter::new();
+        for c in bits.chars() {
+            writer.write(c.to_digit(2)?.into(), 1);
+        }
+        Some(Self {
+            len: writer.len(),
+            bytes: writer.as_bytes().to_vec(),
+        })
+    }
+
+    /// How many bits there are, padding included
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// A reader over the bits, for reading packets some other way, like as [`PacketRef`]s
+    ///
+    /// [`PacketRef`]: crate::PacketRef
+    pub fn reader(&self) -> BitReader<'_> {
+        BitReader::with_len(&self.bytes, self.len)
+    }
+
+    /// Decodes the packets in order, stopping at the padding or the first malformed packet
+    pub fn packets(&self) -> Packets<'_> {
+        Packets {
+            reader: self.reader(),
+            done: false,
+        }
+    }
+}
+
+/// A character in a hex transmission that's neither a hex digit nor whitespace
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NotHex {
+    /// Counting characters, not bytes, from 0
+    pub position: usize,
+    pub found: char,
+}
+impl Display for NotHex {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(
+            f,
+            "{:?} at character {} isn't a hex digit",
+            self.found, self.position
+        )
+    }
+}
+#[cfg(feature = "std")]
+impl std::error::Error for NotHex {}
+
+/// The bits from `position` on don't start a whole packet
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Malformed {
+    pub position: usize,
+}
+impl Display for Malformed {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "no whole packet at bit {}", self.position)
+    }
+}
+#[cfg(feature = "std")]
+impl std::error::Error for Malformed {}
+
+/// The packets in a [`Transmission`]
+#[derive(Debug, Clone)]
+pub struct Packets<'a> {
+    reader: BitReader<'a>,
+    done: bool,
+}
+
+impl Iterator for Packets<'_> {
+    type Item = Result<Packet, Malformed>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done || self.reader.is_padding() {
+            self.done = true;
+            return None;
+        }
+        let position = self.reader.position();
+        let packet = Packet::from_reader(&mut self.reader).ok_or(Malformed { position });
+        self.done = packet.is_err();
+        Some(packet)
+    }
+}
+
+impl FusedIterator for Packets<'_> {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn decodes_each_packet() {
+        let transmission = Transmission::from_hex_str("D2FE2E97F140").unwrap();
+        let packets: Vec<_> = transmission.packets().collect();
+        assert_eq!(packets.len(), 2);
+        assert!(packets
+            .iter()
+            .all(|packet| packet.as_ref().unwrap().to_string() == "2021"));
+    }
+
+    #[test]
+    fn stops_at_padding() {
+        let transmission = Transmission::from_hex_str("D2FE280000").unwrap();
+        assert_eq!(transmission.packets().count(), 1);
+        assert_eq!(Transmission::from_hex_str("").unwrap().packets().count(), 0);
+        assert_eq!(Transmission::from_bytes(&[0; 8]).packets().next(), None);
+    }
+
+    #[test]
+    fn stops_after_a_malformed_packet() {
+        let transmission = Transmission::from_hex_str("D2FE2E97F").unwrap();
+        let mut packets = transmission.packets();
+        assert!(packets.next().unwrap().is_ok());
+        assert_eq!(packets.next(), Some(Err(Malformed { position: 21 })));
+        assert_eq!(packets.next(), None);
+        assert_eq!(
+            Malformed { position: 21 }.to_string(),
+            "no whole packet at bit 21"
+        );
+    }
+
+    #[test]
+    fn skips_whitespace_in_hex() {
+        let tidy = Transmission::from_hex_str("8A004A801A8002F478").unwrap();
+        for messy in [
+            "8a004a801a8002f478\n",
+            " 8A004A801A\r\n8002F478",
+            "8A00 4A80 1A80 02F4 78",
+        ] {
+            assert_eq!(Transmission::from_hex_str(messy), Ok(tidy.clone()));
+        }
+    }
+
+    #[test]
+    fn rejects_what_isnt_bits() {
+        assert_eq!(
+            Transmission::from_hex_str("D2FE2G"),
+            Err(NotHex {
+                position: 5,
+                found: 'G'
+            })
+        );
+        assert_eq!(
+            Transmission::from_hex_str("D2\tFé")
+                .unwrap_err()
+                .to_string(),
+            "'é' at character 4 isn't a hex digit"
+        );
+        assert_eq!(Transmission::from_bits("1102"), None);
+        assert_eq!(Transmission::from_hex_str("D2F").unwrap().len(), 12);
+    }
+}
@@ -0,0 +1,58 @@
+//! Compare each dx's window of dys with trying every dy from the zone's bottom up to as far
+//! above, with
+//!
+//! ```text
+//! cargo bench -p day17
+//! ```
+
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use day17::{
+    calculate_hit, dy_window, dys_hitting, vector_x_bounds_extreme, Input, Point, TargetZone,
+    Vector, INPUT,
+};
+
+/// Every dy from the bottom of the zone to as far above the launcher, as the search used to
+fn every_dy(target_zone: &TargetZone, dx: i64) -> usize {
+    let y_min = target_zone.bottom_left.y;
+    (y_min..=-y_min)
+        .filter(|&dy| calculate_hit(target_zone, Vector { x: dx, y: dy }).is_some())
+        .count()
+}
+
+fn search(c: &mut Criterion) {
+    let input: Input = INPUT.parse().expect("bundled input parses");
+    let large = TargetZone {
+        bottom_left: Point { x: 1000, y: -400 },
+        top_right: Point { x: 1400, y: -200 },
+    };
+    for (name, target_zone) in [("input", input.target_zone()), ("large", &large)] {
+        let (dx_min, _) = vector_x_bounds_extreme(target_zone);
+        let dxs = dx_min..=target_zone.top_right.x;
+        let tried: usize = dxs
+            .clone()
+            .map(|dx| dy_window(target_zone, dx).count())
+            .sum();
+        let every: i64 = (dxs.end() - dxs.start() + 1) * (-2 * target_zone.bottom_left.y + 1);
+        println!("{}: {} dys tried in windows, {} in all", name, tried, every);
+
+        c.bench_function(&format!("day17 {} windows", name), |b| {
+            b.iter(|| {
+                dxs.clone()
+                    .map(|dx| dys_hitting(black_box(target_zone), dx).len())
+                    .sum::<usize>()
+            })
+        });
+        c.bench_function(&format!("day17 {} every dy", name), |b| {
+            b.iter(|| {
+                dxs.clone()
+                    .map(|dx| every_dy(black_box(target_zone), dx))
+                    .sum::<usize>()
+            })
+        });
+    }
+}
+
+criterion_group!(benches, search);
+criterion_main!(benches);
@@ -1,4 +1,17 @@
-use std::ops::{Add, AddAssign};
+use aoc_common::{answer::Answer, error::AocError};
+use aoc_parse::{
+    finish, labeled, lines, literal,
+    nom::{
+        combinator::map,
+        sequence::{preceded, separated_pair},
+    },
+    number,
+};
+use std::{
+    ops::{Add, AddAssign},
+    str::FromStr,
+};
+use tracing::debug;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct TargetZone {
@@ -17,15 +30,18 @@ impl TargetZone {
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct Point {
-    pub x: i32,
-    pub y: i32,
+    pub x: i64,
+    pub y: i64,
 }
 
 impl Point {
     #[allow(clippy::result_unit_err)]
+    /// Moves by `vector` and slows it down, or leaves both alone if either would overflow
     pub fn try_apply_vector(&mut self, vector: &mut Vector) -> Result<(), ()> {
-        self.x = self.x.checked_add(vector.x).ok_or(())?;
-        self.y = self.y.checked_add(vector.y).ok_or(())?;
+        let x = self.x.checked_add(vector.x).ok_or(())?;
+        let y = self.y.checked_add(vector.y).ok_or(())?;
+        vector.y.checked_sub(1).ok_or(())?;
+        (self.x, self.y) = (x, y);
         vector.degrade();
         Ok(())
     }
@@ -53,18 +69,17 @@ impl Add<Vector> for Point {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct Vector {
-    pub x: i32,
-    pub y: i32,
+    pub x: i64,
+    pub y: i64,
 }
 
 impl Vector {
+    /// One step of drag and gravity: x slows towards 0 whichever way it's going, and y falls
     pub fn degrade(&mut self) {
         self.y -= 1;
-        if self.x > 0 {
-            self.x -= 1;
-        }
+        self.x -= self.x.signum();
     }
 }
 
@@ -85,6 +100,130 @@ pub fn has_past(point: &Point, vec: &Vector, target_zone: &TargetZone) -> bool {
     }
 }
 
+pub mod search;
+
+#[cfg(feature = "parallel")]
+pub use search::count_hits_parallel;
+pub use search::{
+    calculate_hit, dy_window, dys_hitting, find_all_hits, find_hits_in_zones, vector_find_hits,
+    vector_x_bounds, vector_x_bounds_extreme, zones_hit, Hit, Probe,
+};
+
+pub const INPUT: &str = include_str!("input.txt");
+
+/// One or more target areas, one to a line. The puzzle only ever has one.
+#[derive(Clone, Debug)]
+pub struct Input {
+    /// Never empty
+    target_zones: Vec<TargetZone>,
+}
+
+impl Input {
+    /// Where the probe has to end up: the first target area, and the puzzle's only one
+    pub fn target_zone(&self) -> &TargetZone {
+        &self.target_zones[0]
+    }
+
+    /// Every target area, in the order they were given
+    pub fn target_zones(&self) -> &[TargetZone] {
+        &self.target_zones
+    }
+}
+
+impl FromStr for Input {
+    type Err = AocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let range = |axis| labeled(axis, separated_pair(number, literal(".."), number));
+        let target_area = map(
+            preceded(
+                literal("target area: "),
+                separated_pair(range("x="), literal(", "), range("y=")),
+            ),
+            |((x_min, x_max), (y_min, y_max))| TargetZone {
+                bottom_left: Point { x: x_min, y: y_min },
+                top_right: Point { x: x_max, y: y_max },
+            },
+        );
+        let target_zones = finish(s, lines(target_area))?;
+        debug!(?target_zones, "parsed target areas");
+        Ok(Self { target_zones })
+    }
+}
+
+pub fn solve_part2(input: Input) -> u64 {
+    let target_zone = input.target_zone();
+
+    find_all_hits(target_zone)
+        .values()
+        .map(|dys| dys.len() as u64)
+        .sum()
+}
+
+/// Highest y position of any launch that hits `target_zone`, worked out without simulating
+/// anything. That only works the way puzzle inputs are: the zone's wholly below the launcher,
+/// and there's a dx whose probe stops moving across inside it. `None` if it isn't like that,
+/// or if the answer's too big for an i64.
+/// ```rust
+/// # use day17::*;
+/// let zone = |x_min, x_max, y_min, y_max| TargetZone {
+///     bottom_left: Point { x: x_min, y: y_min },
+///     top_right: Point { x: x_max, y: y_max },
+/// };
+/// assert_eq!(max_height(&zone(20, 30, -10, -5)), Some(45));
+/// // Too high up to fall into
+/// assert_eq!(max_height(&zone(20, 30, 5, 10)), None);
+/// // Nothing stops inside, 15 and 21 being either side of it
+/// assert_eq!(max_height(&zone(16, 20, -10, -5)), None);
+/// ```
+pub fn max_height(target_zone: &TargetZone) -> Option<i64> {
+    let (y_min, y_max) = (target_zone.bottom_left.y, target_zone.top_right.y);
+    if y_max >= 0 {
+        return None;
+    }
+    // A probe launched at dy comes back down through y = 0 going at -(dy + 1), so the fastest
+    // that can still land in the zone on the next step is the one that lands on its bottom row
+    let dy = -(y_min + 1);
+    // That takes 2 * dy + 2 steps, so it needs a dx that's stopped inside the zone by then
+    let steps = dy.saturating_mul(2).saturating_add(2);
+    let (dx_min, dx_max) = vector_x_bounds(target_zone);
+    if dx_min > dx_max || dx_min > steps {
+        return None;
+    }
+    // dy + 1 is -y_min, so this is even and only overflows for zones too deep to fit the answer
+    dy.checked_mul(dy + 1).map(|twice| twice / 2)
+}
+
+pub fn solve_part1(input: Input) -> u64 {
+    let target_zone = input.target_zone();
+
+    if let Some(height) = max_height(target_zone) {
+        return height as u64;
+    }
+    debug!("no closed form for this zone, searching instead");
+    let best_dy = find_all_hits(target_zone)
+        .into_values()
+        .filter_map(|dys| dys.last().copied())
+        .max()
+        .expect("There must be some vector that hits");
+
+    (1..=best_dy).fold(0, |acc, next| acc + next as u64)
+}
+
+/// Highest y position reachable by a probe that still hits the target
+pub fn part1(input: &str) -> Result<Answer, String> {
+    let input = input.parse::<Input>().map_err(|e| e.to_string())?;
+    Ok(solve_part1(input).into())
+}
+
+/// Number of distinct initial velocities that hit the target
+pub fn part2(input: &str) -> Result<Answer, String> {
+    let input = input.parse::<Input>().map_err(|e| e.to_string())?;
+    Ok(solve_part2(input).into())
+}
+
+aoc_common::register_day!(17);
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -183,6 +322,30 @@ mod tests {
         pos.apply_vector(&mut vector);
         assert_eq!(pos, Point { x: 3, y: -3 });
     }
+    #[test]
+    fn try_apply_vector_stops_short_of_overflowing() {
+        let mut pos = Point {
+            x: i64::MAX - 1,
+            y: 0,
+        };
+        let mut vector = Vector { x: 2, y: 0 };
+        assert!(pos.try_apply_vector(&mut vector).is_err());
+        assert_eq!(
+            pos,
+            Point {
+                x: i64::MAX - 1,
+                y: 0
+            }
+        );
+        assert_eq!(vector, Vector { x: 2, y: 0 });
+
+        let mut vector = Vector { x: 1, y: i64::MIN };
+        assert!(pos.try_apply_vector(&mut vector).is_err());
+        assert!(Point { x: 0, y: 0 }
+            .try_apply_vector(&mut Vector { x: 0, y: i64::MIN })
+            .is_err());
+    }
+
     #[test]
     fn degrade_vector() {
         let mut vector = Vector { x: 3, y: 4 };
@@ -202,4 +365,132 @@ mod tests {
         assert_eq!(vector.x, 0);
         assert_eq!(vector.y, -1);
     }
+
+    #[test]
+    fn degrade_leftward_vector() {
+        let mut vector = Vector { x: -2, y: 0 };
+        vector.degrade();
+        assert_eq!(vector, Vector { x: -1, y: -1 });
+        vector.degrade();
+        assert_eq!(vector, Vector { x: 0, y: -2 });
+        vector.degrade();
+        assert_eq!(vector, Vector { x: 0, y: -3 });
+    }
+}
+
+#[cfg(test)]
+mod solve_tests {
+    use super::*;
+
+    const INPUT: &str = include_str!("test_input.txt");
+    #[test]
+    fn solve_part2() {
+        let input = INPUT.parse().expect("Input must parse");
+        let part2 = super::solve_part2(input);
+
+        assert_eq!(part2, 112)
+    }
+    #[test]
+    fn solve_part1() {
+        let input = INPUT.parse::<Input>().expect("Input must parse");
+        let part1 = super::solve_part1(input);
+
+        assert_eq!(part1, 45);
+    }
+
+    #[test]
+    fn max_height_agrees_with_searching() {
+        for input in [INPUT, super::INPUT] {
+            let input = input.parse::<Input>().unwrap();
+            let target_zone = input.target_zone();
+            let best_dy = find_all_hits(target_zone)
+                .into_values()
+                .filter_map(|dys| dys.last().copied())
+                .max()
+                .unwrap();
+            assert_eq!(max_height(target_zone), Some(best_dy * (best_dy + 1) / 2));
+        }
+
+        // Nothing stops inside this one, so part 1 has to search, and finds a much lower peak
+        let input: Input = "target area: x=16..20, y=-10..-5".parse().unwrap();
+        assert_eq!(max_height(input.target_zone()), None);
+        assert_eq!(super::solve_part1(input), 1);
+    }
+
+    #[test]
+    fn max_height_of_huge_zones() {
+        let input: Input = "target area: x=1000000000000..2000000000000, y=-1000000000..-5"
+            .parse()
+            .unwrap();
+        assert_eq!(
+            max_height(input.target_zone()),
+            Some(999_999_999 * 1_000_000_000 / 2)
+        );
+        let too_deep = TargetZone {
+            bottom_left: Point {
+                x: 20,
+                y: -(1 << 40),
+            },
+            top_right: Point { x: 30, y: -5 },
+        };
+        assert_eq!(max_height(&too_deep), None);
+    }
+
+    #[test]
+    fn parses_target_area() {
+        let input = INPUT.parse::<Input>().expect("Input must parse");
+        assert_eq!(
+            *input.target_zone(),
+            TargetZone {
+                bottom_left: Point { x: 20, y: -10 },
+                top_right: Point { x: 30, y: -5 },
+            }
+        );
+        assert_eq!(
+            "target area: x=20..30, y=-10".parse::<Input>().unwrap_err(),
+            AocError::UnexpectedEnd(String::from("`..`"))
+        );
+        assert_eq!(
+            "target area: x=20..30 y=-10..-5"
+                .parse::<Input>()
+                .unwrap_err(),
+            AocError::parse(1, 23, "expected `, `, found `y`")
+        );
+    }
+
+    #[test]
+    fn parses_several_target_areas() {
+        let input = "target area: x=20..30, y=-10..-5\ntarget area: x=25..40, y=-20..-8\n"
+            .parse::<Input>()
+            .expect("Input must parse");
+        assert_eq!(input.target_zones().len(), 2);
+        assert_eq!(input.target_zone(), &input.target_zones()[0]);
+        assert_eq!(
+            input.target_zones()[1],
+            TargetZone {
+                bottom_left: Point { x: 25, y: -20 },
+                top_right: Point { x: 40, y: -8 },
+            }
+        );
+        assert!(matches!(
+            "target area: x=20..30, y=-10..-5\ntarget area: x=1"
+                .parse::<Input>()
+                .unwrap_err(),
+            AocError::UnexpectedEnd(_)
+        ));
+    }
+
+    #[test]
+    fn reports_progress() {
+        use aoc_common::progress::{with_progress, Event};
+        use std::sync::mpsc;
+
+        let input = INPUT.parse::<Input>().expect("Input must parse");
+        let (sender, events) = mpsc::channel();
+        with_progress(sender, || super::solve_part2(input));
+        let events: Vec<Event> = events.try_iter().collect();
+        // one step for each dx from 6 to 30
+        assert_eq!(events[0], Event::Start(Some(25)));
+        assert_eq!(events[1..], [Event::Advance(1); 25]);
+    }
 }
@@ -0,0 +1,567 @@
+//! Finding the launches that hit a target zone, by simulating each one that might
+
+use std::{collections::HashMap, iter, ops::Range};
+
+use aoc_common::progress;
+use tracing::{debug, trace};
+
+use crate::{has_past, Point, TargetZone, Vector};
+
+/// The largest n whose triangular number n(n + 1) / 2 is no more than `x`, which must be at
+/// least 0
+fn triangular_root(x: i64) -> i64 {
+    // n(n + 1) / 2 <= x exactly when (2n + 1)^2 <= 8x + 1, and that's too big for an i64
+    let root = (8 * x as u128 + 1).isqrt();
+    ((root - 1) / 2) as i64
+}
+
+/// The slowest dx that stops at or past the zone's left edge, and the fastest that stops at
+/// or before its right edge. The probe stops moving across inside the zone for every dx
+/// between them.
+pub fn vector_x_bounds(target_zone: &TargetZone) -> (i64, i64) {
+    let (x_min, x_max) = (target_zone.bottom_left.x, target_zone.top_right.x);
+    let dx_min = if x_min > 0 {
+        triangular_root(x_min - 1) + 1
+    } else {
+        0
+    };
+    (dx_min, triangular_root(x_max.max(0)))
+}
+
+pub fn vector_x_bounds_extreme(target_zone: &TargetZone) -> (i64, i64) {
+    let (dx_min, _) = vector_x_bounds(target_zone);
+    let dx_max = target_zone.top_right.x;
+    (dx_min, dx_max)
+}
+
+/// How a launch reached the target zone
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Hit {
+    /// How many steps it took to first be inside the zone
+    pub step: u64,
+    /// Where it was then
+    pub entry_point: Point,
+    /// The highest y it reached on the way
+    pub apex: i64,
+}
+
+/// Where and when a probe launched at `vector` first lands in the target zone, or `None` if
+/// it never does
+/// ```rust
+/// # use day17::*;
+/// let target_zone = TargetZone {
+///     bottom_left: Point { x: 20, y: -10 },
+///     top_right: Point { x: 30, y: -5 },
+/// };
+/// let hit = calculate_hit(&target_zone, Vector { x: 7, y: 2 }).unwrap();
+/// assert_eq!(hit.step, 7);
+/// assert_eq!(hit.entry_point, Point { x: 28, y: -7 });
+/// assert_eq!(hit.apex, 3);
+/// assert_eq!(calculate_hit(&target_zone, Vector { x: 17, y: -4 }), None);
+/// ```
+pub fn calculate_hit(target_zone: &TargetZone, vector: Vector) -> Option<Hit> {
+    Probe::new(Point { x: 0, y: 0 }, vector).hit(target_zone)
+}
+
+/// A probe in flight, launched from anywhere. Stepping it along as an iterator gives each
+/// position it moves to and the velocity it carries on with from there, stopping if the next
+/// step would overflow.
+/// ```rust
+/// # use day17::*;
+/// let target_zone = TargetZone {
+///     bottom_left: Point { x: 20, y: -10 },
+///     top_right: Point { x: 30, y: -5 },
+/// };
+/// // Where can a probe be dropped from, to fall into the zone?
+/// let hits_from = |x| {
+///     let probe = Probe::new(Point { x, y: 0 }, Vector { x: 0, y: 0 });
+///     probe.hit(&target_zone).is_some()
+/// };
+/// assert!((20..=30).all(hits_from));
+/// assert!(!hits_from(19));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Probe {
+    position: Point,
+    velocity: Vector,
+}
+
+impl Probe {
+    pub fn new(origin: Point, velocity: Vector) -> Self {
+        Self {
+            position: origin,
+            velocity,
+        }
+    }
+
+    pub fn position(&self) -> Point {
+        self.position
+    }
+
+    pub fn velocity(&self) -> Vector {
+        self.velocity
+    }
+
+    /// Where and when the probe first lands in the target zone from here, or `None` if it
+    /// never does. The steps are counted from here, and the apex is the highest y it reaches
+    /// from here on.
+    pub fn hit(self, target_zone: &TargetZone) -> Option<Hit> {
+        let mut apex = self.position.y;
+        let (step, (entry_point, _)) = iter::once((self.position, self.velocity))
+            .chain(self)
+            .take_while(|(position, velocity)| !has_past(position, velocity, target_zone))
+            .inspect(|(position, _)| apex = apex.max(position.y))
+            .enumerate()
+            .find(|(_, (position, _))| target_zone.contains(position))?;
+        Some(Hit {
+            step: step as u64,
+            entry_point,
+            apex,
+        })
+    }
+}
+
+impl Iterator for Probe {
+    type Item = (Point, Vector);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.position.try_apply_vector(&mut self.velocity).ok()?;
+        Some((self.position, self.velocity))
+    }
+}
+
+/// Given a value dx, find all values dy to complete (dx, dy) such that
+/// the projectile will cross into target_zone
+pub fn vector_find_hits(target_zone: &TargetZone, dx: i64) -> Vec<Vector> {
+    dys_hitting(target_zone, dx)
+        .into_iter()
+        .map(|dy| Vector { x: dx, y: dy })
+        .collect()
+}
+
+/// Every dy that hits the target zone alongside `dx`, lowest first
+pub fn dys_hitting(target_zone: &TargetZone, dx: i64) -> Vec<i64> {
+    let hits: Vec<i64> = dy_window(target_zone, dx)
+        .filter(|&dy| calculate_hit(target_zone, Vector { x: dx, y: dy }).is_some())
+        .collect();
+    trace!(dx, hits = hits.len(), "tried every dy");
+    hits
+}
+
+/// The dys worth trying alongside `dx`, empty if `dx` never brings the probe over the zone.
+///
+/// If the target zone is below (0, 0) as ours is, we are guaranteed that any dy > 0 will arc
+/// parabolically up and return down to (_, 0) with a velocity of (_, -dy - 1), so any dy of
+/// abs(target_zone.bottom_left.y) or more falls beyond the bottom of the zone on the next
+/// tick. And once the probe is below the launcher, it only ever falls. So it can't already
+/// be below the zone on the first step it's over it, which bounds dy from below, and it has
+/// to be down into the zone by the last step it's over it, which bounds dy from above.
+///
+/// That only holds for a zone below and to the right of the launcher. For any other, this is
+/// every dy from the bottom of the zone to as far above the launcher.
+pub fn dy_window(target_zone: &TargetZone, dx: i64) -> Range<i64> {
+    let (x_min, x_max) = (target_zone.bottom_left.x, target_zone.top_right.x);
+    let (y_min, y_max) = (target_zone.bottom_left.y, target_zone.top_right.y);
+    if y_max >= 0 || x_min <= 0 || dx <= 0 {
+        return y_min..y_min.saturating_neg().saturating_add(1);
+    }
+    let (x_min, x_max, y_min, y_max) = (
+        i128::from(x_min),
+        i128::from(x_max),
+        i128::from(y_min),
+        i128::from(y_max),
+    );
+    let dx = i128::from(dx);
+    // How far across the probe is after `n` steps
+    let across = |n: i128| {
+        let n = n.min(dx);
+        n * dx - n * (n - 1) / 2
+    };
+    // The first step after which `past(across(step))`, if there is one before it stops
+    let first_step = |past: &dyn Fn(i128) -> bool| {
+        if !past(across(dx)) {
+            return None;
+        }
+        let (mut low, mut high) = (0, dx);
+        while low < high {
+            let middle = low + (high - low) / 2;
+            if past(across(middle)) {
+                high = middle;
+            } else {
+                low = middle + 1;
+            }
+        }
+        Some(low)
+    };
+    let Some(first) = first_step(&|x| x >= x_min) else {
+        // Stops short of the zone
+        return 0..0;
+    };
+    // None if it stops over the zone, and stays over it
+    let last = first_step(&|x| x > x_max).map(|past| past - 1);
+    // After `n` steps the probe is n * dy - n * (n - 1) / 2 up
+    let fallen = |n: i128| n * (n - 1) / 2;
+    let low = -(-(y_min + fallen(first))).div_euclid(first);
+    let high = match last {
+        Some(last) if last < first => return 0..0,
+        Some(last) => (y_max + fallen(last)).div_euclid(last).min(-y_min - 1),
+        None => -y_min - 1,
+    };
+    // Both are between y_min and -y_min, so they fit back in an i64
+    low.max(y_min) as i64..high as i64 + 1
+}
+
+/// `per_dx(dx)` for each dx that could reach the target zone, in order. With the `parallel`
+/// feature, a core's worth of them are worked on at once.
+fn each_dx<T: Send>(target_zone: &TargetZone, per_dx: impl Fn(i64) -> T + Sync) -> Vec<T> {
+    let (dx_min, dx_max) = vector_x_bounds_extreme(target_zone);
+    let dxs: Vec<i64> = (dx_min..=dx_max).collect();
+    let dys = target_zone
+        .bottom_left
+        .y
+        .saturating_mul(-2)
+        .saturating_add(1)
+        .max(0);
+    debug!(
+        dx_min,
+        dx_max,
+        attempts = (dxs.len() as i64).saturating_mul(dys),
+        "searching initial velocities"
+    );
+    progress::start(Some(dxs.len() as u64));
+    #[cfg(feature = "parallel")]
+    {
+        use rayon::prelude::*;
+        // Progress only goes to this thread, so report it here after each batch
+        let mut results = Vec::with_capacity(dxs.len());
+        for batch in dxs.chunks(rayon::current_num_threads()) {
+            results.par_extend(batch.par_iter().map(|&dx| per_dx(dx)));
+            for _ in batch {
+                progress::advance(1);
+            }
+        }
+        results
+    }
+    #[cfg(not(feature = "parallel"))]
+    {
+        dxs.into_iter()
+            .map(|dx| {
+                let result = per_dx(dx);
+                progress::advance(1);
+                result
+            })
+            .collect()
+    }
+}
+
+/// Every initial velocity that hits the target zone, as the dys that hit alongside each dx,
+/// trying each dx that could reach it. A dx with no dys that hit is left out.
+/// ```rust
+/// # use day17::*;
+/// let target_zone = TargetZone {
+///     bottom_left: Point { x: 20, y: -10 },
+///     top_right: Point { x: 30, y: -5 },
+/// };
+/// let hits = find_all_hits(&target_zone);
+/// assert_eq!(hits[&6], (0..=9).collect::<Vec<_>>());
+/// assert!(!hits.contains_key(&5));
+/// assert_eq!(hits.values().map(Vec::len).sum::<usize>(), 112);
+/// ```
+pub fn find_all_hits(target_zone: &TargetZone) -> HashMap<i64, Vec<i64>> {
+    let hits: HashMap<i64, Vec<i64>> =
+        each_dx(target_zone, |dx| (dx, dys_hitting(target_zone, dx)))
+            .into_iter()
+            .filter(|(_, dys)| !dys.is_empty())
+            .collect();
+    debug!(
+        hits = hits.values().map(Vec::len).sum::<usize>(),
+        "found every hit"
+    );
+    hits
+}
+
+/// Which of `target_zones` a probe launched at `velocity` hits, by index. Zones can overlap,
+/// so one probe can hit several.
+/// ```rust
+/// # use day17::*;
+/// let zones = [
+///     TargetZone { bottom_left: Point { x: 20, y: -10 }, top_right: Point { x: 30, y: -5 } },
+///     TargetZone { bottom_left: Point { x: 25, y: -20 }, top_right: Point { x: 40, y: -8 } },
+/// ];
+/// assert_eq!(zones_hit(&zones, Vector { x: 7, y: -1 }), vec![0, 1]);
+/// assert_eq!(zones_hit(&zones, Vector { x: 6, y: 9 }), vec![0]);
+/// ```
+pub fn zones_hit(target_zones: &[TargetZone], velocity: Vector) -> Vec<usize> {
+    target_zones
+        .iter()
+        .enumerate()
+        .filter(|(_, target_zone)| calculate_hit(target_zone, velocity).is_some())
+        .map(|(index, _)| index)
+        .collect()
+}
+
+/// Every initial velocity that hits any of `target_zones`, with the indexes of the zones it
+/// hits, in order. Searches each zone in turn with [`find_all_hits`].
+pub fn find_hits_in_zones(target_zones: &[TargetZone]) -> HashMap<Vector, Vec<usize>> {
+    let mut hits: HashMap<Vector, Vec<usize>> = HashMap::new();
+    for (index, target_zone) in target_zones.iter().enumerate() {
+        for (dx, dys) in find_all_hits(target_zone) {
+            for dy in dys {
+                hits.entry(Vector { x: dx, y: dy }).or_default().push(index);
+            }
+        }
+    }
+    debug!(velocities = hits.len(), "found every hit in every zone");
+    hits
+}
+
+/// How many initial velocities hit the target zone, like counting [`find_all_hits`] but
+/// without keeping them, and searching a core's worth of dx at once
+#[cfg(feature = "parallel")]
+pub fn count_hits_parallel(target_zone: &TargetZone) -> usize {
+    let hits = each_dx(target_zone, |dx| dys_hitting(target_zone, dx).len())
+        .into_iter()
+        .sum();
+    debug!(hits, "counted every hit");
+    hits
+}
+
+#[cfg(test)]
+mod invariants {
+    use super::*;
+    fn triangle_sum(n: i64) -> i64 {
+        // trivially (1..=n).sum(), but mathematically this generalizes to:
+        n * (n + 1) / 2
+    }
+    #[test]
+    fn calculating_dx_max_and_min() {
+        let target_zone = TargetZone {
+            bottom_left: Point { x: 20, y: -10 },
+            top_right: Point { x: 30, y: -5 },
+        };
+        let (dx_min, dx_max) = vector_x_bounds(&target_zone);
+        assert_eq!((dx_min, dx_max), (6, 7));
+
+        // Only 23 comes to rest over this one, at 276
+        let bounds = vector_x_bounds(&TargetZone {
+            bottom_left: Point { x: 265, y: -103 },
+            top_right: Point { x: 287, y: -58 },
+        });
+        assert_eq!(bounds, (23, 23));
+
+        for dx in dx_min..=dx_max {
+            let max_x = triangle_sum(dx);
+            assert!(
+                target_zone.bottom_left.x <= max_x && max_x <= target_zone.top_right.x,
+                "dx_min={}, dx_max={}, max_x={}, dx={}",
+                dx_min,
+                dx_max,
+                max_x,
+                dx
+            );
+        }
+    }
+
+    #[test]
+    fn x_bounds_are_exact_for_huge_zones() {
+        for (x_min, x_max) in [
+            (1, 1),
+            (16, 20),
+            (1 << 40, 1 << 41),
+            (i64::MAX / 2, i64::MAX),
+        ] {
+            let (dx_min, dx_max) = vector_x_bounds(&TargetZone {
+                bottom_left: Point { x: x_min, y: -10 },
+                top_right: Point { x: x_max, y: -5 },
+            });
+            let triangle = |n: i64| n as i128 * (n as i128 + 1) / 2;
+            assert!(triangle(dx_min - 1) < x_min as i128 && triangle(dx_min) >= x_min as i128);
+            assert!(triangle(dx_max) <= x_max as i128 && triangle(dx_max + 1) > x_max as i128);
+        }
+    }
+
+    #[test]
+    fn launching_elsewhere_is_moving_the_zone() {
+        let target_zone = TargetZone {
+            bottom_left: Point { x: 20, y: -10 },
+            top_right: Point { x: 30, y: -5 },
+        };
+        let origin = Point { x: -7, y: 40 };
+        let moved = TargetZone {
+            bottom_left: target_zone.bottom_left + Vector { x: -7, y: 40 },
+            top_right: target_zone.top_right + Vector { x: -7, y: 40 },
+        };
+        for dx in 0..=30 {
+            for dy in -10..=10 {
+                let velocity = Vector { x: dx, y: dy };
+                let from_origin = calculate_hit(&target_zone, velocity);
+                let from_elsewhere = Probe::new(origin, velocity).hit(&moved);
+                assert_eq!(
+                    from_elsewhere.map(|hit| (hit.step, hit.entry_point, hit.apex)),
+                    from_origin.map(|hit| (
+                        hit.step,
+                        hit.entry_point + Vector { x: -7, y: 40 },
+                        hit.apex + 40
+                    )),
+                    "{:?}",
+                    velocity
+                );
+            }
+        }
+        let path: Vec<(Point, Vector)> =
+            Probe::new(origin, Vector { x: 2, y: 1 }).take(3).collect();
+        assert_eq!(
+            path,
+            [
+                (Point { x: -5, y: 41 }, Vector { x: 1, y: 0 }),
+                (Point { x: -4, y: 41 }, Vector { x: 0, y: -1 }),
+                (Point { x: -4, y: 40 }, Vector { x: 0, y: -2 })
+            ]
+        );
+    }
+
+    #[test]
+    fn launching_leftwards_mirrors_launching_rightwards() {
+        let target_zone = TargetZone {
+            bottom_left: Point { x: 20, y: -10 },
+            top_right: Point { x: 30, y: -5 },
+        };
+        let origin = Point { x: 40, y: 0 };
+        // Slows to a stop at x=34, short of the zone
+        assert_eq!(
+            Probe::new(origin, Vector { x: -3, y: 0 }).hit(&target_zone),
+            None
+        );
+        // The zone as seen from the origin with x flipped
+        let mirrored = TargetZone {
+            bottom_left: Point { x: 10, y: -10 },
+            top_right: Point { x: 20, y: -5 },
+        };
+        for dx in 0..=20 {
+            for dy in -10..=10 {
+                let leftwards = Probe::new(origin, Vector { x: -dx, y: dy }).hit(&target_zone);
+                let rightwards = calculate_hit(&mirrored, Vector { x: dx, y: dy });
+                assert_eq!(
+                    leftwards.map(|hit| (hit.step, hit.entry_point, hit.apex)),
+                    rightwards.map(|hit| (
+                        hit.step,
+                        Point {
+                            x: 40 - hit.entry_point.x,
+                            y: hit.entry_point.y
+                        },
+                        hit.apex
+                    )),
+                    "dx={} dy={}",
+                    -dx,
+                    dy
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn hits_rank_by_apex_and_step() {
+        let target_zone = TargetZone {
+            bottom_left: Point { x: 20, y: -10 },
+            top_right: Point { x: 30, y: -5 },
+        };
+        let hits: Vec<Hit> = find_all_hits(&target_zone)
+            .into_iter()
+            .flat_map(|(dx, dys)| dys.into_iter().map(move |dy| Vector { x: dx, y: dy }))
+            .map(|vector| calculate_hit(&target_zone, vector).unwrap())
+            .collect();
+        let highest = hits.iter().max_by_key(|hit| hit.apex).unwrap();
+        assert_eq!(Some(highest.apex), crate::max_height(&target_zone));
+        assert!(target_zone.contains(&highest.entry_point));
+        // Launching straight into the zone gets there on the first step
+        assert_eq!(hits.iter().map(|hit| hit.step).min(), Some(1));
+    }
+
+    #[test]
+    fn dy_windows_miss_no_hits() {
+        let mut zones = vec![TargetZone {
+            bottom_left: Point { x: 265, y: -103 },
+            top_right: Point { x: 287, y: -58 },
+        }];
+        for (x_min, y_max) in [(1, -1), (5, -3), (20, -5), (40, -20)] {
+            for (width, height) in [(0, 0), (3, 1), (10, 7), (25, 30)] {
+                zones.push(TargetZone {
+                    bottom_left: Point {
+                        x: x_min,
+                        y: y_max - height,
+                    },
+                    top_right: Point {
+                        x: x_min + width,
+                        y: y_max,
+                    },
+                });
+            }
+        }
+        for target_zone in &zones {
+            let y_min = target_zone.bottom_left.y;
+            let (mut tried, mut every) = (0, 0);
+            for dx in 0..=target_zone.top_right.x {
+                let all: Vec<i64> = (y_min..=-y_min)
+                    .filter(|&dy| calculate_hit(target_zone, Vector { x: dx, y: dy }).is_some())
+                    .collect();
+                assert_eq!(
+                    dys_hitting(target_zone, dx),
+                    all,
+                    "{:?} dx={}",
+                    target_zone,
+                    dx
+                );
+                tried += dy_window(target_zone, dx).count();
+                every += (y_min..=-y_min).count();
+            }
+            assert!(tried < every, "{:?}", target_zone);
+        }
+    }
+
+    #[test]
+    fn hits_in_zones_agree_with_each_zone() {
+        let target_zones = [
+            TargetZone {
+                bottom_left: Point { x: 20, y: -10 },
+                top_right: Point { x: 30, y: -5 },
+            },
+            TargetZone {
+                bottom_left: Point { x: 25, y: -20 },
+                top_right: Point { x: 40, y: -8 },
+            },
+            TargetZone {
+                bottom_left: Point { x: 100, y: -3 },
+                top_right: Point { x: 110, y: -1 },
+            },
+        ];
+        let hits = find_hits_in_zones(&target_zones);
+        for (index, target_zone) in target_zones.iter().enumerate() {
+            let expected = find_all_hits(target_zone)
+                .values()
+                .map(Vec::len)
+                .sum::<usize>();
+            let found = hits.values().filter(|zones| zones.contains(&index)).count();
+            assert_eq!(found, expected, "{:?}", target_zone);
+        }
+        for (&velocity, zones) in &hits {
+            assert_eq!(zones, &zones_hit(&target_zones, velocity), "{:?}", velocity);
+        }
+        assert!(hits.values().any(|zones| zones == &[0, 1]));
+        assert!(find_hits_in_zones(&[]).is_empty());
+    }
+
+    #[test]
+    fn calculate_dy_from_given_dx() {
+        let target_zone = TargetZone {
+            bottom_left: Point { x: 20, y: -10 },
+            top_right: Point { x: 30, y: -5 },
+        };
+
+        let possible_dys = vector_find_hits(&target_zone, 6);
+        let expected: Vec<Vector> = (0..=9).map(|dy| Vector { x: 6, y: dy }).collect();
+        assert_eq!(possible_dys, expected);
+        let possible_dys = vector_find_hits(&target_zone, 7);
+        let expected: Vec<Vector> = (-1..=9).map(|dy| Vector { x: 7, y: dy }).collect();
+        assert_eq!(possible_dys, expected);
+    }
+}
@@ -0,0 +1,533 @@
+//! Snailfish numbers kept in one `Vec` of nodes that point at each other by index, so adding
+//! two of them copies a couple of `Vec`s rather than cloning a tree of `Box`es.
+//!
+//! The regular numbers are also linked to their neighbours left and right, so exploding a
+//! pair reaches the numbers on either side without searching for them, and reducing a sum
+//! is one sweep along the numbers rather than a search from the top after every step.
+//!
+//! Pairs can remember their magnitudes too, once asked to, which sums carry over from the
+//! numbers added, so working out a magnitude again only revisits the pairs that have changed
+//! since. Part 2 doesn't ask: reducing a sum changes most of its pairs, so keeping the cache
+//! up to date costs more than it saves.
+
+use std::{fmt::Display, ops::Add};
+
+use crate::SnailFish;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Node {
+    Num {
+        value: u32,
+        /// How many pairs it's inside
+        depth: usize,
+        /// The regular numbers either side of it
+        prev: Option<usize>,
+        next: Option<usize>,
+    },
+    Pair {
+        /// The indices of the left and right halves
+        left: usize,
+        right: usize,
+        /// Cached since the pair last changed, if it has been. A pair that isn't cached is
+        /// never inside one that is.
+        magnitude: Option<u64>,
+    },
+}
+
+/// A snailfish number as an arena of nodes. Exploding leaves the exploded pair's numbers
+/// behind in the arena, unreachable, until the number is next converted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SnailArena {
+    nodes: Vec<Node>,
+    /// Every node's pair, `None` for the root
+    parents: Vec<Option<usize>>,
+    /// Whether any pair has its magnitude cached, so there's nothing to forget if not
+    cached: bool,
+    root: usize,
+    /// The leftmost and rightmost regular numbers
+    first: usize,
+    last: usize,
+}
+
+impl From<&SnailFish> for SnailArena {
+    fn from(snailfish: &SnailFish) -> Self {
+        let mut arena = SnailArena {
+            nodes: Vec::new(),
+            parents: Vec::new(),
+            cached: false,
+            root: 0,
+            first: 0,
+            last: 0,
+        };
+        let mut last = None;
+        arena.root = arena.push(snailfish, 0, &mut last);
+        arena.first = arena.leftmost(arena.root);
+        arena.last = last.expect("every snailfish has a regular number");
+        arena
+    }
+}
+
+impl From<&SnailArena> for SnailFish {
+    fn from(arena: &SnailArena) -> Self {
+        arena.to_snailfish(arena.root)
+    }
+}
+
+/// Written the way the puzzle input writes them, like [`SnailFish`]
+impl Display for SnailArena {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        fn write(nodes: &[Node], node: usize, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            match nodes[node] {
+                Node::Num { value, .. } => write!(f, "{}", value),
+                Node::Pair { left, right, .. } => {
+                    write!(f, "[")?;
+                    write(nodes, left, f)?;
+                    write!(f, ",")?;
+                    write(nodes, right, f)?;
+                    write!(f, "]")
+                }
+            }
+        }
+        write(&self.nodes, self.root, f)
+    }
+}
+
+/// Adds and reduces, just like adding [`SnailFish`]
+impl Add for &SnailArena {
+    type Output = SnailArena;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        let offset = self.nodes.len();
+        let shift = |index: usize| index + offset;
+        let mut nodes = Vec::with_capacity(offset + rhs.nodes.len() + 1);
+        let mut parents = Vec::with_capacity(nodes.capacity());
+        for (part, moved) in [(self, 0), (rhs, offset)] {
+            nodes.extend(part.nodes.iter().map(|node| match *node {
+                Node::Num {
+                    value,
+                    depth,
+                    prev,
+                    next,
+                } => Node::Num {
+                    value,
+                    depth: depth + 1,
+                    prev: prev.map(|prev| prev + moved),
+                    next: next.map(|next| next + moved),
+                },
+                Node::Pair {
+                    left,
+                    right,
+                    magnitude,
+                } => Node::Pair {
+                    left: left + moved,
+                    right: right + moved,
+                    magnitude,
+                },
+            }));
+            parents.extend(
+                part.parents
+                    .iter()
+                    .map(|parent| parent.map(|parent| parent + moved)),
+            );
+        }
+        let root = nodes.len();
+        nodes.push(Node::Pair {
+            left: self.root,
+            right: shift(rhs.root),
+            magnitude: None,
+        });
+        parents.push(None);
+        parents[self.root] = Some(root);
+        parents[shift(rhs.root)] = Some(root);
+        let mut sum = SnailArena {
+            nodes,
+            parents,
+            cached: self.cached || rhs.cached,
+            root,
+            first: self.first,
+            last: shift(rhs.last),
+        };
+        sum.link(Some(self.last), Some(shift(rhs.first)));
+        sum.reduce();
+        sum
+    }
+}
+
+impl SnailArena {
+    /// Adds `snailfish` and everything in it, linking its regular numbers after `last`
+    fn push(&mut self, snailfish: &SnailFish, depth: usize, last: &mut Option<usize>) -> usize {
+        let node = match snailfish {
+            SnailFish::Num(value) => Node::Num {
+                value: *value,
+                depth,
+                prev: None,
+                next: None,
+            },
+            SnailFish::Pair(bx) => {
+                let left = self.push(&bx.0, depth + 1, last);
+                let right = self.push(&bx.1, depth + 1, last);
+                Node::Pair {
+                    left,
+                    right,
+                    magnitude: None,
+                }
+            }
+        };
+        let index = self.nodes.len();
+        self.nodes.push(node);
+        self.parents.push(None);
+        match node {
+            Node::Num { .. } => {
+                self.link(*last, Some(index));
+                *last = Some(index);
+            }
+            Node::Pair { left, right, .. } => {
+                self.parents[left] = Some(index);
+                self.parents[right] = Some(index);
+            }
+        }
+        index
+    }
+
+    fn to_snailfish(&self, node: usize) -> SnailFish {
+        match self.nodes[node] {
+            Node::Num { value, .. } => SnailFish::Num(value),
+            Node::Pair { left, right, .. } => {
+                SnailFish::pair(self.to_snailfish(left), self.to_snailfish(right))
+            }
+        }
+    }
+
+    fn leftmost(&self, mut node: usize) -> usize {
+        while let Node::Pair { left, .. } = self.nodes[node] {
+            node = left;
+        }
+        node
+    }
+
+    /// Makes `left` and `right` neighbours, either of which may be off the end
+    fn link(&mut self, left: Option<usize>, right: Option<usize>) {
+        if let Some(Node::Num { next, .. }) = left.map(|left| &mut self.nodes[left]) {
+            *next = right;
+        }
+        if let Some(Node::Num { prev, .. }) = right.map(|right| &mut self.nodes[right]) {
+            *prev = left;
+        }
+    }
+
+    /// The regular number at `node`, which must be one
+    fn num(&self, node: usize) -> (u32, usize, Option<usize>, Option<usize>) {
+        match self.nodes[node] {
+            Node::Num {
+                value,
+                depth,
+                prev,
+                next,
+            } => (value, depth, prev, next),
+            Node::Pair { .. } => unreachable!("node {} is a pair, not a regular number", node),
+        }
+    }
+
+    /// Whether `node` is the left half of a pair of regular numbers that's deep enough to
+    /// explode
+    fn explodes_at(&self, node: usize) -> bool {
+        let (_, depth, _, next) = self.num(node);
+        depth > 4 && next.is_some_and(|next| self.parents[next] == self.parents[node])
+    }
+
+    /// Forgets the magnitudes of every pair `node` is inside, after it's changed
+    fn changed(&mut self, node: usize) {
+        if !self.cached {
+            return;
+        }
+        let mut pair = self.parents[node];
+        while let Some(index) = pair {
+            let Node::Pair { magnitude, .. } = &mut self.nodes[index] else {
+                unreachable!("node {} holds others, so it's a pair", index)
+            };
+            if magnitude.take().is_none() {
+                break;
+            }
+            pair = self.parents[index];
+        }
+    }
+
+    fn add_to(&mut self, node: Option<usize>, amount: u32) {
+        if let Some(node) = node {
+            if let Node::Num { value, .. } = &mut self.nodes[node] {
+                *value += amount;
+            }
+            self.changed(node);
+        }
+    }
+
+    /// Explodes the pair whose left half is the regular number `left`, returning the 0 that
+    /// replaces it
+    fn explode_at(&mut self, left: usize) -> usize {
+        debug_assert!(self.explodes_at(left));
+        let (a, depth, prev, right) = self.num(left);
+        let right = right.expect("a left half has a right half beside it");
+        let (b, _, _, next) = self.num(right);
+        self.add_to(prev, a);
+        self.add_to(next, b);
+        let pair = self.parents[left].expect("a number this deep is in a pair");
+        self.nodes[pair] = Node::Num {
+            value: 0,
+            depth: depth - 1,
+            prev,
+            next,
+        };
+        self.changed(pair);
+        self.link(prev, Some(pair));
+        self.link(Some(pair), next);
+        if self.first == left {
+            self.first = pair;
+        }
+        if self.last == right {
+            self.last = pair;
+        }
+        pair
+    }
+
+    /// Splits the regular number `node` into a pair, returning the new left half
+    fn split_at(&mut self, node: usize) -> usize {
+        let (value, depth, prev, next) = self.num(node);
+        let (left, right) = (self.nodes.len(), self.nodes.len() + 1);
+        for half in [value / 2, value - value / 2] {
+            self.nodes.push(Node::Num {
+                value: half,
+                depth: depth + 1,
+                prev: None,
+                next: None,
+            });
+            self.parents.push(Some(node));
+        }
+        self.nodes[node] = Node::Pair {
+            left,
+            right,
+            magnitude: None,
+        };
+        self.changed(node);
+        self.link(prev, Some(left));
+        self.link(Some(left), Some(right));
+        self.link(Some(right), next);
+        if self.first == node {
+            self.first = left;
+        }
+        if self.last == node {
+            self.last = right;
+        }
+        left
+    }
+
+    /// The regular numbers from `start` rightwards
+    fn nums_from(&self, start: usize) -> impl Iterator<Item = usize> + '_ {
+        std::iter::successors(Some(start), |&node| self.num(node).3)
+    }
+
+    /// The same as [`SnailFish::magnitude`], using whatever magnitudes are cached
+    pub fn magnitude(&self) -> u64 {
+        fn magnitude(arena: &SnailArena, node: usize) -> u64 {
+            match arena.nodes[node] {
+                Node::Num { value, .. } => value.into(),
+                Node::Pair {
+                    left,
+                    right,
+                    magnitude: cached,
+                } => cached
+                    .unwrap_or_else(|| 3 * magnitude(arena, left) + 2 * magnitude(arena, right)),
+            }
+        }
+        magnitude(self, self.root)
+    }
+
+    /// The magnitude, caching it for every pair so that it's quicker to work out again,
+    /// even for sums of this number. Exploding and splitting forget the cached magnitudes
+    /// of the pairs they change.
+    pub fn cache_magnitudes(&mut self) -> u64 {
+        fn cache(arena: &mut SnailArena, node: usize) -> u64 {
+            match arena.nodes[node] {
+                Node::Num { value, .. } => value.into(),
+                Node::Pair {
+                    magnitude: Some(magnitude),
+                    ..
+                } => magnitude,
+                Node::Pair { left, right, .. } => {
+                    let magnitude = 3 * cache(arena, left) + 2 * cache(arena, right);
+                    arena.nodes[node] = Node::Pair {
+                        left,
+                        right,
+                        magnitude: Some(magnitude),
+                    };
+                    magnitude
+                }
+            }
+        }
+        self.cached = true;
+        cache(self, self.root)
+    }
+
+    /// Explodes the leftmost pair of regular numbers nested inside four pairs, like
+    /// [`SnailFish::explode`], returning whether there was one
+    pub fn explode(&mut self) -> bool {
+        let deep = self
+            .nums_from(self.first)
+            .find(|&node| self.explodes_at(node));
+        deep.map(|left| self.explode_at(left)).is_some()
+    }
+
+    /// Splits the leftmost regular number of 10 or more, like [`SnailFish::split`] does to a
+    /// single number, returning whether there was one
+    pub fn split(&mut self) -> bool {
+        let big = self
+            .nums_from(self.first)
+            .find(|&node| self.num(node).0 >= 10);
+        big.map(|node| self.split_at(node)).is_some()
+    }
+
+    /// Explodes and splits until there's nothing left to do, like [`SnailFish::reduce`], in
+    /// one sweep left to right
+    pub fn reduce(&mut self) {
+        // Exploding can only make the pair the 0 ends up in explode, so every pair that can
+        // goes first, left to right
+        let mut cursor = Some(self.first);
+        while let Some(node) = cursor {
+            if !self.explodes_at(node) {
+                cursor = self.num(node).3;
+                continue;
+            }
+            let zero = self.explode_at(node);
+            cursor = match self.num(zero).2 {
+                Some(prev) if self.parents[prev] == self.parents[zero] => Some(prev),
+                _ => Some(zero),
+            };
+        }
+        // Now everything left of the cursor is shallow enough and less than 10. Splitting
+        // can make a pair that explodes straight away, which can push the number to its
+        // left to 10 or more, so that's where to carry on from.
+        let mut cursor = Some(self.first);
+        while let Some(node) = cursor {
+            let (value, depth, _, next) = self.num(node);
+            if value < 10 {
+                cursor = next;
+                continue;
+            }
+            let left = self.split_at(node);
+            if depth < 4 {
+                cursor = Some(left);
+                continue;
+            }
+            let prev = self.num(left).2;
+            let zero = self.explode_at(left);
+            cursor = match prev {
+                Some(prev) if self.num(prev).0 >= 10 => Some(prev),
+                _ => Some(zero),
+            };
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn arena(s: &str) -> SnailArena {
+        SnailArena::from(&s.parse::<SnailFish>().unwrap())
+    }
+
+    #[test]
+    fn explodes_like_snailfish() {
+        for (given, expected) in [
+            ("[[[[[9,8],1],2],3],4]", "[[[[0,9],2],3],4]"),
+            ("[7,[6,[5,[4,[3,2]]]]]", "[7,[6,[5,[7,0]]]]"),
+            ("[[6,[5,[4,[3,2]]]],1]", "[[6,[5,[7,0]]],3]"),
+            (
+                "[[3,[2,[1,[7,3]]]],[6,[5,[4,[3,2]]]]]",
+                "[[3,[2,[8,0]]],[9,[5,[4,[3,2]]]]]",
+            ),
+        ] {
+            let mut number = arena(given);
+            assert!(number.explode());
+            assert_eq!(SnailFish::from(&number), expected.parse().unwrap());
+        }
+        assert!(!arena("[[1,2],[3,4]]").explode());
+    }
+
+    #[test]
+    fn reduces_unreduced_numbers_like_snailfish() {
+        for given in [
+            "[[[[[[1,2],3],4],5],6],7]",
+            "[[[[[9,[1,2]],3],4],5],6]",
+            "[[[[[[[[1,2],3],4],5],6],7],8],[[[[[[9,8],7],6],5],4],[30,[[[[1,1],1],1],1]]]]",
+        ] {
+            let snailfish: SnailFish = given.parse().unwrap();
+            let mut number = SnailArena::from(&snailfish);
+            number.reduce();
+            assert_eq!(SnailFish::from(&number), snailfish.reduce(), "{}", given);
+        }
+    }
+
+    #[test]
+    fn cached_magnitudes_follow_changes() {
+        let numbers: Vec<SnailArena> = include_str!("test_input.txt").lines().map(arena).collect();
+        for a in &numbers {
+            for b in &numbers {
+                let expected = (a + b).magnitude();
+                let (mut a, mut b) = (a.clone(), b.clone());
+                a.cache_magnitudes();
+                b.cache_magnitudes();
+                let mut sum = &a + &b;
+                assert_eq!(sum.magnitude(), expected);
+                assert_eq!(sum.cache_magnitudes(), expected);
+                assert_eq!(sum.magnitude(), expected);
+            }
+        }
+        let mut number = arena("[[[[[4,3],4],4],[7,[[8,4],9]]],[1,1]]");
+        number.cache_magnitudes();
+        while number.explode() || number.split() {
+            assert_eq!(
+                number.magnitude(),
+                SnailFish::from(&number).magnitude(),
+                "{}",
+                number
+            );
+        }
+    }
+
+    #[test]
+    fn splits_like_snailfish() {
+        let mut number = arena("[[1,11],[21,2]]");
+        assert!(number.split());
+        assert!(number.split());
+        assert_eq!(
+            SnailFish::from(&number),
+            "[[1,[5,6]],[[10,11],2]]".parse().unwrap()
+        );
+    }
+
+    #[test]
+    fn adds_like_snailfish() {
+        let numbers: Vec<SnailFish> = include_str!("test_input.txt")
+            .lines()
+            .map(|line| line.parse().unwrap())
+            .collect();
+        let arenas: Vec<SnailArena> = numbers.iter().map(SnailArena::from).collect();
+        let sum = arenas[1..]
+            .iter()
+            .fold(arenas[0].clone(), |acc, next| &acc + next);
+        let expected = numbers
+            .iter()
+            .cloned()
+            .reduce(|acc, next| acc + next)
+            .unwrap();
+        assert_eq!(SnailFish::from(&sum), expected);
+        assert_eq!(sum.magnitude(), 4140);
+        assert_eq!(sum.to_string(), expected.to_string());
+        for a in &arenas {
+            for b in &arenas {
+                let sum = SnailFish::from(a) + SnailFish::from(b);
+                assert_eq!(SnailFish::from(&(a + b)), sum);
+            }
+        }
+    }
+}
@@ -0,0 +1,114 @@
+//! Strategies for snailfish numbers
+
+use std::ops::RangeInclusive;
+
+use aoc_common::strategies;
+use proptest::prelude::*;
+
+use crate::{SnailArena, SnailFish};
+
+/// A regular number or a pair, nested a few deep
+pub fn element() -> impl Strategy<Value = SnailFish> {
+    strategies::tree(any::<u32>().prop_map(SnailFish::num), |element| {
+        (element.clone(), element).prop_map(|(a, b)| SnailFish::pair(a, b))
+    })
+}
+
+/// A whole snailfish number, which is always a pair at the top
+pub fn snailfish() -> impl Strategy<Value = SnailFish> {
+    (element(), element()).prop_map(|(a, b)| SnailFish::pair(a, b))
+}
+
+/// A snailfish number with pairs nested at most `depth` deep, and regular numbers from
+/// `values`. Sums of reduced numbers are 5 deep, which is as deep as reducing expects.
+pub fn snailfish_within(
+    depth: u32,
+    values: RangeInclusive<u32>,
+) -> impl Strategy<Value = SnailFish> {
+    let element = values.prop_map(SnailFish::num).prop_recursive(
+        depth.saturating_sub(1),
+        1 << depth,
+        2,
+        |element| (element.clone(), element).prop_map(|(a, b)| SnailFish::pair(a, b)),
+    );
+    (element.clone(), element).prop_map(|(a, b)| SnailFish::pair(a, b))
+}
+
+/// Whether nothing in `snailfish` would explode or split
+fn is_reduced(snailfish: &SnailFish) -> bool {
+    snailfish
+        .iter_with_depth()
+        .all(|(depth, n)| depth <= 4 && matches!(n, SnailFish::Num(value) if *value < 10))
+}
+
+/// Written the way the puzzle input writes them, without spaces
+pub fn compact(snailfish: &SnailFish) -> String {
+    snailfish.to_string()
+}
+
+proptest! {
+    #![proptest_config(strategies::config())]
+
+    #[test]
+    fn debug_round_trips(snailfish in snailfish()) {
+        prop_assert_eq!(format!("{:?}", snailfish).parse::<SnailFish>(), Ok(snailfish));
+    }
+
+    #[test]
+    fn compact_round_trips(snailfish in snailfish()) {
+        prop_assert_eq!(compact(&snailfish).parse::<SnailFish>(), Ok(snailfish));
+    }
+
+    #[test]
+    fn rejects_trailing_input(snailfish in snailfish(), rest in "[\\],0-9]{1,3}") {
+        let text = format!("{}{}", compact(&snailfish), rest);
+        prop_assert!(text.parse::<SnailFish>().is_err());
+    }
+
+    #[test]
+    fn stays_within_its_bounds(snailfish in snailfish_within(3, 5..=7)) {
+        for (depth, n) in snailfish.iter_with_depth() {
+            prop_assert!((1..=3).contains(&depth));
+            prop_assert!(matches!(n, SnailFish::Num(5..=7)));
+        }
+    }
+
+    #[test]
+    fn reduces_to_shallow_small_numbers(snailfish in snailfish_within(5, 0..=30)) {
+        let reduced = snailfish.reduce();
+        prop_assert!(is_reduced(&reduced), "{} isn't reduced", reduced);
+    }
+
+    #[test]
+    fn reduce_is_idempotent(snailfish in snailfish_within(5, 0..=30)) {
+        let reduced = snailfish.reduce();
+        prop_assert_eq!(reduced.clone().reduce(), reduced);
+    }
+
+    #[test]
+    fn numbers_are_equivalent_to_their_reductions(
+        snailfish in snailfish_within(5, 0..=30),
+        other in snailfish_within(4, 0..=9),
+    ) {
+        let reduced = snailfish.clone().reduce();
+        prop_assert!(snailfish.equivalent(&reduced));
+        prop_assert!(reduced.equivalent(&snailfish));
+        prop_assert_eq!(snailfish.equivalent(&other), reduced == other.clone().reduce());
+    }
+
+    #[test]
+    fn sums_of_reduced_numbers_are_reduced(
+        a in snailfish_within(4, 0..=9),
+        b in snailfish_within(4, 0..=9),
+    ) {
+        let sum = a.clone() + b.clone();
+        prop_assert!(is_reduced(&sum), "{} isn't reduced", sum);
+        let arena_sum = &SnailArena::from(&a) + &SnailArena::from(&b);
+        prop_assert_eq!(SnailFish::from(&arena_sum), sum);
+    }
+
+    #[test]
+    fn rejects_bare_numbers(n in any::<u32>()) {
+        prop_assert!(n.to_string().parse::<SnailFish>().is_err());
+    }
+}
@@ -1,103 +1,218 @@
+use aoc_common::answer::Answer;
 use std::{
-    collections::{HashSet, VecDeque},
-    fmt::Debug,
-    ops::Add,
+    fmt::{Debug, Display},
+    iter::Sum,
+    ops::{Add, AddAssign, Div, Sub},
     str::FromStr,
 };
 
+mod arena;
+#[cfg(test)]
+mod generators;
 mod parser;
+mod path;
+mod render;
+#[cfg(feature = "serde")]
+pub mod serialize;
+mod tokens;
+
+pub use arena::SnailArena;
+pub use parser::ParseError;
+pub use path::{Path, Side};
+
+/// What a regular number can be: anything that adds, halves and compares like an unsigned
+/// integer, such as `u64` for numbers that won't fit in the puzzle's `u32`
+pub trait Leaf:
+    Copy + Ord + Debug + From<u8> + Add<Output = Self> + Sub<Output = Self> + Div<Output = Self>
+{
+}
 
+impl<T> Leaf for T where
+    T: Copy + Ord + Debug + From<u8> + Add<Output = T> + Sub<Output = T> + Div<Output = T>
+{
+}
+
+/// A snailfish number, whose regular numbers are `u32`s unless you pick another [`Leaf`]
+/// ```rust
+/// # use day18::*;
+/// let big: SnailFish<u64> = "[5000000000,[1,2]]".parse().unwrap();
+/// assert_eq!(big.magnitude(), 3 * 5000000000 + 2 * 7);
+/// assert!("[5000000000,1]".parse::<SnailFish>().is_err());
+/// ```
 #[derive(Clone, PartialEq, Eq)]
-pub enum SnailFish {
-    Num(u32),
-    Pair(Box<(SnailFish, SnailFish)>),
+pub enum SnailFish<T = u32> {
+    Num(T),
+    Pair(Box<(SnailFish<T>, SnailFish<T>)>),
 }
 
-impl Debug for SnailFish {
+impl<T: Debug> Debug for SnailFish<T> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            Self::Num(n) => write!(f, "{}", n),
+            Self::Num(n) => write!(f, "{:?}", n),
             Self::Pair(ref bx) => write!(f, "[{:?}, {:?}]", bx.0, bx.1),
         }
     }
 }
 
-impl Add<SnailFish> for SnailFish {
+/// Written the way the puzzle input writes them, e.g. `[[1,2],3]`, which parses back to the
+/// same number
+/// ```rust
+/// # use day18::*;
+/// let s: SnailFish = "[[1, 2], 3]".parse().unwrap();
+/// assert_eq!(s.to_string(), "[[1,2],3]");
+/// assert_eq!(s.to_string().parse::<SnailFish>(), Ok(s));
+/// ```
+impl<T: Display> Display for SnailFish<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Num(n) => write!(f, "{}", n),
+            Self::Pair(ref bx) => write!(f, "[{},{}]", bx.0, bx.1),
+        }
+    }
+}
+
+impl<T: Leaf> Add for SnailFish<T> {
     type Output = Self;
 
-    fn add(self, rhs: SnailFish) -> Self::Output {
+    fn add(self, rhs: Self) -> Self::Output {
         Self::Pair(Box::new((self, rhs))).reduce()
     }
 }
 
-impl FromStr for SnailFish {
-    type Err = String;
+/// Adds in place, reducing the sum
+/// ```rust
+/// # use day18::*;
+/// let mut total: SnailFish = "[1,2]".parse().unwrap();
+/// total += "[[3,4],5]".parse().unwrap();
+/// assert_eq!(total.to_string(), "[[1,2],[[3,4],5]]");
+/// ```
+impl<T: Leaf> AddAssign for SnailFish<T> {
+    fn add_assign(&mut self, rhs: Self) {
+        let lhs = std::mem::replace(self, SnailFish::Num(T::from(0)));
+        *self = lhs + rhs;
+    }
+}
 
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match parser::root(s) {
-            Ok((_, snailfish)) => Ok(snailfish),
-            Err(e) => Err(e.to_string()),
+/// Adds every number in turn, or `None` if there aren't any, since there's no snailfish
+/// number that adding leaves unchanged
+/// ```rust
+/// # use day18::*;
+/// let numbers: Vec<SnailFish> = ["[1,1]", "[2,2]", "[3,3]", "[4,4]"]
+///     .iter()
+///     .map(|s| s.parse().unwrap())
+///     .collect();
+/// let total: Option<SnailFish> = numbers.into_iter().sum();
+/// assert_eq!(total.unwrap().to_string(), "[[[[1,1],[2,2]],[3,3]],[4,4]]");
+/// assert_eq!(std::iter::empty::<SnailFish>().sum::<Option<SnailFish>>(), None);
+/// ```
+impl<T: Leaf> Sum<SnailFish<T>> for Option<SnailFish<T>> {
+    fn sum<I: Iterator<Item = SnailFish<T>>>(mut iter: I) -> Self {
+        let mut total = iter.next()?;
+        for next in iter {
+            total += next;
         }
+        Some(total)
     }
 }
 
-impl SnailFish {
-    pub fn pair(a: SnailFish, b: SnailFish) -> Self {
-        Self::Pair(Box::new((a, b)))
+/// Collects into the reduced sum, like [`Sum`], so a puzzle input can be added up as it's
+/// parsed
+/// ```rust
+/// # use day18::*;
+/// let total: Result<Option<SnailFish>, _> = "[1,1]\n[2,2]\n[3,3]\n[4,4]\n"
+///     .lines()
+///     .map(str::parse)
+///     .collect();
+/// assert_eq!(total.unwrap().unwrap().to_string(), "[[[[1,1],[2,2]],[3,3]],[4,4]]");
+/// ```
+impl<T: Leaf> FromIterator<SnailFish<T>> for Option<SnailFish<T>> {
+    fn from_iter<I: IntoIterator<Item = SnailFish<T>>>(iter: I) -> Self {
+        iter.into_iter().sum()
     }
-    pub fn num(n: u32) -> Self {
-        Self::Num(n)
+}
+
+/// Parses a number written the way the puzzle writes them, ignoring whitespace around it and
+/// between its parts
+/// ```rust
+/// # use day18::*;
+/// let s: SnailFish = " [[1, 2],\n3]\n".parse().unwrap();
+/// assert_eq!(s.to_string(), "[[1,2],3]");
+/// let err = "[[1,2]3]".parse::<SnailFish>().unwrap_err();
+/// assert_eq!(err.to_string(), "expected ',' at byte 6, found '3'");
+/// ```
+impl<T: FromStr> FromStr for SnailFish<T> {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parser::parse(s)
     }
-    fn try_add_value(&mut self, rhs: u32) -> Option<&Self> {
-        match self {
-            Self::Num(n) => {
-                *n += rhs;
-                Some(self)
-            }
-            Self::Pair(_) => None,
-        }
+}
+
+impl<T> SnailFish<T> {
+    pub fn pair(a: SnailFish<T>, b: SnailFish<T>) -> Self {
+        Self::Pair(Box::new((a, b)))
     }
-    fn recurse_left_mut(&mut self) -> &mut Self {
-        let mut cur = self;
-        while let SnailFish::Pair(ref mut bx) = cur {
-            cur = &mut bx.0;
-        }
-        cur
+    pub fn num(n: T) -> Self {
+        Self::Num(n)
     }
-    fn recurse_right_mut(&mut self) -> &mut Self {
-        let mut cur = self;
-        while let SnailFish::Pair(ref mut bx) = cur {
-            cur = &mut bx.1;
-        }
-        cur
+
+    /// Produce all the numbers out of a SnailFish
+    pub fn iter(&self) -> impl Iterator<Item = &SnailFish<T>> {
+        self.iter_with_depth().map(|(_, snail)| snail)
     }
 
-    /// Recursively calculates the magnitude of a SnailFish
+    /// Produce all the numbers out of a SnailFish, left to right, each with how many pairs
+    /// it's inside. A pair explodes when both its numbers are more than 4 deep.
     /// ```rust
     /// # use day18::*;
-    /// let s = SnailFish::pair(
-    ///     SnailFish::pair(
-    ///         SnailFish::num(1),
-    ///         SnailFish::num(2)
-    ///     ),
-    ///     SnailFish::pair(
-    ///         SnailFish::pair(SnailFish::num(3), SnailFish::num(4)),
-    ///         SnailFish::num(5)
-    ///     )
-    /// );
-    /// // ((1, 2), ((3, 4), 5))
-    /// assert_eq!(s.magnitude(), 143)
+    /// let s: SnailFish = "[[1,[2,3]],4]".parse().unwrap();
+    /// let depths: Vec<(usize, String)> = s
+    ///     .iter_with_depth()
+    ///     .map(|(depth, n)| (depth, n.to_string()))
+    ///     .collect();
+    /// assert_eq!(depths, vec![
+    ///     (2, String::from("1")),
+    ///     (3, String::from("2")),
+    ///     (3, String::from("3")),
+    ///     (1, String::from("4")),
+    /// ]);
     /// ```
-    pub fn magnitude(&self) -> u64 {
-        match self {
-            SnailFish::Num(x) => *x as u64,
-            SnailFish::Pair(ref bx) => {
-                let (left, right) = bx.as_ref();
-                left.magnitude() * 3 + right.magnitude() * 2
+    pub fn iter_with_depth(&self) -> impl Iterator<Item = (usize, &SnailFish<T>)> {
+        // All the pairs we haven't iterated through yet, next on top
+        let mut stack = vec![(0, self)];
+        std::iter::from_fn(move || loop {
+            let (depth, snail) = stack.pop()?;
+            match snail {
+                Self::Num(_) => return Some((depth, snail)),
+                Self::Pair(ref bx) => {
+                    let (ref a, ref b) = **bx;
+                    stack.push((depth + 1, b));
+                    stack.push((depth + 1, a));
+                }
+            }
+        })
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut SnailFish<T>> {
+        let mut acc = Vec::new(); // We'll return this one
+        let mut stack = vec![self]; // All the pairs we haven't iterated through yet
+
+        while let Some(snail) = stack.pop() {
+            match snail {
+                Self::Num(_) => acc.push(snail),
+                Self::Pair(bx) => {
+                    let (ref mut a, ref mut b) = **bx;
+                    stack.push(a);
+                    stack.push(b);
+                }
             }
         }
+
+        acc.into_iter().rev()
     }
+}
 
+impl<T: Leaf> SnailFish<T> {
     /// Split a number in two if it's valid to do so.
     /// SnailFish numbers split in two when they are single
     /// numbers >= 10 (pairs never split and nums < 10 never split)
@@ -114,176 +229,223 @@ impl SnailFish {
     /// split returns true if it took an action and false if it did not
     pub fn split(&mut self) -> bool {
         match self {
-            Self::Num(d) if *d >= 10 => {
-                let (left, mut right) = (*d / 2, *d / 2);
-                if left + right < *d {
-                    right += 1;
-                }
+            Self::Num(d) if *d >= T::from(10) => {
+                let left = *d / T::from(2);
+                let right = *d - left;
                 *self = Self::pair(SnailFish::num(left), SnailFish::num(right));
                 true
             }
             _ => false,
         }
     }
-    pub fn reduce(mut self) -> Self {
-        'outer: loop {
-            // if anything can explode, go back to the start
-            if self.explode() {
-                continue 'outer;
-            }
-
-            for snailfish in self.iter_mut() {
-                if snailfish.split() {
-                    // if anything can split, go back to the start
-                    continue 'outer;
-                }
-            }
-            break;
-        }
-        self
+    /// Explodes and splits until there's nothing left to do, exploding whenever anything can
+    pub fn reduce(self) -> Self {
+        let mut tokens = tokens::flatten(&self);
+        tokens::reduce(&mut tokens);
+        tokens::unflatten(&tokens)
     }
 
+    /// Explodes the leftmost pair of regular numbers nested inside four pairs, adding its left
+    /// number to the nearest regular number to its left and its right number to the nearest
+    /// to its right, then replacing it with 0.
+    ///
+    /// explode returns true if it took an action and false if it did not
     pub fn explode(&mut self) -> bool {
-        // We use raw pointers here because we want to pass around what are (effectively) multiple mutable references
-        // to the same object. Since we have eclusive access to self (via the &mut self reference) and never read a
-        // value after modifying it, this is safe.
-        let mut queue = VecDeque::from([(0, self as *mut SnailFish, Vec::new())]);
-
-        /// Takes a mutable raw pointer and returns the *mut to the rightmost Snailfish
-        unsafe fn get_rightmost_value_from(
-            this: *mut SnailFish,
-            mut parents: Vec<*mut SnailFish>,
-        ) -> Option<*mut SnailFish> {
-            let mut seen = HashSet::new();
-            while let Some(parent) = parents.pop() {
-                if let SnailFish::Pair(ref mut bx) = *parent {
-                    if this == &mut bx.1 || seen.iter().any(|&p| p == &mut bx.1 as *mut SnailFish) {
-                        seen.insert(parent);
-                        continue;
-                    } else {
-                        let target = &mut bx.1;
-                        return Some(target.recurse_left_mut() as *mut SnailFish);
-                    }
-                }
-            }
-            None
-        }
-        /// Takes a mutable raw pointer and returns the *mut to the leftmost Snailfish
-        unsafe fn get_leftmost_value_from(
-            this: *mut SnailFish,
-            mut parents: Vec<*mut SnailFish>,
-        ) -> Option<*mut SnailFish> {
-            let mut seen = HashSet::new();
-            while let Some(parent) = parents.pop() {
-                if let SnailFish::Pair(ref mut bx) = *parent {
-                    if this == &mut bx.0 || seen.iter().any(|&p| p == &mut bx.0 as *mut SnailFish) {
-                        seen.insert(parent);
-                        continue;
-                    } else {
-                        let target = &mut bx.0;
-                        return Some(target.recurse_right_mut() as *mut SnailFish);
-                    }
-                }
-            }
-            None
+        let mut tokens = tokens::flatten(self);
+        let exploded = tokens::explode(&mut tokens);
+        if exploded {
+            *self = tokens::unflatten(&tokens);
         }
+        exploded
+    }
 
-        while let Some((depth, cur, parents)) = queue.pop_front() {
-            if depth >= 4 {
-                unsafe {
-                    // we only need to explode if we're in a pair
-                    if let SnailFish::Num(_) = *cur {
-                        continue;
-                    }
-                    let left_val = if let SnailFish::Pair(ref bx) = *cur {
-                        if let SnailFish::Num(n) = bx.0 {
-                            Some(n)
-                        } else {
-                            None
-                        }
-                    } else {
-                        None
-                    };
-                    let right_val = if let SnailFish::Pair(ref bx) = *cur {
-                        if let SnailFish::Num(n) = bx.1 {
-                            Some(n)
-                        } else {
-                            None
-                        }
-                    } else {
-                        None
-                    };
-                    let leftmost = get_leftmost_value_from(cur, parents.clone());
-                    let rightmost = get_rightmost_value_from(cur, parents);
-                    if let (Some(left_snail), Some(left_val)) = (leftmost, left_val) {
-                        (*left_snail)
-                            .try_add_value(left_val)
-                            .expect("left_snail should always be a Num, so this shouldn't fail");
-                    }
-                    if let (Some(right_snail), Some(right_val)) = (rightmost, right_val) {
-                        (*right_snail)
-                            .try_add_value(right_val)
-                            .expect("right_snail should always be a Num, so this shouldn't fail");
-                    }
-                    *cur = SnailFish::Num(0);
-                    return true;
-                }
-            } else {
-                unsafe {
-                    if let SnailFish::Pair(ref mut bx) = *cur {
-                        let mut new_parents = parents.clone();
-                        new_parents.push(cur);
-                        queue.push_back((
-                            depth + 1,
-                            &mut bx.0 as *mut SnailFish,
-                            new_parents.clone(),
-                        ));
-                        queue.push_back((depth + 1, &mut bx.1 as *mut SnailFish, new_parents));
-                    }
-                }
+    /// Whether the two numbers are the same once they're both reduced, unlike `==`, which
+    /// tells unreduced numbers apart from what they reduce to
+    /// ```rust
+    /// # use day18::*;
+    /// let unreduced: SnailFish = "[[[[[4,3],4],4],[7,[[8,4],9]]],[1,1]]".parse().unwrap();
+    /// let reduced: SnailFish = "[[[[0,7],4],[[7,8],[6,0]]],[8,1]]".parse().unwrap();
+    /// assert_ne!(unreduced, reduced);
+    /// assert!(unreduced.equivalent(&reduced));
+    /// assert!(!unreduced.equivalent(&"[1,1]".parse().unwrap()));
+    /// ```
+    pub fn equivalent(&self, other: &Self) -> bool {
+        // A list of regular numbers and their depths only fits one shape of pairs, so there's
+        // no need to build the trees back up to compare them
+        let reduced = |snailfish| {
+            let mut tokens = tokens::flatten(snailfish);
+            tokens::reduce(&mut tokens);
+            tokens
+        };
+        reduced(self) == reduced(other)
+    }
+}
+
+impl<T: Copy + Into<u64>> SnailFish<T> {
+    /// Recursively calculates the magnitude of a SnailFish
+    /// ```rust
+    /// # use day18::*;
+    /// let s: SnailFish = SnailFish::pair(
+    ///     SnailFish::pair(
+    ///         SnailFish::num(1),
+    ///         SnailFish::num(2)
+    ///     ),
+    ///     SnailFish::pair(
+    ///         SnailFish::pair(SnailFish::num(3), SnailFish::num(4)),
+    ///         SnailFish::num(5)
+    ///     )
+    /// );
+    /// // ((1, 2), ((3, 4), 5))
+    /// assert_eq!(s.magnitude(), 143)
+    /// ```
+    pub fn magnitude(&self) -> u64 {
+        match self {
+            SnailFish::Num(x) => (*x).into(),
+            SnailFish::Pair(ref bx) => {
+                let (left, right) = bx.as_ref();
+                left.magnitude() * 3 + right.magnitude() * 2
             }
         }
-        false
     }
 
-    /// Produce all the numbers out of a SnailFish
-    pub fn iter(&self) -> impl Iterator<Item = &SnailFish> {
-        let mut acc = Vec::new(); // We'll return this one
-        let mut stack = vec![self]; // All the pairs we haven't iterated through yet
+    /// The sum of every regular number in the SnailFish
+    fn leaf_sum(&self) -> u64 {
+        self.iter()
+            .map(|snail| match snail {
+                SnailFish::Num(n) => (*n).into(),
+                SnailFish::Pair(_) => 0,
+            })
+            .sum()
+    }
+}
 
-        while let Some(snail) = stack.pop() {
-            match snail {
-                Self::Num(_) => acc.push(snail),
-                Self::Pair(ref bx) => {
-                    let (ref a, ref b) = **bx;
-                    stack.push(a);
-                    stack.push(b);
-                }
-            }
+/// Largest magnitude of the sum of any two different numbers, trying every ordered pair. With
+/// the `parallel` feature, each number's sums with the others are added up on a core of their
+/// own.
+pub fn max_pair_magnitude(numbers: &[SnailFish]) -> Option<u64> {
+    let arenas: Vec<SnailArena> = numbers.iter().map(SnailArena::from).collect();
+    let best_with = |i: usize| {
+        (0..arenas.len())
+            .filter(|&j| j != i)
+            .map(|j| (&arenas[i] + &arenas[j]).magnitude())
+            .max()
+    };
+    #[cfg(feature = "parallel")]
+    {
+        use rayon::prelude::*;
+        (0..arenas.len())
+            .into_par_iter()
+            .filter_map(best_with)
+            .max()
+    }
+    #[cfg(not(feature = "parallel"))]
+    {
+        (0..arenas.len()).filter_map(best_with).max()
+    }
+}
+
+/// The same answer as [`max_pair_magnitude`], but skips additions that provably can't win.
+///
+/// Exploding never creates value (it only moves it, or drops it off the ends) and splitting
+/// preserves it, so the leaves of a reduced sum can never add up to more than the leaves of its
+/// operands. Combined with every leaf being at most 9 and at most 4 deep, that caps the
+/// magnitude of a sum (see [`magnitude_upper_bound`]). Unordered pairs are visited in order of
+/// decreasing bound, trying both orders of each, and the search stops as soon as no remaining
+/// pair could beat the best magnitude found so far.
+pub fn max_pair_magnitude_pruned(numbers: &[SnailFish]) -> Option<u64> {
+    let leaf_sums: Vec<u64> = numbers.iter().map(SnailFish::leaf_sum).collect();
+    let arenas: Vec<SnailArena> = numbers.iter().map(SnailArena::from).collect();
+    let mut pairs: Vec<(u64, usize, usize)> = (0..numbers.len())
+        .flat_map(|i| (i + 1..numbers.len()).map(move |j| (i, j)))
+        .map(|(i, j)| (magnitude_upper_bound(leaf_sums[i] + leaf_sums[j]), i, j))
+        .collect();
+    pairs.sort_unstable_by_key(|&(bound, _, _)| std::cmp::Reverse(bound));
+
+    let mut best = None;
+    for (bound, i, j) in pairs {
+        if best.is_some_and(|best| best >= bound) {
+            break;
+        }
+        for (a, b) in [(i, j), (j, i)] {
+            let magnitude = (&arenas[a] + &arenas[b]).magnitude();
+            best = best.max(Some(magnitude));
         }
+    }
+    best
+}
 
-        acc.into_iter().rev()
+/// The largest magnitude a reduced SnailFish could have if its leaves add up to `leaf_sum`.
+///
+/// Moving a leaf's value deeper down its leftmost path never lowers its weight, so the best case
+/// is a full tree 4 deep with 9s packed into the most heavily weighted leaves.
+pub fn magnitude_upper_bound(leaf_sum: u64) -> u64 {
+    // weights of the 16 leaves of a full tree 4 deep, heaviest first: 3^l * 2^r for l + r = 4
+    const WEIGHTS: [u64; 16] = [
+        81, 54, 54, 54, 54, 36, 36, 36, 36, 36, 36, 24, 24, 24, 24, 16,
+    ];
+    let mut remaining = leaf_sum;
+    let mut bound = 0;
+    for weight in WEIGHTS {
+        let value = remaining.min(9);
+        bound += weight * value;
+        remaining -= value;
     }
+    bound
+}
 
-    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut SnailFish> {
-        let mut acc = Vec::new(); // We'll return this one
-        let mut stack = vec![self]; // All the pairs we haven't iterated through yet
+pub const INPUT: &str = include_str!("input.txt");
 
-        while let Some(snail) = stack.pop() {
-            match snail {
-                Self::Num(_) => acc.push(snail),
-                Self::Pair(bx) => {
-                    let (ref mut a, ref mut b) = **bx;
-                    stack.push(a);
-                    stack.push(b);
-                }
-            }
-        }
+#[derive(Debug, Clone)]
+pub struct Input {
+    numbers: Vec<SnailFish>,
+}
 
-        acc.into_iter().rev()
+impl From<Vec<SnailFish>> for Input {
+    fn from(numbers: Vec<SnailFish>) -> Self {
+        Self { numbers }
     }
 }
 
+pub fn solve_part1(input: Input) -> Option<u64> {
+    input
+        .numbers
+        .into_iter()
+        .sum::<Option<SnailFish>>()
+        .map(|total| total.magnitude())
+}
+
+pub fn solve_part2(input: Input) -> Option<u64> {
+    max_pair_magnitude_pruned(&input.numbers)
+}
+
+fn parse_numbers(input: &str) -> Result<Input, String> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| {
+            line.parse::<SnailFish>()
+                .map_err(|e| format!("line {}: {}", i + 1, e))
+        })
+        .collect::<Result<Vec<_>, _>>()
+        .map(Input::from)
+}
+
+/// Magnitude of the sum of every number
+pub fn part1(input: &str) -> Result<Answer, String> {
+    solve_part1(parse_numbers(input)?)
+        .map(Answer::from)
+        .ok_or_else(|| String::from("Input has no numbers"))
+}
+
+/// Largest magnitude of any sum of two different numbers
+pub fn part2(input: &str) -> Result<Answer, String> {
+    solve_part2(parse_numbers(input)?)
+        .map(Answer::from)
+        .ok_or_else(|| String::from("Input needs at least two numbers"))
+}
+
+aoc_common::register_day!(18);
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -310,6 +472,21 @@ mod tests {
         )
     }
 
+    #[test]
+    fn snailfish_iter_with_depth() {
+        let unreduced: SnailFish = "[[[[[9,8],1],2],3],4]".parse().unwrap();
+        assert_eq!(
+            unreduced.iter_with_depth().map(|(depth, _)| depth).max(),
+            Some(5)
+        );
+        let reduced = unreduced.reduce();
+        assert!(reduced.iter_with_depth().all(|(depth, _)| depth <= 4));
+        assert_eq!(
+            SnailFish::num(7).iter_with_depth().collect::<Vec<_>>(),
+            vec![(0, &SnailFish::num(7))]
+        );
+    }
+
     #[test]
     fn test_snailfish_split() {
         let mut s = SnailFish::num(10);
@@ -386,6 +563,8 @@ mod tests {
     }
 
     #[test]
+    // Checks `Add` itself, which `+=` would go through `AddAssign` to reach
+    #[allow(clippy::assign_op_pattern)]
     fn test_reduce() {
         let mut acc = "[[[0,[4,5]],[0,0]],[[[4,5],[2,6]],[9,5]]]"
             .parse::<SnailFish>()
@@ -402,4 +581,143 @@ mod tests {
                 .unwrap()
         );
     }
+
+    #[test]
+    fn test_reduce_in_place() {
+        let mut acc = "[[[0,[4,5]],[0,0]],[[[4,5],[2,6]],[9,5]]]"
+            .parse::<SnailFish>()
+            .unwrap();
+        let next = "[7,[[[3,7],[4,3]],[[6,3],[8,8]]]]"
+            .parse::<SnailFish>()
+            .unwrap();
+        acc += next.clone();
+        let added = "[[[0,[4,5]],[0,0]],[[[4,5],[2,6]],[9,5]]]"
+            .parse::<SnailFish>()
+            .unwrap()
+            + next;
+        assert_eq!(acc, added);
+    }
+
+    #[test]
+    fn test_display_writes_the_input_back() {
+        for line in INPUT.lines() {
+            assert_eq!(line.parse::<SnailFish>().unwrap().to_string(), line);
+        }
+    }
+
+    #[test]
+    fn test_parse_rejects_deep_nesting() {
+        let deep = format!("{}[1,2]{}", "[1,".repeat(100), "]".repeat(100));
+        assert!(deep.parse::<SnailFish>().is_err());
+        let ok = format!("{}[1,2]{}", "[1,".repeat(10), "]".repeat(10));
+        assert!(ok.parse::<SnailFish>().is_ok());
+    }
+
+    #[test]
+    fn test_magnitude_upper_bound() {
+        assert_eq!(magnitude_upper_bound(0), 0);
+        assert_eq!(magnitude_upper_bound(5), 405);
+        assert_eq!(magnitude_upper_bound(10), 729 + 54);
+        // all 9s, 4 deep
+        let full = "[[[[9,9],[9,9]],[[9,9],[9,9]]],[[[9,9],[9,9]],[[9,9],[9,9]]]]"
+            .parse::<SnailFish>()
+            .unwrap();
+        assert_eq!(magnitude_upper_bound(1000), full.magnitude());
+    }
+
+    #[test]
+    fn test_max_pair_magnitude_pruned() {
+        let numbers: Vec<SnailFish> = include_str!("test_input.txt")
+            .lines()
+            .map(|line| line.parse().unwrap())
+            .collect();
+        assert_eq!(max_pair_magnitude(&numbers), Some(3993));
+        assert_eq!(max_pair_magnitude_pruned(&numbers), Some(3993));
+        for n in 0..numbers.len() {
+            assert_eq!(
+                max_pair_magnitude_pruned(&numbers[n..]),
+                max_pair_magnitude(&numbers[n..])
+            );
+        }
+        assert_eq!(max_pair_magnitude_pruned(&numbers[..1]), None);
+    }
+}
+
+#[cfg(test)]
+mod solve_tests {
+    use super::*;
+    const INPUT: &str = include_str!("test_input.txt");
+
+    #[test]
+    fn solve_part1() {
+        let input: Input = INPUT
+            .lines()
+            .map(|line| {
+                line.parse::<SnailFish>()
+                    .expect("Input lines must all parse")
+            })
+            .collect::<Vec<_>>()
+            .into();
+        let result = super::solve_part1(input);
+        assert_eq!(result, Some(4140))
+    }
+    #[test]
+    fn solve_part2() {
+        let input: Input = INPUT
+            .lines()
+            .map(|line| {
+                line.parse::<SnailFish>()
+                    .expect("Input lines must all parse")
+            })
+            .collect::<Vec<_>>()
+            .into();
+        let result = super::solve_part2(input);
+        assert_eq!(result, Some(3993))
+    }
+
+    #[test]
+    fn test_sum() {
+        let input_fish = INPUT
+            .lines()
+            .map(|line| {
+                line.parse::<SnailFish>()
+                    .expect("Input lines must all parse")
+            })
+            .reduce(|acc, next| acc + next)
+            .expect("input is nonempty");
+
+        let expected: SnailFish = "[[[[6,6],[7,6]],[[7,7],[7,0]]],[[[7,7],[7,7]],[[7,8],[9,9]]]]"
+            .parse()
+            .expect("expected must parse");
+        assert_eq!(input_fish, expected);
+    }
+
+    #[test]
+    fn test_sum_into_option() {
+        let numbers: Vec<SnailFish> = INPUT
+            .lines()
+            .map(|line| line.parse().expect("Input lines must all parse"))
+            .collect();
+        assert_eq!(
+            numbers.iter().cloned().sum::<Option<SnailFish>>(),
+            numbers.into_iter().reduce(|acc, next| acc + next)
+        );
+        assert_eq!(
+            Vec::<SnailFish>::new()
+                .into_iter()
+                .sum::<Option<SnailFish>>(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_collect_parsed_lines() {
+        let total: Option<SnailFish> = INPUT
+            .lines()
+            .map(str::parse)
+            .collect::<Result<_, _>>()
+            .expect("Input lines must all parse");
+        assert_eq!(total.map(|total| total.magnitude()), Some(4140));
+        assert_eq!(std::iter::empty::<SnailFish>().collect::<Option<_>>(), None);
+    }
 }
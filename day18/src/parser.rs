@@ -1,37 +1,154 @@
-use nom::IResult;
+//! Reading snailfish numbers the way the puzzle writes them, with errors that say where and
+//! why they went wrong
+
+use std::{fmt::Display, str::FromStr};
+
+use nom::{
+    character::complete::{char, digit1, multispace0},
+    sequence::{delimited, preceded, separated_pair, terminated},
+    IResult,
+};
 
 use crate::SnailFish;
 
-pub(crate) fn inner(s: &str) -> IResult<&str, (SnailFish, SnailFish)> {
-    nom::sequence::separated_pair(
-        nom::branch::alt((
-            nom::combinator::map(nom::character::complete::u32, SnailFish::num),
-            outer,
-        )),
-        nom::sequence::pair(
-            nom::character::complete::char(','),
-            nom::character::complete::multispace0,
-        ),
-        nom::branch::alt((
-            nom::combinator::map(nom::character::complete::u32, SnailFish::num),
-            outer,
-        )),
+/// How deep pairs can nest, deeper than any reduced number (4) or one part way through being
+/// added (5), but shallow enough that the recursive parser can't run out of stack
+const MAX_DEPTH: usize = 64;
+
+/// Why a snailfish number didn't parse
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// Counting bytes from 0
+    pub position: usize,
+    pub expected: &'static str,
+    /// `None` at the end of the input
+    pub found: Option<char>,
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "expected {} at byte {}, ", self.expected, self.position)?;
+        match self.found {
+            Some(c) => write!(f, "found {:?}", c),
+            None => write!(f, "found the end"),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// What the parser wanted, and the rest of the input from where it wanted it
+#[derive(Debug, PartialEq)]
+pub(crate) struct Expected<'a> {
+    rest: &'a str,
+    expected: &'static str,
+}
+
+impl<'a> nom::error::ParseError<&'a str> for Expected<'a> {
+    fn from_error_kind(rest: &'a str, _: nom::error::ErrorKind) -> Self {
+        Self {
+            rest,
+            expected: "something else",
+        }
+    }
+
+    fn append(_: &'a str, _: nom::error::ErrorKind, other: Self) -> Self {
+        other
+    }
+}
+
+type Parsed<'a, O> = IResult<&'a str, O, Expected<'a>>;
+
+/// Says what `parser` wanted if it fails without getting anywhere. Failures from part way
+/// through a pair already say what went wrong, so they're left alone.
+fn expect<'a, O>(
+    expected: &'static str,
+    mut parser: impl FnMut(&'a str) -> Parsed<'a, O>,
+) -> impl FnMut(&'a str) -> Parsed<'a, O> {
+    move |s| {
+        parser(s).map_err(|e| match e {
+            nom::Err::Error(_) => nom::Err::Error(Expected { rest: s, expected }),
+            e => e,
+        })
+    }
+}
+
+/// `c`, after any whitespace
+fn token<'a>(c: char, expected: &'static str) -> impl FnMut(&'a str) -> Parsed<'a, char> {
+    preceded(multispace0, expect(expected, char(c)))
+}
+
+/// A regular number, as whatever type it's parsed into
+pub(crate) fn num<T: FromStr>(s: &str) -> Parsed<'_, SnailFish<T>> {
+    let (rest, digits) = digit1(s)?;
+    match digits.parse() {
+        Ok(n) => Ok((rest, SnailFish::num(n))),
+        Err(_) => Err(nom::Err::Failure(Expected {
+            rest: s,
+            expected: "a regular number small enough to fit",
+        })),
+    }
+}
+
+/// Either half of a pair
+fn element<T: FromStr>(s: &str) -> Parsed<'_, SnailFish<T>> {
+    preceded(
+        multispace0,
+        expect("a regular number or '['", nom::branch::alt((num, outer))),
     )(s)
 }
 
-pub(crate) fn outer(s: &str) -> IResult<&str, SnailFish> {
-    nom::combinator::map(
-        nom::sequence::delimited(
-            nom::bytes::complete::tag("["),
-            inner,
-            nom::bytes::complete::tag("]"),
-        ),
+pub(crate) fn inner<T: FromStr>(s: &str) -> Parsed<'_, (SnailFish<T>, SnailFish<T>)> {
+    separated_pair(element, token(',', "','"), element)(s)
+}
+
+/// A pair, which can't be anything else once it's started with `[`
+pub(crate) fn outer<T: FromStr>(s: &str) -> Parsed<'_, SnailFish<T>> {
+    let (s, _) = char('[')(s)?;
+    nom::combinator::cut(nom::combinator::map(
+        terminated(inner, token(']', "']'")),
         |(a, b)| SnailFish::pair(a, b),
-    )(s)
+    ))(s)
+}
+
+pub(crate) fn root<T: FromStr>(s: &str) -> Parsed<'_, SnailFish<T>> {
+    let (rest, snailfish) = delimited(multispace0, expect("'['", outer), multispace0)(s)?;
+    if !rest.is_empty() {
+        return Err(nom::Err::Error(Expected {
+            rest,
+            expected: "the end",
+        }));
+    }
+    Ok((rest, snailfish))
 }
 
-pub(crate) fn root(s: &str) -> IResult<&str, SnailFish> {
-    nom::combinator::all_consuming(outer)(s)
+/// Parses a whole snailfish number, which may have whitespace around it and between its
+/// parts
+pub(crate) fn parse<T: FromStr>(s: &str) -> Result<SnailFish<T>, ParseError> {
+    let error_at = |rest: &str, expected| ParseError {
+        position: s.len() - rest.len(),
+        expected,
+        found: rest.chars().next(),
+    };
+    let mut depth: usize = 0;
+    for (i, ch) in s.char_indices() {
+        match ch {
+            '[' => depth += 1,
+            ']' => depth = depth.saturating_sub(1),
+            _ => {}
+        }
+        if depth > MAX_DEPTH {
+            return Err(error_at(
+                &s[i..],
+                "a regular number, since pairs can't nest any deeper",
+            ));
+        }
+    }
+    match root(s) {
+        Ok((_, snailfish)) => Ok(snailfish),
+        Err(nom::Err::Error(e) | nom::Err::Failure(e)) => Err(error_at(e.rest, e.expected)),
+        Err(nom::Err::Incomplete(_)) => unreachable!("only complete parsers are used"),
+    }
 }
 
 #[cfg(test)]
@@ -41,7 +158,7 @@ mod tests {
     #[test]
     fn test_single_pair() {
         let s = "[1, 2]";
-        let (_, result) = root(s).unwrap();
+        let (_, result) = root::<u32>(s).unwrap();
         assert_eq!(
             result,
             SnailFish::pair(SnailFish::num(1), SnailFish::num(2))
@@ -51,7 +168,7 @@ mod tests {
     #[test]
     fn test_multiple_pairs() {
         let s = "[1, [2, [3, 4]]]";
-        let (_, result) = root(s).unwrap();
+        let (_, result) = root::<u32>(s).unwrap();
         assert_eq!(
             result,
             SnailFish::pair(
@@ -63,4 +180,59 @@ mod tests {
             )
         );
     }
+
+    fn error(s: &str) -> ParseError {
+        parse::<u32>(s).unwrap_err()
+    }
+
+    #[test]
+    fn test_surrounding_whitespace() {
+        let expected = SnailFish::pair(SnailFish::num(1), SnailFish::num(2));
+        assert_eq!(parse("[1,2]\n"), Ok(expected.clone()));
+        assert_eq!(parse("\t[ 1 ,\r\n 2 ]  \n"), Ok(expected));
+    }
+
+    #[test]
+    fn test_errors_say_where_and_what() {
+        let cases = [
+            ("", 0, "'['", None),
+            ("1", 0, "'['", Some('1')),
+            ("[1,2", 4, "']'", None),
+            ("[1 2]", 3, "','", Some('2')),
+            ("[1,[x,2]]", 4, "a regular number or '['", Some('x')),
+            ("[1,2]]", 5, "the end", Some(']')),
+            (
+                "[1,99999999999]",
+                3,
+                "a regular number small enough to fit",
+                Some('9'),
+            ),
+        ];
+        for (s, position, expected, found) in cases {
+            assert_eq!(
+                error(s),
+                ParseError {
+                    position,
+                    expected,
+                    found
+                },
+                "{:?}",
+                s
+            );
+        }
+    }
+
+    #[test]
+    fn test_error_positions_count_bytes() {
+        assert_eq!(error("[é,1]").position, 1);
+        assert_eq!(error("[[1,2],é]").position, 7);
+    }
+
+    #[test]
+    fn test_too_deep_points_at_the_bracket() {
+        let deep = "[".repeat(MAX_DEPTH + 1);
+        let e = error(&deep);
+        assert_eq!(e.position, MAX_DEPTH);
+        assert_eq!(e.found, Some('['));
+    }
 }
@@ -0,0 +1,174 @@
+//! Finding a way down to any part of a snailfish number
+
+use std::{fmt::Display, str::FromStr};
+
+use crate::SnailFish;
+
+/// Which half of a pair to go into
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Side {
+    Left,
+    Right,
+}
+
+/// The halves to go into, from the top, to reach part of a snailfish number. The empty path
+/// is the whole number.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct Path(Vec<Side>);
+
+impl Path {
+    /// The whole number
+    pub fn root() -> Self {
+        Self::default()
+    }
+
+    /// This path and then one step further, into `side`
+    pub fn child(&self, side: Side) -> Self {
+        let mut child = self.clone();
+        child.0.push(side);
+        child
+    }
+
+    /// The pair this path leads into, or `None` for the root
+    pub fn parent(&self) -> Option<Self> {
+        let (_, steps) = self.0.split_last()?;
+        Some(Self(steps.to_vec()))
+    }
+
+    pub fn push(&mut self, side: Side) {
+        self.0.push(side)
+    }
+
+    pub fn pop(&mut self) -> Option<Side> {
+        self.0.pop()
+    }
+
+    /// How many pairs deep it leads
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = Side> + '_ {
+        self.0.iter().copied()
+    }
+}
+
+impl FromIterator<Side> for Path {
+    fn from_iter<T: IntoIterator<Item = Side>>(iter: T) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
+/// `L` and `R` for each step, like `LRR`
+impl Display for Path {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for side in self.iter() {
+            let letter = match side {
+                Side::Left => 'L',
+                Side::Right => 'R',
+            };
+            write!(f, "{}", letter)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Path {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.chars()
+            .map(|c| match c {
+                'L' => Ok(Side::Left),
+                'R' => Ok(Side::Right),
+                _ => Err(format!("`{}` isn't L or R", c)),
+            })
+            .collect()
+    }
+}
+
+impl<T> SnailFish<T> {
+    /// The part of the number that `path` leads to, or `None` if it goes into a regular number
+    /// ```rust
+    /// # use day18::*;
+    /// let s: SnailFish = "[[1,[2,3]],4]".parse().unwrap();
+    /// let path: Path = "LR".parse().unwrap();
+    /// assert_eq!(s.get(&path).unwrap().to_string(), "[2,3]");
+    /// assert_eq!(s.get(&"RL".parse().unwrap()), None);
+    /// ```
+    pub fn get(&self, path: &Path) -> Option<&SnailFish<T>> {
+        path.iter()
+            .try_fold(self, |snail, side| match (snail, side) {
+                (SnailFish::Num(_), _) => None,
+                (SnailFish::Pair(bx), Side::Left) => Some(&bx.0),
+                (SnailFish::Pair(bx), Side::Right) => Some(&bx.1),
+            })
+    }
+
+    /// Like [`SnailFish::get`], but for changing that part in place
+    pub fn get_mut(&mut self, path: &Path) -> Option<&mut SnailFish<T>> {
+        path.iter()
+            .try_fold(self, |snail, side| match (snail, side) {
+                (SnailFish::Num(_), _) => None,
+                (SnailFish::Pair(bx), Side::Left) => Some(&mut bx.0),
+                (SnailFish::Pair(bx), Side::Right) => Some(&mut bx.1),
+            })
+    }
+
+    /// Puts `with` where `path` leads, without reducing, and gives back what was there. `None`,
+    /// leaving the number as it was, if the path goes into a regular number.
+    /// ```rust
+    /// # use day18::*;
+    /// let mut s: SnailFish = "[[1,[2,3]],4]".parse().unwrap();
+    /// let old = s.replace(&"LR".parse().unwrap(), SnailFish::num(5));
+    /// assert_eq!(old.unwrap().to_string(), "[2,3]");
+    /// assert_eq!(s.to_string(), "[[1,5],4]");
+    /// ```
+    pub fn replace(&mut self, path: &Path, with: SnailFish<T>) -> Option<SnailFish<T>> {
+        Some(std::mem::replace(self.get_mut(path)?, with))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn paths_read_and_write() {
+        let path: Path = "LRR".parse().unwrap();
+        assert_eq!(path.to_string(), "LRR");
+        assert_eq!(path.len(), 3);
+        assert_eq!(path.parent(), Some("LR".parse().unwrap()));
+        assert_eq!(Path::root().parent(), None);
+        assert_eq!("".parse::<Path>(), Ok(Path::root()));
+        assert_eq!(
+            Path::root().child(Side::Right).child(Side::Left),
+            [Side::Right, Side::Left].into_iter().collect()
+        );
+        assert!("LX".parse::<Path>().is_err());
+    }
+
+    #[test]
+    fn reaches_every_part() {
+        let mut s: SnailFish = "[[[[[9,8],1],2],3],4]".parse().unwrap();
+        assert_eq!(s.get(&Path::root()), Some(&s.clone()));
+        let deep: Path = "LLLL".parse().unwrap();
+        assert_eq!(s.get(&deep).unwrap().to_string(), "[9,8]");
+        assert_eq!(s.get(&deep.child(Side::Left).child(Side::Left)), None);
+
+        if let Some(SnailFish::Num(n)) = s.get_mut(&"R".parse().unwrap()) {
+            *n += 1;
+        }
+        assert_eq!(s.to_string(), "[[[[[9,8],1],2],3],5]");
+        assert_eq!(s.replace(&"RL".parse().unwrap(), SnailFish::num(0)), None);
+        assert_eq!(
+            s.replace(&deep, SnailFish::num(0)),
+            Some("[9,8]".parse().unwrap())
+        );
+        assert_eq!(s.to_string(), "[[[[0,1],2],3],5]");
+    }
+}
@@ -0,0 +1,119 @@
+//! Drawing snailfish numbers as trees, to see which pair is where
+
+use std::fmt::{Display, Write};
+
+use crate::SnailFish;
+
+impl<T: Display> SnailFish<T> {
+    /// The number as a tree in plain text, one pair or regular number per line, with each
+    /// half marked `L` or `R` like a [`Path`](crate::Path). Printing it after every explode
+    /// and split shows each step of reducing:
+    /// ```rust
+    /// # use day18::*;
+    /// let mut s: SnailFish = "[[[[[9,8],1],2],3],4]".parse().unwrap();
+    /// s.explode();
+    /// assert_eq!(s.to_tree(), "\
+    /// pair
+    /// |- L pair
+    /// |  |- L pair
+    /// |  |  |- L pair
+    /// |  |  |  |- L 0
+    /// |  |  |  `- R 9
+    /// |  |  `- R 2
+    /// |  `- R 3
+    /// `- R 4
+    /// ");
+    /// ```
+    pub fn to_tree(&self) -> String {
+        fn draw<T: Display>(snailfish: &SnailFish<T>, indent: &str, out: &mut String) {
+            match snailfish {
+                SnailFish::Num(n) => {
+                    writeln!(out, "{}", n).expect("writing to a String can't fail");
+                }
+                SnailFish::Pair(bx) => {
+                    out.push_str("pair\n");
+                    out.push_str(indent);
+                    out.push_str("|- L ");
+                    draw(&bx.0, &format!("{}|  ", indent), out);
+                    out.push_str(indent);
+                    out.push_str("`- R ");
+                    draw(&bx.1, &format!("{}   ", indent), out);
+                }
+            }
+        }
+        let mut out = String::new();
+        draw(self, "", &mut out);
+        out
+    }
+
+    /// The number as a graph for Graphviz's `dot`, with pairs as points and each half's edge
+    /// labelled `L` or `R`
+    /// ```rust
+    /// # use day18::*;
+    /// let s: SnailFish = "[1,2]".parse().unwrap();
+    /// assert_eq!(s.to_dot(), "\
+    /// digraph snailfish {
+    ///     n0 [shape=point];
+    ///     n1 [shape=box, label=\"1\"];
+    ///     n0 -> n1 [label=\"L\"];
+    ///     n2 [shape=box, label=\"2\"];
+    ///     n0 -> n2 [label=\"R\"];
+    /// }
+    /// ");
+    /// ```
+    pub fn to_dot(&self) -> String {
+        /// Writes `snailfish` and everything in it, numbering nodes from `next`, and returns
+        /// its number
+        fn node<T: Display>(snailfish: &SnailFish<T>, next: &mut usize, out: &mut String) -> usize {
+            let id = *next;
+            *next += 1;
+            match snailfish {
+                SnailFish::Num(n) => {
+                    writeln!(out, "    n{} [shape=box, label=\"{}\"];", id, n)
+                        .expect("writing to a String can't fail");
+                }
+                SnailFish::Pair(bx) => {
+                    writeln!(out, "    n{} [shape=point];", id)
+                        .expect("writing to a String can't fail");
+                    for (half, side) in [(&bx.0, 'L'), (&bx.1, 'R')] {
+                        let child = node(half, next, out);
+                        writeln!(out, "    n{} -> n{} [label=\"{}\"];", id, child, side)
+                            .expect("writing to a String can't fail");
+                    }
+                }
+            }
+            id
+        }
+        let mut out = String::from("digraph snailfish {\n");
+        node(self, &mut 0, &mut out);
+        out.push_str("}\n");
+        out
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn draws_every_half() {
+        let s: SnailFish = "[[1,[2,3]],[4,5]]".parse().unwrap();
+        assert_eq!(
+            s.to_tree(),
+            "pair\n\
+             |- L pair\n\
+             |  |- L 1\n\
+             |  `- R pair\n\
+             |     |- L 2\n\
+             |     `- R 3\n\
+             `- R pair\n   \
+                |- L 4\n   \
+                `- R 5\n"
+        );
+        let dot = s.to_dot();
+        assert_eq!(dot.matches("shape=point").count(), 4);
+        assert_eq!(dot.matches("shape=box").count(), 5);
+        assert_eq!(dot.matches("->").count(), 8);
+        assert!(dot.contains("n3 [shape=point];\n    n4 [shape=box, label=\"2\"];"));
+    }
+}
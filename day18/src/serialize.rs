@@ -0,0 +1,157 @@
+//! Serde support, behind the `serde` feature
+//!
+//! A [`SnailFish`] serializes as nested arrays by default, so `[[1,2],3]` in JSON is the same
+//! number as it is in the puzzle. For the puzzle's own string instead, use [`puzzle_string`].
+
+use std::{fmt, marker::PhantomData, str::FromStr};
+
+use serde::{
+    de::{self, IntoDeserializer, SeqAccess, Visitor},
+    ser::SerializeTuple,
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+
+use crate::SnailFish;
+
+/// Regular numbers as themselves and pairs as two-element arrays
+/// ```rust
+/// # use day18::*;
+/// let s: SnailFish = "[[1,2],3]".parse().unwrap();
+/// assert_eq!(serde_json::to_string(&s).unwrap(), "[[1,2],3]");
+/// ```
+impl<T: Serialize> Serialize for SnailFish<T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            SnailFish::Num(n) => n.serialize(serializer),
+            SnailFish::Pair(ref bx) => {
+                let mut pair = serializer.serialize_tuple(2)?;
+                pair.serialize_element(&bx.0)?;
+                pair.serialize_element(&bx.1)?;
+                pair.end()
+            }
+        }
+    }
+}
+
+/// Reads back what [`Serialize`] writes, so it needs a self-describing format like JSON
+/// ```rust
+/// # use day18::*;
+/// let s: SnailFish = serde_json::from_str("[[1, 2], 3]").unwrap();
+/// assert_eq!(s.to_string(), "[[1,2],3]");
+/// assert!(serde_json::from_str::<SnailFish>("[1, 2, 3]").is_err());
+/// ```
+impl<'de, T: Deserialize<'de>> Deserialize<'de> for SnailFish<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(SnailFishVisitor(PhantomData))
+    }
+}
+
+struct SnailFishVisitor<T>(PhantomData<T>);
+
+impl<T> SnailFishVisitor<T> {
+    fn num<'de, E: de::Error>(value: impl IntoDeserializer<'de, E>) -> Result<SnailFish<T>, E>
+    where
+        T: Deserialize<'de>,
+    {
+        T::deserialize(value.into_deserializer()).map(SnailFish::Num)
+    }
+}
+
+impl<'de, T: Deserialize<'de>> Visitor<'de> for SnailFishVisitor<T> {
+    type Value = SnailFish<T>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "a regular number or a pair of snailfish numbers")
+    }
+
+    fn visit_u64<E: de::Error>(self, value: u64) -> Result<Self::Value, E> {
+        Self::num(value)
+    }
+
+    fn visit_i64<E: de::Error>(self, value: i64) -> Result<Self::Value, E> {
+        Self::num(value)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let left = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+        let right = seq
+            .next_element()?
+            .ok_or_else(|| de::Error::invalid_length(1, &self))?;
+        if seq.next_element::<de::IgnoredAny>()?.is_some() {
+            return Err(de::Error::invalid_length(3, &self));
+        }
+        Ok(SnailFish::pair(left, right))
+    }
+}
+
+/// Serializes a [`SnailFish`] as the puzzle writes it, `"[[1,2],3]"`, for use with
+/// `#[serde(with = "day18::serialize::puzzle_string")]`
+/// ```rust
+/// # use day18::*;
+/// #[derive(serde::Serialize, serde::Deserialize)]
+/// struct Homework {
+///     #[serde(with = "day18::serialize::puzzle_string")]
+///     answer: SnailFish,
+/// }
+///
+/// let json = r#"{"answer":"[[1,2],3]"}"#;
+/// let homework: Homework = serde_json::from_str(json).unwrap();
+/// assert_eq!(homework.answer.magnitude(), 27);
+/// assert_eq!(serde_json::to_string(&homework).unwrap(), json);
+/// ```
+pub mod puzzle_string {
+    use super::*;
+
+    pub fn serialize<T, S>(snailfish: &SnailFish<T>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: fmt::Display,
+        S: Serializer,
+    {
+        serializer.collect_str(snailfish)
+    }
+
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<SnailFish<T>, D::Error>
+    where
+        T: FromStr,
+        D: Deserializer<'de>,
+    {
+        let s = <std::borrow::Cow<str>>::deserialize(deserializer)?;
+        s.parse().map_err(de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_through_json() {
+        let s: SnailFish = "[[[[0,7],4],[[7,8],[6,0]]],[8,1]]".parse().unwrap();
+        let json = serde_json::to_string(&s).unwrap();
+        assert_eq!(json, s.to_string());
+        assert_eq!(serde_json::from_str::<SnailFish>(&json).unwrap(), s);
+    }
+
+    #[test]
+    fn rejects_what_isnt_a_snailfish_number() {
+        for json in [
+            "[1]",
+            "[1,2,3]",
+            "[-1,2]",
+            "[1.5,2]",
+            "\"[1,2]\"",
+            "[[1,2],[3]]",
+        ] {
+            assert!(serde_json::from_str::<SnailFish>(json).is_err(), "{}", json);
+        }
+        assert!(serde_json::from_str::<SnailFish<u8>>("[256,1]").is_err());
+    }
+
+    #[test]
+    fn puzzle_string_rejects_bad_strings() {
+        let mut deserializer = serde_json::Deserializer::from_str("\"[1,2\"");
+        assert!(puzzle_string::deserialize::<u32, _>(&mut deserializer).is_err());
+    }
+}
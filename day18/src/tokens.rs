@@ -0,0 +1,133 @@
+//! Reducing a snailfish number as a flat list of its regular numbers, each with how deep it
+//! is. A number's neighbours to the left and right are then just the tokens beside it, so
+//! exploding needs no pointers back up the tree.
+
+use crate::{Leaf, SnailFish};
+
+/// A regular number, inside `depth` pairs
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Token<T> {
+    pub depth: usize,
+    pub value: T,
+}
+
+/// Pairs this deep explode, which puts their numbers this deep plus one
+const EXPLODE_DEPTH: usize = 4;
+
+/// The regular numbers in `snailfish`, left to right
+pub(crate) fn flatten<T: Copy>(snailfish: &SnailFish<T>) -> Vec<Token<T>> {
+    snailfish
+        .iter_with_depth()
+        .map(|(depth, number)| match number {
+            SnailFish::Num(value) => Token {
+                depth,
+                value: *value,
+            },
+            SnailFish::Pair(_) => unreachable!("iter_with_depth only gives regular numbers"),
+        })
+        .collect()
+}
+
+/// Puts the tree back together, the reverse of [`flatten`]
+pub(crate) fn unflatten<T: Copy>(tokens: &[Token<T>]) -> SnailFish<T> {
+    fn build<T: Copy>(tokens: &[Token<T>], next: &mut usize, depth: usize) -> SnailFish<T> {
+        let token = tokens[*next];
+        if token.depth == depth {
+            *next += 1;
+            SnailFish::Num(token.value)
+        } else {
+            let left = build(tokens, next, depth + 1);
+            let right = build(tokens, next, depth + 1);
+            SnailFish::pair(left, right)
+        }
+    }
+    let mut next = 0;
+    let snailfish = build(tokens, &mut next, 0);
+    debug_assert_eq!(next, tokens.len(), "tokens left over from a whole tree");
+    snailfish
+}
+
+/// Explodes the leftmost pair of regular numbers nested inside four or more pairs, returning
+/// whether there was one.
+///
+/// The first two tokens beside each other at the same depth are always a pair: if the left
+/// one were a right child, its sibling to the left would hold a pair that comes first.
+pub(crate) fn explode<T: Leaf>(tokens: &mut Vec<Token<T>>) -> bool {
+    let Some(i) = tokens
+        .windows(2)
+        .position(|pair| pair[0].depth > EXPLODE_DEPTH && pair[0].depth == pair[1].depth)
+    else {
+        return false;
+    };
+    let (left, right) = (tokens[i], tokens[i + 1]);
+    if let Some(before) = i.checked_sub(1) {
+        tokens[before].value = tokens[before].value + left.value;
+    }
+    if let Some(after) = tokens.get_mut(i + 2) {
+        after.value = after.value + right.value;
+    }
+    tokens.splice(
+        i..i + 2,
+        [Token {
+            depth: left.depth - 1,
+            value: T::from(0),
+        }],
+    );
+    true
+}
+
+/// Splits the leftmost regular number of 10 or more, returning whether there was one
+pub(crate) fn split<T: Leaf>(tokens: &mut Vec<Token<T>>) -> bool {
+    let Some(i) = tokens.iter().position(|token| token.value >= T::from(10)) else {
+        return false;
+    };
+    let Token { depth, value } = tokens[i];
+    let half = |value| Token {
+        depth: depth + 1,
+        value,
+    };
+    let left = value / T::from(2);
+    tokens.splice(i..=i, [half(left), half(value - left)]);
+    true
+}
+
+/// Explodes and splits until neither does anything, exploding first whenever it can
+pub(crate) fn reduce<T: Leaf>(tokens: &mut Vec<Token<T>>) {
+    while explode(tokens) || split(tokens) {}
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn round_trips() {
+        let snailfish: SnailFish = "[[1,[2,3]],[[[4,5],6],7]]".parse().unwrap();
+        let tokens = flatten(&snailfish);
+        let depths: Vec<usize> = tokens.iter().map(|token| token.depth).collect();
+        assert_eq!(depths, vec![2, 3, 3, 4, 4, 3, 2]);
+        assert_eq!(unflatten(&tokens), snailfish);
+    }
+
+    #[test]
+    fn explodes_the_leftmost_deep_pair() {
+        // [[6,[5,[4,[3,2]]]],1] explodes [3,2], then there's nothing deep enough
+        let mut tokens = flatten::<u32>(&"[[6,[5,[4,[3,2]]]],1]".parse().unwrap());
+        assert!(explode(&mut tokens));
+        assert_eq!(
+            unflatten(&tokens),
+            "[[6,[5,[7,0]]],3]".parse::<SnailFish>().unwrap()
+        );
+        assert!(!explode(&mut tokens));
+    }
+
+    #[test]
+    fn explodes_pairs_of_numbers_beneath_deeper_pairs_first() {
+        let mut tokens = flatten::<u32>(&"[[[[[[1,2],3],4],5],6],7]".parse().unwrap());
+        assert!(explode(&mut tokens));
+        assert_eq!(
+            unflatten(&tokens),
+            "[[[[[0,5],4],5],6],7]".parse::<SnailFish>().unwrap()
+        );
+    }
+}
@@ -0,0 +1,395 @@
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    ops::{Add, Sub},
+    str::FromStr,
+};
+
+use aoc_common::{answer::Answer, error::AocError};
+
+pub const INPUT: &str = include_str!("input.txt");
+
+/// How many beacons two scanners must both see before we trust that they overlap
+const OVERLAP: usize = 12;
+
+/// A position, or the offset between two
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Vec3 {
+    pub x: i32,
+    pub y: i32,
+    pub z: i32,
+}
+
+impl Vec3 {
+    pub fn new(x: i32, y: i32, z: i32) -> Self {
+        Self { x, y, z }
+    }
+
+    fn axes(self) -> [i32; 3] {
+        [self.x, self.y, self.z]
+    }
+
+    fn from_axes([x, y, z]: [i32; 3]) -> Self {
+        Self { x, y, z }
+    }
+
+    pub fn manhattan(self, other: Self) -> u32 {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y) + self.z.abs_diff(other.z)
+    }
+
+    /// Squared straight-line distance, which doesn't change however a scanner is turned
+    fn squared_distance(self, other: Self) -> i64 {
+        let d = self - other;
+        d.axes().iter().map(|&n| i64::from(n) * i64::from(n)).sum()
+    }
+}
+
+impl Add for Vec3 {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self::new(self.x + rhs.x, self.y + rhs.y, self.z + rhs.z)
+    }
+}
+
+impl Sub for Vec3 {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self::new(self.x - rhs.x, self.y - rhs.y, self.z - rhs.z)
+    }
+}
+
+/// One of the 24 ways a scanner can be facing: the new x, y and z are old axes `axes[i]`,
+/// negated where `signs[i]` is -1
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rotation {
+    axes: [usize; 3],
+    signs: [i32; 3],
+}
+
+impl Rotation {
+    pub const IDENTITY: Self = Self {
+        axes: [0, 1, 2],
+        signs: [1, 1, 1],
+    };
+
+    /// Every rotation, starting with [`Rotation::IDENTITY`]. Mirror images (the other 24 of
+    /// the 48 ways to shuffle and flip axes) are left out.
+    pub fn all() -> Vec<Self> {
+        const PERMUTATIONS: [[usize; 3]; 6] = [
+            [0, 1, 2],
+            [0, 2, 1],
+            [1, 0, 2],
+            [1, 2, 0],
+            [2, 0, 1],
+            [2, 1, 0],
+        ];
+        let mut rotations = Vec::with_capacity(24);
+        for axes in PERMUTATIONS {
+            for flips in 0..8 {
+                let signs = [0, 1, 2].map(|bit| if flips & (1 << bit) == 0 { 1 } else { -1 });
+                let rotation = Self { axes, signs };
+                if rotation.determinant() == 1 {
+                    rotations.push(rotation);
+                }
+            }
+        }
+        rotations
+    }
+
+    /// +1 for rotations, -1 for reflections
+    fn determinant(&self) -> i32 {
+        let inversions = (0..3)
+            .flat_map(|i| (i + 1..3).map(move |j| (i, j)))
+            .filter(|&(i, j)| self.axes[i] > self.axes[j])
+            .count();
+        let parity = if inversions % 2 == 0 { 1 } else { -1 };
+        parity * self.signs.iter().product::<i32>()
+    }
+
+    pub fn apply(&self, v: Vec3) -> Vec3 {
+        let axes = v.axes();
+        Vec3::from_axes([0, 1, 2].map(|i| axes[self.axes[i]] * self.signs[i]))
+    }
+}
+
+/// The beacons one scanner can see, relative to itself and facing whichever way it faces
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Scanner {
+    pub id: usize,
+    pub beacons: Vec<Vec3>,
+}
+
+impl Scanner {
+    /// Distances between every pair of beacons, which are the same from any angle. Scanners
+    /// sharing 12 beacons share at least 66 of these.
+    fn fingerprint(&self) -> HashSet<i64> {
+        self.beacons
+            .iter()
+            .enumerate()
+            .flat_map(|(idx, a)| {
+                self.beacons[idx + 1..]
+                    .iter()
+                    .map(|b| a.squared_distance(*b))
+            })
+            .collect()
+    }
+}
+
+/// Finds how to turn and move `scanner` so at least 12 of its beacons land on `known` ones
+fn align(known: &[Vec3], scanner: &Scanner, rotations: &[Rotation]) -> Option<(Rotation, Vec3)> {
+    for rotation in rotations {
+        let mut offsets: HashMap<Vec3, usize> = HashMap::new();
+        for beacon in scanner.beacons.iter().map(|&b| rotation.apply(b)) {
+            for &target in known {
+                let count = offsets.entry(target - beacon).or_default();
+                *count += 1;
+                if *count == OVERLAP {
+                    return Some((*rotation, target - beacon));
+                }
+            }
+        }
+    }
+    None
+}
+
+/// Every beacon and scanner, relative to scanner 0
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BeaconMap {
+    pub beacons: HashSet<Vec3>,
+    /// Where each scanner is, in input order
+    pub scanners: Vec<Vec3>,
+}
+
+impl BeaconMap {
+    /// The furthest apart any two scanners are
+    pub fn largest_distance(&self) -> u32 {
+        self.scanners
+            .iter()
+            .flat_map(|a| self.scanners.iter().map(|b| a.manhattan(*b)))
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+/// Pieces the scanners together, starting from the first. `None` if some scanner doesn't
+/// overlap any of the others.
+pub fn assemble(scanners: &[Scanner]) -> Option<BeaconMap> {
+    let rotations = Rotation::all();
+    let fingerprints: Vec<HashSet<i64>> = scanners.iter().map(Scanner::fingerprint).collect();
+    let shared_pairs = OVERLAP * (OVERLAP - 1) / 2;
+
+    // Beacons of each scanner that's been placed, relative to scanner 0
+    let mut placed: Vec<Option<Vec<Vec3>>> = vec![None; scanners.len()];
+    let mut positions = vec![Vec3::default(); scanners.len()];
+    placed[0] = Some(scanners.first()?.beacons.clone());
+    let mut frontier = VecDeque::from([0]);
+    while let Some(anchor) = frontier.pop_front() {
+        for idx in 0..scanners.len() {
+            if placed[idx].is_some()
+                || fingerprints[anchor]
+                    .intersection(&fingerprints[idx])
+                    .count()
+                    < shared_pairs
+            {
+                continue;
+            }
+            let known = placed[anchor].as_deref().expect("anchors are placed");
+            if let Some((rotation, offset)) = align(known, &scanners[idx], &rotations) {
+                let beacons = scanners[idx]
+                    .beacons
+                    .iter()
+                    .map(|&beacon| rotation.apply(beacon) + offset)
+                    .collect();
+                placed[idx] = Some(beacons);
+                positions[idx] = offset;
+                frontier.push_back(idx);
+            }
+        }
+    }
+
+    let beacons = placed
+        .into_iter()
+        .collect::<Option<Vec<_>>>()?
+        .into_iter()
+        .flatten()
+        .collect();
+    Some(BeaconMap {
+        beacons,
+        scanners: positions,
+    })
+}
+
+fn parse_beacon(line_no: usize, line: &str) -> Result<Vec3, AocError> {
+    let coords: Vec<&str> = line.split(',').collect();
+    if coords.len() != 3 {
+        return Err(AocError::parse(
+            line_no,
+            1,
+            format!("expected x,y,z, found {} coordinates", coords.len()),
+        ));
+    }
+    let mut axes = [0; 3];
+    for (axis, coord) in axes.iter_mut().zip(&coords) {
+        *axis = coord.trim().parse().map_err(|_| {
+            AocError::at_token(line_no, line, coord, format!("{:?} is not a number", coord))
+        })?;
+    }
+    Ok(Vec3::from_axes(axes))
+}
+
+#[derive(Debug, Clone)]
+pub struct Input {
+    scanners: Vec<Scanner>,
+}
+
+impl FromStr for Input {
+    type Err = AocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut scanners: Vec<Scanner> = Vec::new();
+        for (idx, line) in s.lines().enumerate() {
+            let line_no = idx + 1;
+            if line.trim().is_empty() {
+                continue;
+            }
+            if let Some(header) = line.strip_prefix("--- scanner ") {
+                let id = header.strip_suffix(" ---").unwrap_or(header);
+                let id = id.parse().map_err(|_| {
+                    AocError::at_token(line_no, line, id, "expected a scanner number")
+                })?;
+                scanners.push(Scanner {
+                    id,
+                    beacons: Vec::new(),
+                });
+                continue;
+            }
+            let scanner = scanners.last_mut().ok_or_else(|| {
+                AocError::parse(line_no, 1, "expected a `--- scanner N ---` header")
+            })?;
+            scanner.beacons.push(parse_beacon(line_no, line)?);
+        }
+        if scanners.is_empty() {
+            return Err(AocError::UnexpectedEnd(String::from("a scanner report")));
+        }
+        Ok(Self { scanners })
+    }
+}
+
+pub fn solve_part1(input: Input) -> u64 {
+    let map = assemble(&input.scanners).expect("Scanners must all overlap");
+    map.beacons.len() as u64
+}
+
+pub fn solve_part2(input: Input) -> u64 {
+    let map = assemble(&input.scanners).expect("Scanners must all overlap");
+    map.largest_distance() as u64
+}
+
+fn parse_map(input: &str) -> Result<BeaconMap, String> {
+    let input = input.parse::<Input>().map_err(|e| e.to_string())?;
+    assemble(&input.scanners)
+        .ok_or_else(|| String::from("Some scanners don't overlap any of the others"))
+}
+
+/// Beacons in the whole map
+pub fn part1(input: &str) -> Result<Answer, String> {
+    Ok(parse_map(input)?.beacons.len().into())
+}
+
+/// Largest Manhattan distance between any two scanners
+pub fn part2(input: &str) -> Result<Answer, String> {
+    Ok(parse_map(input)?.largest_distance().into())
+}
+
+aoc_common::register_day!(19);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = include_str!("test_input.txt");
+
+    #[test]
+    fn solve_part1() {
+        let input = INPUT.parse::<Input>().expect("Input must parse");
+        let part1 = super::solve_part1(input);
+        let expected = 86;
+        assert_eq!(part1, expected);
+    }
+
+    #[test]
+    fn solve_part2() {
+        let input = INPUT.parse::<Input>().expect("Input must parse");
+        let part2 = super::solve_part2(input);
+        let expected = 3566;
+        assert_eq!(part2, expected);
+    }
+
+    #[test]
+    fn rotations() {
+        let rotations = Rotation::all();
+        assert_eq!(rotations.len(), 24);
+        assert_eq!(rotations[0], Rotation::IDENTITY);
+        let v = Vec3::new(1, 2, 3);
+        let turned: HashSet<Vec3> = rotations.iter().map(|r| r.apply(v)).collect();
+        assert_eq!(turned.len(), 24, "every rotation faces a different way");
+        // Rotations keep x, y and z right-handed: x cross y is still z
+        for r in &rotations {
+            let (x, y, z) = (
+                r.apply(Vec3::new(1, 0, 0)),
+                r.apply(Vec3::new(0, 1, 0)),
+                r.apply(Vec3::new(0, 0, 1)),
+            );
+            let cross = Vec3::new(
+                x.y * y.z - x.z * y.y,
+                x.z * y.x - x.x * y.z,
+                x.x * y.y - x.y * y.x,
+            );
+            assert_eq!(cross, z, "{:?} is a reflection", r);
+        }
+    }
+
+    #[test]
+    fn align_recovers_placement() {
+        let input = INPUT.parse::<Input>().expect("Input must parse");
+        let scanner = &input.scanners[0];
+        let rotation = Rotation::all()[17];
+        let offset = Vec3::new(68, -1246, -43);
+        let known: Vec<Vec3> = scanner
+            .beacons
+            .iter()
+            .map(|&b| rotation.apply(b) + offset)
+            .collect();
+        assert_eq!(
+            align(&known, scanner, &Rotation::all()),
+            Some((rotation, offset))
+        );
+    }
+
+    #[test]
+    fn scanners_that_overlap_nothing() {
+        let mut input = INPUT.parse::<Input>().expect("Input must parse");
+        input.scanners[1].beacons.truncate(3);
+        assert_eq!(assemble(&input.scanners), None);
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(
+            "1,2,3".parse::<Input>().unwrap_err(),
+            AocError::parse(1, 1, "expected a `--- scanner N ---` header")
+        );
+        assert_eq!(
+            "--- scanner 0 ---\n1,2,3\n4,x,6"
+                .parse::<Input>()
+                .unwrap_err(),
+            AocError::parse(3, 3, "\"x\" is not a number")
+        );
+        assert_eq!(
+            "--- scanner 0 ---\n1,2".parse::<Input>().unwrap_err(),
+            AocError::parse(2, 1, "expected x,y,z, found 2 coordinates")
+        );
+        assert!("".parse::<Input>().is_err());
+    }
+}
@@ -0,0 +1,494 @@
+use aoc_common::{
+    answer::Answer,
+    error::{column_of, AocError},
+};
+
+use std::{fmt::Display, num::ParseIntError, str::FromStr};
+
+pub const INPUT: &str = include_str!("input.txt");
+
+/// Horizontal position * depth when `up` and `down` change depth directly
+pub fn part1(input: &str) -> Result<Answer, String> {
+    let commands = parse_commands(input).map_err(|err| err.to_string())?;
+    answer(run(commands, Interpreter::Direct))
+}
+
+/// Horizontal position * depth when `up` and `down` change aim
+pub fn part2(input: &str) -> Result<Answer, String> {
+    let commands = parse_commands(input).map_err(|err| err.to_string())?;
+    answer(run(commands, Interpreter::Aimed))
+}
+
+aoc_common::register_day!(2);
+
+/// Answers are positive unless the input flies the submarine out of the water
+fn answer(position: Position) -> Result<Answer, String> {
+    let value = position.get_value();
+    u64::try_from(value)
+        .map(Answer::Num)
+        .map_err(|_| format!("Horizontal position * depth is negative ({})", value))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Command {
+    Forward(u32),
+    Down(u32),
+    Up(u32),
+}
+
+const COMMANDS: &str = "`forward`, `down`, or `up`";
+
+/// The ways a single command can fail to parse
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseCommandErrorKind {
+    MissingCommand,
+    MissingValue(String),
+    UnknownCommand(String),
+    InvalidValue(String, ParseIntError),
+    TrailingToken(String),
+}
+impl Display for ParseCommandErrorKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MissingCommand => write!(f, "expected {}, found an empty line", COMMANDS),
+            Self::MissingValue(command) => {
+                write!(f, "expected a distance after `{}`, found nothing", command)
+            }
+            Self::UnknownCommand(token) => {
+                write!(f, "expected {}, found `{}`", COMMANDS, token)
+            }
+            Self::InvalidValue(token, err) => write!(
+                f,
+                "expected a non-negative integer distance, found `{}` ({})",
+                token, err
+            ),
+            Self::TrailingToken(token) => {
+                write!(f, "expected end of line, found `{}`", token)
+            }
+        }
+    }
+}
+impl std::error::Error for ParseCommandErrorKind {}
+
+impl FromStr for Command {
+    type Err = ParseCommandErrorKind;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.split_whitespace();
+        let command = parts.next().ok_or(ParseCommandErrorKind::MissingCommand)?;
+        let build = match command {
+            "forward" => Command::Forward,
+            "down" => Command::Down,
+            "up" => Command::Up,
+            _ => return Err(ParseCommandErrorKind::UnknownCommand(command.to_string())),
+        };
+        let value = parts
+            .next()
+            .ok_or_else(|| ParseCommandErrorKind::MissingValue(command.to_string()))?;
+        if let Some(token) = parts.next() {
+            return Err(ParseCommandErrorKind::TrailingToken(token.to_string()));
+        }
+        let value = value
+            .parse()
+            .map_err(|err| ParseCommandErrorKind::InvalidValue(value.to_string(), err))?;
+        Ok(build(value))
+    }
+}
+
+impl ParseCommandErrorKind {
+    /// The 1-indexed column of `line` this error is about
+    fn column_in(&self, line: &str) -> usize {
+        let token = match self {
+            Self::MissingCommand | Self::UnknownCommand(_) => 0,
+            Self::MissingValue(_) | Self::InvalidValue(_, _) => 1,
+            Self::TrailingToken(_) => 2,
+        };
+        match line.split_whitespace().nth(token) {
+            Some(token) => column_of(line, token),
+            // Whatever's missing should have come after the end of the line
+            None => line.chars().count() + 1,
+        }
+    }
+}
+
+/// Parses one command per line, stopping at the first malformed line
+pub fn parse_commands(input: &str) -> Result<Vec<Command>, AocError> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(idx, line)| {
+            line.parse().map_err(|kind: ParseCommandErrorKind| {
+                AocError::parse(idx + 1, kind.column_in(line), kind.to_string())
+            })
+        })
+        .collect()
+}
+
+#[derive(Default, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Position {
+    pub horizontal: i64,
+    pub depth: i64,
+    pub aim: i64,
+}
+
+impl Position {
+    pub fn act(self, command: Command) -> Self {
+        match command {
+            Command::Forward(x) => Self {
+                horizontal: self.horizontal + x as i64,
+                ..self
+            },
+            Command::Down(y) => Self {
+                depth: self.depth + y as i64,
+                ..self
+            },
+            Command::Up(y) => Self {
+                depth: self.depth - y as i64,
+                ..self
+            },
+        }
+    }
+    pub fn act_v2(self, command: Command) -> Self {
+        match command {
+            Command::Forward(x) => Self {
+                horizontal: self.horizontal + x as i64,
+                depth: self.depth + x as i64 * self.aim,
+                ..self
+            },
+            Command::Down(y) => Self {
+                aim: self.aim + y as i64,
+                ..self
+            },
+            Command::Up(y) => Self {
+                aim: self.aim - y as i64,
+                ..self
+            },
+        }
+    }
+    pub fn get_value(&self) -> i64 {
+        self.horizontal * self.depth
+    }
+}
+
+/// Which reading of the submarine manual to steer by
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Interpreter {
+    /// `up` and `down` change depth directly (part 1)
+    Direct,
+    /// `up` and `down` change aim, and `forward` dives along it (part 2)
+    Aimed,
+}
+
+impl Interpreter {
+    pub fn step(self, position: Position, command: Command) -> Position {
+        match self {
+            Self::Direct => position.act(command),
+            Self::Aimed => position.act_v2(command),
+        }
+    }
+}
+
+/// Runs every command from the surface and returns where the submarine ends up
+pub fn run<I>(commands: I, interpreter: Interpreter) -> Position
+where
+    I: IntoIterator<Item = Command>,
+{
+    commands
+        .into_iter()
+        .fold(Position::default(), |position, command| {
+            interpreter.step(position, command)
+        })
+}
+
+/// Shrinks a command stream without changing where it ends up under either [`Interpreter`].
+///
+/// Runs of consecutive `forward`s are merged, and runs of consecutive `up`/`down`s are
+/// collapsed into their net movement (cancelling entirely if they balance out). Vertical and
+/// horizontal runs are never merged across each other, since the aimed interpreter depends on
+/// their order.
+pub fn optimize(commands: Vec<Command>) -> Vec<Command> {
+    let mut optimized = Vec::with_capacity(commands.len());
+    let mut forward: u64 = 0;
+    let mut vertical: i64 = 0;
+    for command in commands {
+        match command {
+            Command::Forward(x) => {
+                flush_vertical(&mut optimized, &mut vertical);
+                forward += x as u64;
+            }
+            Command::Down(y) => {
+                flush_forward(&mut optimized, &mut forward);
+                vertical += y as i64;
+            }
+            Command::Up(y) => {
+                flush_forward(&mut optimized, &mut forward);
+                vertical -= y as i64;
+            }
+        }
+    }
+    flush_forward(&mut optimized, &mut forward);
+    flush_vertical(&mut optimized, &mut vertical);
+    optimized
+}
+
+fn flush_forward(commands: &mut Vec<Command>, forward: &mut u64) {
+    while *forward > 0 {
+        let step = (*forward).min(u32::MAX as u64);
+        commands.push(Command::Forward(step as u32));
+        *forward -= step;
+    }
+}
+
+fn flush_vertical(commands: &mut Vec<Command>, vertical: &mut i64) {
+    while *vertical != 0 {
+        let step = vertical.unsigned_abs().min(u32::MAX as u64);
+        if *vertical > 0 {
+            commands.push(Command::Down(step as u32));
+            *vertical -= step as i64;
+        } else {
+            commands.push(Command::Up(step as u32));
+            *vertical += step as i64;
+        }
+    }
+}
+
+/// A run of commands that remembers where the submarine was after each one
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Voyage {
+    interpreter: Interpreter,
+    history: Vec<Position>,
+}
+
+impl Voyage {
+    pub fn new(interpreter: Interpreter) -> Self {
+        Self {
+            interpreter,
+            history: Vec::new(),
+        }
+    }
+
+    /// Records a full voyage from the surface
+    pub fn record<I>(commands: I, interpreter: Interpreter) -> Self
+    where
+        I: IntoIterator<Item = Command>,
+    {
+        let mut voyage = Self::new(interpreter);
+        voyage.extend(commands);
+        voyage
+    }
+
+    pub fn push(&mut self, command: Command) -> Position {
+        let next = self.interpreter.step(self.position(), command);
+        self.history.push(next);
+        next
+    }
+
+    /// Positions after each command, in order. Does not include the starting position.
+    pub fn history(&self) -> &[Position] {
+        &self.history
+    }
+
+    /// Position after `step` commands, where step 0 is the surface
+    pub fn position_at(&self, step: usize) -> Option<Position> {
+        match step {
+            0 => Some(Position::default()),
+            n => self.history.get(n - 1).copied(),
+        }
+    }
+
+    /// Current position, i.e. after the last recorded command
+    pub fn position(&self) -> Position {
+        self.history.last().copied().unwrap_or_default()
+    }
+}
+
+impl Extend<Command> for Voyage {
+    fn extend<T: IntoIterator<Item = Command>>(&mut self, iter: T) {
+        for command in iter {
+            self.push(command);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const INPUT: &str = "\
+forward 5
+down 5
+forward 8
+up 3
+down 8
+forward 2";
+
+    #[test]
+    fn test_calculate() {
+        let want = Position {
+            horizontal: 3,
+            depth: 3,
+            ..Default::default()
+        };
+        let got = [
+            Command::Forward(3),
+            Command::Down(2),
+            Command::Down(2),
+            Command::Up(1),
+        ]
+        .into_iter()
+        .fold(Default::default(), Position::act);
+        assert_eq!(got, want);
+    }
+
+    #[test]
+    fn test_position_get_value() {
+        assert_eq!(
+            Position {
+                horizontal: 4,
+                depth: 3,
+                ..Default::default()
+            }
+            .get_value(),
+            12
+        );
+    }
+
+    #[test]
+    fn test_day1() {
+        let commands = parse_commands(INPUT).unwrap();
+        assert_eq!(run(commands, Interpreter::Direct).get_value(), 150)
+    }
+
+    #[test]
+    fn test_day2() {
+        let commands = parse_commands(INPUT).unwrap();
+        assert_eq!(run(commands, Interpreter::Aimed).get_value(), 900)
+    }
+
+    #[test]
+    fn test_above_surface() {
+        let commands = [Command::Up(2), Command::Forward(3), Command::Down(1)];
+        assert_eq!(
+            run(commands, Interpreter::Direct),
+            Position {
+                horizontal: 3,
+                depth: -1,
+                aim: 0
+            }
+        );
+        assert_eq!(
+            run(commands, Interpreter::Aimed),
+            Position {
+                horizontal: 3,
+                depth: -6,
+                aim: -1
+            }
+        );
+    }
+
+    #[test]
+    fn test_voyage_history() {
+        let commands = parse_commands(INPUT).unwrap();
+        let voyage = Voyage::record(commands.iter().copied(), Interpreter::Aimed);
+        assert_eq!(voyage.history().len(), commands.len());
+        assert_eq!(voyage.position_at(0), Some(Position::default()));
+        assert_eq!(
+            voyage.position_at(3),
+            Some(Position {
+                horizontal: 13,
+                depth: 40,
+                aim: 5
+            })
+        );
+        assert_eq!(voyage.position_at(commands.len() + 1), None);
+        assert_eq!(voyage.position(), run(commands, Interpreter::Aimed));
+    }
+
+    #[test]
+    fn test_optimize() {
+        use Command::*;
+        let commands = vec![
+            Down(2),
+            Down(3),
+            Forward(1),
+            Forward(4),
+            Up(5),
+            Down(5),
+            Up(1),
+        ];
+        assert_eq!(optimize(commands), vec![Down(5), Forward(5), Up(1)]);
+        assert_eq!(optimize(vec![Up(3), Down(3), Forward(0)]), vec![]);
+        assert_eq!(
+            optimize(vec![Forward(u32::MAX), Forward(1)]),
+            vec![Forward(u32::MAX), Forward(1)]
+        );
+    }
+
+    #[test]
+    fn test_optimize_preserves_position() {
+        let inputs = [INPUT, include_str!("input.txt")];
+        for input in inputs {
+            let commands = parse_commands(input).unwrap();
+            let optimized = optimize(commands.clone());
+            assert!(optimized.len() <= commands.len());
+            for interpreter in [Interpreter::Direct, Interpreter::Aimed] {
+                assert_eq!(
+                    run(optimized.iter().copied(), interpreter),
+                    run(commands.iter().copied(), interpreter)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        use ParseCommandErrorKind::*;
+        let cases = [
+            ("forwart 5", UnknownCommand("forwart".into())),
+            ("down", MissingValue("down".into())),
+            ("", MissingCommand),
+            ("up 1 2", TrailingToken("2".into())),
+        ];
+        for (input, kind) in cases {
+            assert_eq!(input.parse::<Command>(), Err(kind));
+        }
+        assert!(matches!(
+            "down x".parse::<Command>(),
+            Err(InvalidValue(_, _))
+        ));
+
+        let cases = [
+            ("forward 5\nforwart 5", 2, 1),
+            ("down", 1, 5),
+            ("up 1\n\n", 2, 1),
+            ("up 1  2", 1, 7),
+            ("down x", 1, 6),
+        ];
+        for (input, line, column) in cases {
+            assert!(
+                matches!(
+                    parse_commands(input),
+                    Err(AocError::Parse { line: l, column: c, .. }) if (l, c) == (line, column)
+                ),
+                "{:?} should fail at line {}, column {}",
+                input,
+                line,
+                column
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_error_messages() {
+        let err = parse_commands("forward 5\nforwart 5").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 2, column 1: expected `forward`, `down`, or `up`, found `forwart`"
+        );
+        let err = parse_commands("up").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "line 1, column 3: expected a distance after `up`, found nothing"
+        );
+    }
+}
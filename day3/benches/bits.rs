@@ -0,0 +1,26 @@
+//! Compare the scalar and `simd` builds with
+//!
+//! ```text
+//! cargo bench -p day3 -- --save-baseline scalar
+//! cargo bench -p day3 --features simd -- --baseline scalar
+//! ```
+
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use day3::{calculate, CalculationType, INPUT};
+
+fn bits(c: &mut Criterion) {
+    // The bundled input over and over, so counting outweighs the setup. The ratings would end
+    // up with duplicate candidates, so only the rates are measured on it.
+    let repeated = [INPUT.trim_end()].repeat(64).join("\n");
+    c.bench_function("day3 gamma x64", |b| {
+        b.iter(|| calculate(black_box(&repeated), CalculationType::Gamma))
+    });
+    c.bench_function("day3 oxygen", |b| {
+        b.iter(|| calculate(black_box(INPUT), CalculationType::Oxygen))
+    });
+}
+
+criterion_group!(benches, bits);
+criterion_main!(benches);
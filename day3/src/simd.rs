@@ -0,0 +1,34 @@
+//! Counts bits for eight numbers at a time
+
+use wide::u32x8;
+
+/// How many of `bitses` have a one in each bit position, least significant first. Only whole
+/// groups of eight numbers are counted; the ones left over are returned to count separately.
+pub(crate) fn count_ones(bitses: &[u16]) -> ([usize; 16], &[u16]) {
+    let chunks = bitses.chunks_exact(8);
+    let rest = chunks.remainder();
+    let mut ones = [u32x8::splat(0); 16];
+    for chunk in chunks {
+        let bits = u32x8::new(std::array::from_fn(|i| u32::from(chunk[i])));
+        for (shift, count) in (0u32..).zip(ones.iter_mut()) {
+            *count += (bits >> shift) & u32x8::splat(1);
+        }
+    }
+    let ones = ones.map(|count| count.to_array().iter().sum::<u32>() as usize);
+    (ones, rest)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_count_ones() {
+        let bitses: Vec<u16> = (0..20).collect();
+        let (ones, rest) = count_ones(&bitses);
+        assert_eq!(rest, &[16, 17, 18, 19]);
+        // 0 to 15, so each of the low four bits is set in half of them
+        assert_eq!(ones[..5], [8, 8, 8, 8, 0]);
+        assert!(ones[5..].iter().all(|&n| n == 0));
+    }
+}
@@ -0,0 +1,36 @@
+//! Strategies for vent lines
+
+use aoc_common::strategies;
+use aoc_parse::finish;
+use proptest::prelude::*;
+
+use crate::{vent_line, Input, Point};
+
+pub fn point() -> impl Strategy<Value = Point> {
+    strategies::point(1000).prop_map(|(x, y)| Point {
+        x: x as i32,
+        y: y as i32,
+    })
+}
+
+/// A line's ends, written like the input
+pub fn segment() -> impl Strategy<Value = ((Point, Point), String)> {
+    (point(), point()).prop_map(|(start, end)| {
+        let text = format!("{},{} -> {},{}", start.x, start.y, end.x, end.y);
+        ((start, end), text)
+    })
+}
+
+proptest! {
+    #![proptest_config(strategies::config())]
+
+    #[test]
+    fn segment_round_trips((segment, text) in segment()) {
+        prop_assert_eq!(finish(&text, vent_line), Ok(segment));
+    }
+
+    #[test]
+    fn bad_lines_dont_panic(line in "[0-9, ->]{0,12}") {
+        let _ = Input::parse(&line);
+    }
+}
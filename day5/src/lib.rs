@@ -0,0 +1,262 @@
+use aoc_common::{answer::Answer, error::AocError};
+use aoc_parse::{
+    coordinate, expected, finish, lines, literal,
+    nom::{combinator::cut, sequence::separated_pair, Parser},
+    IResult,
+};
+use std::{cmp::Ordering, collections::HashMap, fmt::Display};
+
+#[cfg(test)]
+mod generators;
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+impl Point {
+    fn step_towards(&self, other: &Point) -> Point {
+        let x = match self.x.cmp(&other.x) {
+            Ordering::Less => self.x + 1,
+            Ordering::Equal => self.x,
+            Ordering::Greater => self.x - 1,
+        };
+        let y = match self.y.cmp(&other.y) {
+            Ordering::Less => self.y + 1,
+            Ordering::Equal => self.y,
+            Ordering::Greater => self.y - 1,
+        };
+        Self { x, y }
+    }
+}
+
+impl Display for Point {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&format!("({}, {})", self.x, self.y))
+    }
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum DiagonalHandling {
+    Ignore,
+    Include,
+}
+
+#[derive(Debug, Eq, PartialEq)]
+struct Line(Vec<Point>);
+
+impl Line {
+    #[allow(unused)]
+    fn between(start: Point, end: Point, handling: DiagonalHandling) -> Self {
+        let mut result: Vec<Point> = Vec::new();
+
+        // Check for valid input, rejecting non-straight lines and non-45 degrees
+        match handling {
+            // Early out if the line isn't straight
+            DiagonalHandling::Ignore => {
+                if start.x != end.x && start.y != end.y {
+                    return Self(Vec::new());
+                }
+            }
+
+            // Early out if the line isn't straight or if the diagonal isn't
+            // 45 degrees
+            DiagonalHandling::Include => {
+                if (start.x != end.x && start.y != end.y)
+                    && (start.x.abs_diff(end.x) != start.y.abs_diff(end.y))
+                {
+                    return Self(Vec::new());
+                }
+            }
+        }
+
+        // Make sure that we're always going from the smallest to the largest
+        let mut point = start;
+        result.push(point);
+        loop {
+            point = point.step_towards(&end);
+            result.push(point);
+            if point == end {
+                break;
+            }
+        }
+        Self(result)
+    }
+}
+
+impl IntoIterator for Line {
+    type Item = Point;
+
+    type IntoIter = std::vec::IntoIter<Point>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+fn point(input: &str) -> IResult<'_, Point> {
+    coordinate.map(|(x, y)| Point { x, y }).parse(input)
+}
+
+/// Reads a line like `0,9 -> 5,9` into its two ends
+fn vent_line(input: &str) -> IResult<'_, (Point, Point)> {
+    separated_pair(point, literal(" -> "), cut(point))(input)
+}
+
+#[derive(Debug, Eq, PartialEq)]
+pub struct Input {
+    map: HashMap<Point, u32>,
+}
+
+impl Input {
+    pub fn parse_with_handling(input: &str, handling: DiagonalHandling) -> Result<Self, AocError> {
+        let mut map: HashMap<Point, u32> = HashMap::new();
+        for (start, stop) in finish(input, lines(expected("a vent line", vent_line)))? {
+            for point in Line::between(start, stop, handling) {
+                map.entry(point)
+                    .and_modify(|count| *count += 1)
+                    .or_insert(1);
+            }
+        }
+        Ok(Input { map })
+    }
+    pub fn parse(input: &str) -> Result<Self, AocError> {
+        Self::parse_with_handling(input, DiagonalHandling::Ignore)
+    }
+}
+
+pub fn solve_part1(input: Input) -> u32 {
+    input.map.values().fold(
+        0,
+        |acc, point_count| if *point_count > 1 { acc + 1 } else { acc },
+    )
+}
+
+pub fn solve_part2(input: Input) -> u32 {
+    input.map.values().fold(
+        0,
+        |acc, point_count| if *point_count > 1 { acc + 1 } else { acc },
+    )
+}
+
+pub const INPUT: &str = include_str!("input.txt");
+
+/// Points where at least two horizontal or vertical lines overlap
+pub fn part1(input: &str) -> Result<Answer, String> {
+    let input = Input::parse(input).map_err(|e| e.to_string())?;
+    Ok(solve_part1(input).into())
+}
+
+/// Points where at least two lines overlap, diagonals included
+pub fn part2(input: &str) -> Result<Answer, String> {
+    let input =
+        Input::parse_with_handling(input, DiagonalHandling::Include).map_err(|e| e.to_string())?;
+    Ok(solve_part2(input).into())
+}
+
+aoc_common::register_day!(5);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const INPUT: &str = include_str!("test_input.txt");
+
+    #[test]
+    fn test_solve_part1() {
+        let got = solve_part1(Input::parse(INPUT).expect("Could not parse input"));
+        let expect = 5;
+        assert_eq!(got, expect);
+    }
+
+    #[test]
+    fn test_solve_part2() {
+        let got = solve_part2(
+            Input::parse_with_handling(INPUT, DiagonalHandling::Include)
+                .expect("Could not parse intput"),
+        );
+        let expect = 12;
+        assert_eq!(got, expect);
+    }
+
+    #[test]
+    fn parse_input() {
+        let input: &'static str = "\
+1,1 -> 1,3
+1,1 -> 3,1";
+
+        let got = Input::parse(input).expect("Cannot parse input");
+        let expect = Input {
+            map: HashMap::<Point, u32>::from_iter([
+                (Point { x: 1, y: 1 }, 2),
+                (Point { x: 1, y: 2 }, 1),
+                (Point { x: 1, y: 3 }, 1),
+                (Point { x: 2, y: 1 }, 1),
+                (Point { x: 3, y: 1 }, 1),
+            ]),
+        };
+        assert_eq!(got, expect);
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(
+            Input::parse("1,1 -> 1,3\n1,1 => 3,1"),
+            Err(AocError::parse(2, 5, "expected ` -> `, found `=`"))
+        );
+        assert_eq!(
+            Input::parse("1,1 -> 1,3\n1,1 -> 3;1"),
+            Err(AocError::parse(2, 9, "expected `,`, found `;`"))
+        );
+        assert_eq!(
+            Input::parse("1,1 -> 1,3\n\n1,1 -> 3,1"),
+            Err(AocError::parse(
+                1,
+                11,
+                "expected the end of the input, found the end of the line"
+            ))
+        );
+        assert_eq!(
+            Input::parse(""),
+            Err(AocError::UnexpectedEnd(String::from("a vent line")))
+        );
+    }
+
+    #[test]
+    fn build_backwards_line() {
+        let line = Line::between(
+            Point { x: 3, y: 8 },
+            Point { x: 3, y: 3 },
+            DiagonalHandling::Ignore,
+        );
+        let expect = vec![
+            Point { x: 3, y: 8 },
+            Point { x: 3, y: 7 },
+            Point { x: 3, y: 6 },
+            Point { x: 3, y: 5 },
+            Point { x: 3, y: 4 },
+            Point { x: 3, y: 3 },
+        ];
+
+        assert_eq!(line, Line(expect));
+    }
+    #[test]
+    fn build_line() {
+        let line = Line::between(
+            Point { x: 3, y: 3 },
+            Point { x: 3, y: 8 },
+            DiagonalHandling::Ignore,
+        );
+        let expect = vec![
+            Point { x: 3, y: 3 },
+            Point { x: 3, y: 4 },
+            Point { x: 3, y: 5 },
+            Point { x: 3, y: 6 },
+            Point { x: 3, y: 7 },
+            Point { x: 3, y: 8 },
+        ];
+
+        assert_eq!(line, Line(expect));
+    }
+}
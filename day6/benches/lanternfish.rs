@@ -0,0 +1,24 @@
+//! Compare the scalar and `simd` builds with
+//!
+//! ```text
+//! cargo bench -p day6 -- --save-baseline scalar
+//! cargo bench -p day6 --features simd -- --baseline scalar
+//! ```
+
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use day6::{solve_part1, solve_part2, Input, INPUT};
+
+fn lanternfish(c: &mut Criterion) {
+    let input = Input::parse(INPUT).expect("bundled input parses");
+    c.bench_function("day6 part1", |b| {
+        b.iter(|| solve_part1(black_box(input.clone())))
+    });
+    c.bench_function("day6 part2", |b| {
+        b.iter(|| solve_part2(black_box(input.clone())))
+    });
+}
+
+criterion_group!(benches, lanternfish);
+criterion_main!(benches);
@@ -0,0 +1,161 @@
+//! Counts lanternfish. Only the puzzle input and the runner's `part1`/`part2` need `std`, so
+//! with `default-features = false` the simulation runs anywhere there's an allocator.
+
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
+use alloc::{format, string::String, vec::Vec};
+#[cfg(feature = "std")]
+use aoc_common::answer::Answer;
+
+#[cfg(feature = "simd")]
+mod simd;
+
+// With `simd` only the tests still run the simulation one histogram at a time
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "simd", allow(dead_code))]
+struct FishState([u64; 9]);
+#[cfg_attr(feature = "simd", allow(dead_code))]
+impl FishState {
+    fn tick(&mut self) {
+        self.0.rotate_left(1);
+        self.0[6] += self.0[8];
+    }
+
+    fn count(&self) -> u64 {
+        self.0.iter().sum()
+    }
+}
+
+#[cfg_attr(feature = "simd", allow(dead_code))]
+struct State {
+    fishes: FishState,
+    tick: i32,
+}
+
+#[cfg_attr(feature = "simd", allow(dead_code))]
+impl State {
+    fn tick(&mut self) {
+        self.fishes.tick();
+        self.tick += 1;
+    }
+
+    fn new(fishes: Vec<i32>) -> Self {
+        let mut acc = [0; 9];
+        fishes.iter().for_each(|n| {
+            acc[*n as usize] += 1;
+        });
+        let fishes = FishState(acc);
+        Self { fishes, tick: 0 }
+    }
+}
+
+impl Iterator for State {
+    type Item = FishState;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.tick();
+        Some(self.fishes)
+    }
+}
+
+#[derive(Clone)]
+pub struct Input(Vec<i32>);
+impl Input {
+    pub fn parse(input: &str) -> Result<Self, String> {
+        let input = input.trim().split(',');
+        let mut result = Vec::new();
+        for n in input {
+            let n = match n.parse() {
+                Ok(n) => Ok(n),
+                Err(_) => Err(format!("failed to parse number {:?} in input", n)),
+            }?;
+            result.push(n);
+        }
+        Ok(Self(result))
+    }
+}
+
+/// How many fish there are after `ticks` days
+#[cfg(not(feature = "simd"))]
+fn count_after(fishes: Vec<i32>, ticks: usize) -> u64 {
+    let state = State::new(fishes);
+    match state.into_iter().nth(ticks - 1) {
+        Some(newstate) => newstate.count(),
+        None => panic!("this error should never happen"),
+    }
+}
+
+/// How many fish there are after `ticks` days, looked up from how many one fish with each
+/// starting timer turns into
+#[cfg(feature = "simd")]
+fn count_after(fishes: Vec<i32>, ticks: usize) -> u64 {
+    let descendants = simd::descendants(ticks);
+    fishes.iter().map(|n| descendants[*n as usize]).sum()
+}
+
+pub fn solve_part1(input: Input) -> u64 {
+    count_after(input.0, 80)
+}
+
+pub fn solve_part2(input: Input) -> u64 {
+    count_after(input.0, 256)
+}
+
+#[cfg(feature = "std")]
+pub const INPUT: &str = include_str!("input.txt");
+
+/// Lanternfish after 80 days
+#[cfg(feature = "std")]
+pub fn part1(input: &str) -> Result<Answer, String> {
+    let input = Input::parse(input)?;
+    Ok(solve_part1(input).into())
+}
+
+/// Lanternfish after 256 days
+#[cfg(feature = "std")]
+pub fn part2(input: &str) -> Result<Answer, String> {
+    let input = Input::parse(input)?;
+    Ok(solve_part2(input).into())
+}
+
+#[cfg(feature = "std")]
+aoc_common::register_day!(6);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const INPUT: &str = include_str!("test_input.txt");
+
+    #[test]
+    fn test_solve_part1() {
+        let input = Input::parse(INPUT).expect("Failed to parse input");
+        let part1 = solve_part1(input);
+        let expected = 5934u64;
+        assert_eq!(part1, expected);
+    }
+    #[test]
+    fn test_solve_part2() {
+        let input = Input::parse(INPUT).expect("Failed to parse input");
+        let part2 = solve_part2(input);
+        let expected = 26984457539u64;
+        assert_eq!(part2, expected);
+    }
+
+    #[test]
+    fn test_tick() {
+        let mut state = State::new(Input::parse(INPUT).expect("Failed to parse input").0);
+        state.tick();
+        assert_eq!(&state.fishes.0, &[1, 1, 2, 1, 0, 0, 0, 0, 0]);
+        state.tick();
+        assert_eq!(&state.fishes.0, &[1, 2, 1, 0, 0, 0, 1, 0, 1]);
+        state.tick();
+        assert_eq!(&state.fishes.0, &[2, 1, 0, 0, 0, 1, 1, 1, 1]);
+        state.tick();
+        assert_eq!(&state.fishes.0, &[1, 0, 0, 0, 1, 1, 3, 1, 2]);
+        state.tick();
+        assert_eq!(&state.fishes.0, &[0, 0, 0, 1, 1, 3, 2, 2, 1]);
+    }
+}
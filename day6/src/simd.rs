@@ -0,0 +1,49 @@
+//! Ticks a lone fish with every starting timer at once, one starting timer per lane
+
+use wide::u64x4;
+
+/// Enough groups of four lanes for starting timers 0 to 8
+const GROUPS: usize = 3;
+
+/// How many fish a single fish with each starting timer (0 to 8) has become after `ticks` days
+pub(crate) fn descendants(ticks: usize) -> [u64; 9] {
+    // timers[t] counts, in each starting timer's lane, how many of its fish have timer t. The
+    // array is a ring that turns a slot each day instead of shifting all nine along, so after
+    // `day` days timer t is at slot (day + t) % 9.
+    let mut timers = [[u64x4::splat(0); GROUPS]; 9];
+    for start in 0..9 {
+        timers[start][start / 4].as_array_mut()[start % 4] = 1;
+    }
+    for day in 0..ticks {
+        // The fish at timer 0 stay where they are as their own babies at timer 8, and reset to 6
+        let zero = day % 9;
+        let parents = timers[zero];
+        for (timer, parent) in timers[(zero + 7) % 9].iter_mut().zip(parents) {
+            *timer += parent;
+        }
+    }
+    let mut totals = [u64x4::splat(0); GROUPS];
+    for counts in timers {
+        for (total, count) in totals.iter_mut().zip(counts) {
+            *total += count;
+        }
+    }
+    core::array::from_fn(|start| totals[start / 4].as_array_ref()[start % 4])
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::State;
+
+    #[test]
+    fn test_matches_scalar() {
+        for ticks in [1, 18, 80, 256] {
+            let expected: [u64; 9] = core::array::from_fn(|start| {
+                let mut state = State::new(vec![start as i32]);
+                state.nth(ticks - 1).unwrap().count()
+            });
+            assert_eq!(descendants(ticks), expected, "after {} ticks", ticks);
+        }
+    }
+}
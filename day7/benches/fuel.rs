@@ -0,0 +1,24 @@
+//! Compare the scalar and `simd` builds with
+//!
+//! ```text
+//! cargo bench -p day7 -- --save-baseline scalar
+//! cargo bench -p day7 --features simd -- --baseline scalar
+//! ```
+
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use day7::{solve_part1, solve_part2, Input, INPUT};
+
+fn fuel(c: &mut Criterion) {
+    let input = Input::parse(INPUT).expect("bundled input parses");
+    c.bench_function("day7 part1", |b| {
+        b.iter(|| solve_part1(black_box(input.clone())))
+    });
+    c.bench_function("day7 part2", |b| {
+        b.iter(|| solve_part2(black_box(input.clone())))
+    });
+}
+
+criterion_group!(benches, fuel);
+criterion_main!(benches);
@@ -0,0 +1,148 @@
+//! Lines up the crabs for the least fuel. Only the puzzle input and the runner's
+//! `part1`/`part2` need `std`, so with `default-features = false` the fuel calculation runs
+//! anywhere there's an allocator.
+
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
+use alloc::{string::String, vec::Vec};
+#[cfg(feature = "std")]
+use aoc_common::answer::Answer;
+
+#[cfg(feature = "simd")]
+mod simd;
+
+#[derive(Clone, Debug)]
+pub struct Input(Vec<i64>);
+impl Input {
+    pub fn parse(input: &str) -> Result<Self, String> {
+        let mut vec = Vec::new();
+        for n in input.trim_end().split(',') {
+            vec.push(n.parse().map_err(|_| "can't parse value")?)
+        }
+        Ok(Self(vec))
+    }
+}
+
+#[derive(Clone, Copy)]
+enum CalculationMethod {
+    FlatCost,
+    IncreasingCost,
+}
+
+impl CalculationMethod {
+    /// Fuel for one crab to move `distance`
+    fn cost(self, distance: u64) -> u64 {
+        match self {
+            Self::FlatCost => distance,
+            Self::IncreasingCost => (1..=distance).sum(),
+        }
+    }
+}
+
+/// Legacy
+fn total_fuel_cost(positions: &[i64], target_position: i64) -> u64 {
+    total_fuel_cost_by_calculation(positions, target_position, CalculationMethod::FlatCost)
+}
+
+fn total_fuel_cost_by_calculation(
+    positions: &[i64],
+    target_position: i64,
+    calculation: CalculationMethod,
+) -> u64 {
+    #[cfg(feature = "simd")]
+    {
+        simd::total_fuel_cost(positions, target_position, calculation)
+    }
+    #[cfg(not(feature = "simd"))]
+    {
+        scalar_total_fuel_cost(positions, target_position, calculation)
+    }
+}
+
+/// One crab at a time
+#[cfg(any(not(feature = "simd"), test))]
+fn scalar_total_fuel_cost(
+    positions: &[i64],
+    target_position: i64,
+    calculation: CalculationMethod,
+) -> u64 {
+    positions
+        .iter()
+        .map(|pos| calculation.cost(pos.abs_diff(target_position)))
+        .sum()
+}
+
+pub fn solve_part1(input: Input) -> u64 {
+    let positions = input.0;
+    let (min, max) = (
+        *positions.iter().min().unwrap(),
+        *positions.iter().max().unwrap(),
+    );
+    (min..=max)
+        .map(|target_position| total_fuel_cost(&positions, target_position))
+        .min()
+        .unwrap()
+}
+
+pub fn solve_part2(input: Input) -> u64 {
+    let positions = input.0;
+    let (min, max) = (
+        *positions.iter().min().unwrap(),
+        *positions.iter().max().unwrap(),
+    );
+    (min..=max)
+        .map(|target_position| {
+            total_fuel_cost_by_calculation(
+                &positions,
+                target_position,
+                CalculationMethod::IncreasingCost,
+            )
+        })
+        .min()
+        .unwrap()
+}
+
+#[cfg(feature = "std")]
+pub const INPUT: &str = include_str!("input.txt");
+
+/// Least fuel to align every crab when each step costs 1
+#[cfg(feature = "std")]
+pub fn part1(input: &str) -> Result<Answer, String> {
+    let input = Input::parse(input)?;
+    Ok(solve_part1(input).into())
+}
+
+/// Least fuel to align every crab when each step costs one more than the last
+#[cfg(feature = "std")]
+pub fn part2(input: &str) -> Result<Answer, String> {
+    let input = Input::parse(input)?;
+    Ok(solve_part2(input).into())
+}
+
+#[cfg(feature = "std")]
+aoc_common::register_day!(7);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const INPUT: &str = include_str!("test_input.txt");
+
+    #[test]
+    fn test_solve_part1() {
+        let input = Input::parse(INPUT).expect("failed to parse input");
+        let got = solve_part1(input);
+        let expected = 37;
+        assert_eq!(got, expected)
+    }
+
+    #[test]
+    fn test_solve_part2() {
+        let input = Input::parse(INPUT).expect("failed to parse input");
+        let got = solve_part2(input);
+        let expected = 168;
+        assert_eq!(got, expected)
+    }
+}
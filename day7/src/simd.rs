@@ -0,0 +1,56 @@
+//! Fuel costs for four crabs at a time
+
+use wide::i64x4;
+
+use crate::CalculationMethod;
+
+/// Same as adding up each crab's cost, but four lanes at once, with any crabs left over from
+/// the last group of four added up one by one
+pub(crate) fn total_fuel_cost(
+    positions: &[i64],
+    target_position: i64,
+    calculation: CalculationMethod,
+) -> u64 {
+    let target = i64x4::splat(target_position);
+    let chunks = positions.chunks_exact(4);
+    let leftover: u64 = chunks
+        .remainder()
+        .iter()
+        .map(|pos| calculation.cost(pos.abs_diff(target_position)))
+        .sum();
+    let mut total = i64x4::splat(0);
+    for chunk in chunks {
+        let positions = i64x4::new(chunk.try_into().expect("chunks are 4 long"));
+        let distance = (positions - target).abs();
+        let cost = match calculation {
+            CalculationMethod::FlatCost => distance,
+            // 1 + 2 + ... + n, without the loop
+            CalculationMethod::IncreasingCost => (distance * (distance + 1)) >> 1,
+        };
+        total = total + cost;
+    }
+    total.to_array().iter().sum::<i64>() as u64 + leftover
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::scalar_total_fuel_cost;
+
+    #[test]
+    fn test_matches_scalar() {
+        // 11 crabs, so there are leftovers after the groups of four
+        let positions = [16, 1, 2, 0, 4, 2, 7, 1, 2, 14, 1900];
+        for calculation in [
+            CalculationMethod::FlatCost,
+            CalculationMethod::IncreasingCost,
+        ] {
+            for target in [0, 2, 5, 1900, 2000] {
+                assert_eq!(
+                    total_fuel_cost(&positions, target, calculation),
+                    scalar_total_fuel_cost(&positions, target, calculation),
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,173 @@
+use aoc_common::{answer::Answer, error::AocError};
+use grid2d::Grid;
+
+use std::{collections::HashSet, str::FromStr};
+
+type Coords = (usize, usize);
+
+#[derive(Debug, Clone)]
+struct Basin {
+    members: Vec<Coords>,
+}
+
+impl Basin {
+    fn size(&self) -> usize {
+        self.members.len()
+    }
+}
+
+fn risk_level(height: u8) -> u64 {
+    u64::from(height) + 1
+}
+
+#[allow(dead_code)] // This was used to help diagnose issues with basin identification
+fn display_basin(grid: &Grid<u8>, basin: &Basin) -> String {
+    Grid::from_fn(grid.width(), grid.height(), |x, y| {
+        if basin.members.contains(&(x, y)) {
+            grid[(x, y)].to_string()
+        } else {
+            ".".to_string()
+        }
+    })
+    .to_string()
+}
+
+fn low_points(grid: &Grid<u8>) -> impl Iterator<Item = (Coords, u8)> + '_ {
+    grid.iter_coords()
+        // If neighbors are all larger than self, then it's a low point
+        .filter(|&((x, y), &value)| grid.neighbors4(x, y).all(|neighbor| grid[neighbor] > value))
+        .map(|(coords, &value)| (coords, value))
+}
+
+fn basins(grid: &Grid<u8>) -> Vec<Basin> {
+    let mut pool: Vec<Coords> = grid
+        .iter_coords()
+        .filter(|(_, &value)| value != 9)
+        .map(|(coords, _)| coords)
+        .collect();
+    let mut seen: HashSet<Coords> = HashSet::new();
+
+    let mut basins = Vec::new();
+    loop {
+        let mut members: Vec<Coords> = Vec::new();
+        let head = match pool.pop() {
+            None => break,
+            Some(coords) => match !seen.contains(&coords) {
+                true => coords,
+                false => continue,
+            },
+        };
+        let mut pending: Vec<Coords> = vec![head];
+        while let Some(coords) = pending.pop() {
+            // If pending is not empty
+            // Then pop the tail and push it onto members
+            members.push(coords);
+            // Mark that member as seen
+            seen.insert(coords);
+            // Get the non-nine-value neighbors
+            for neighbor in grid
+                .neighbors4(coords.0, coords.1)
+                .filter(|&neighbor| grid[neighbor] != 9)
+            {
+                // and if they haven't been seen already
+                if !seen.contains(&neighbor) {
+                    // Push them into the pending list
+                    pending.push(neighbor);
+                    // and "see" them
+                    seen.insert(neighbor);
+                }
+            }
+            // If pending IS empty
+        }
+        basins.push(Basin { members });
+    }
+
+    basins
+}
+
+#[derive(Clone, Debug)]
+pub struct Input<T> {
+    grid: Grid<T>,
+}
+
+impl FromStr for Input<u8> {
+    type Err = AocError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let grid = s.parse::<Grid<u8>>()?;
+        Ok(Self { grid })
+    }
+}
+
+pub const INPUT: &str = include_str!("input.txt");
+
+pub fn solve_part1(input: Input<u8>) -> u64 {
+    low_points(&input.grid)
+        .map(|(_, height)| risk_level(height))
+        .sum()
+}
+
+pub fn solve_part2(input: Input<u8>) -> u64 {
+    let mut basins = basins(&input.grid);
+    basins.sort_unstable_by_key(|basin| basin.size());
+    basins
+        .into_iter()
+        .rev()
+        .take(3)
+        .map(|basin| -> u64 { basin.size().try_into().unwrap() })
+        .product()
+}
+
+/// Sum of the risk levels of every low point
+pub fn part1(input: &str) -> Result<Answer, String> {
+    let input = input.parse::<Input<u8>>().map_err(|e| e.to_string())?;
+    Ok(solve_part1(input).into())
+}
+
+/// Product of the sizes of the three largest basins
+pub fn part2(input: &str) -> Result<Answer, String> {
+    let input = input.parse::<Input<u8>>().map_err(|e| e.to_string())?;
+    Ok(solve_part2(input).into())
+}
+
+aoc_common::register_day!(9);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    const INPUT: &str = include_str!("test_input.txt");
+
+    #[test]
+    fn solve_part1() {
+        let input = INPUT.parse::<Input<u8>>().unwrap();
+        let part1 = super::solve_part1(input);
+        let expected = 15;
+
+        assert_eq!(part1, expected);
+    }
+
+    #[test]
+    fn solve_part2() {
+        let input = INPUT.parse::<Input<u8>>().unwrap();
+        let part2 = super::solve_part2(input);
+        let expected = 1134;
+
+        assert_eq!(part2, expected);
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(
+            "123\n4a6".parse::<Input<u8>>().map(|_| ()),
+            Err(AocError::parse(2, 2, "expected a digit, found 'a'"))
+        );
+        assert_eq!(
+            "123\n45".parse::<Input<u8>>().map(|_| ()),
+            Err(AocError::parse(
+                2,
+                3,
+                "expected 3 digits like the first line, found 2"
+            ))
+        );
+    }
+}
@@ -0,0 +1,8 @@
+#![no_main]
+
+use day13::Input;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    let _ = input.parse::<Input>();
+});
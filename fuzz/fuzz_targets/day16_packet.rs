@@ -0,0 +1,11 @@
+#![no_main]
+
+use day16::Packet;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|hex: &str| {
+    if let Some(packet) = Packet::from_hex_str(hex) {
+        packet.version_sum();
+        let _ = packet.to_string();
+    }
+});
@@ -0,0 +1,10 @@
+#![no_main]
+
+use day18::SnailFish;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|line: &str| {
+    if let Ok(snailfish) = line.parse::<SnailFish>() {
+        let _ = format!("{:?}", snailfish);
+    }
+});
@@ -0,0 +1,396 @@
+use aoc_common::error::AocError;
+
+use std::{
+    fmt::Display,
+    ops::{Index, IndexMut},
+    str::FromStr,
+};
+
+/// A rectangular grid stored row by row in one flat `Vec`.
+///
+/// Coordinates are `(x, y)`, with `(0, 0)` in the top left and `y` growing downwards, the way
+/// puzzle inputs are written.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+const OFFSETS_4: [(isize, isize); 4] = [(0, -1), (-1, 0), (1, 0), (0, 1)];
+const OFFSETS_8: [(isize, isize); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (-1, 0),
+    (1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+];
+
+impl<T> Grid<T> {
+    /// Builds a grid from cells given row by row, or `None` if there aren't `width * height`
+    /// of them
+    pub fn new(width: usize, height: usize, cells: Vec<T>) -> Option<Self> {
+        (width.checked_mul(height)? == cells.len()).then_some(Self {
+            cells,
+            width,
+            height,
+        })
+    }
+
+    /// Builds a grid by calling `f(x, y)` for every cell, row by row
+    pub fn from_fn(width: usize, height: usize, mut f: impl FnMut(usize, usize) -> T) -> Self {
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(|(x, y)| f(x, y))
+            .collect();
+        Self {
+            cells,
+            width,
+            height,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// Position of `(x, y)` in the flat storage, if it's inside the grid
+    pub fn index_of(&self, x: usize, y: usize) -> Option<usize> {
+        (x < self.width && y < self.height).then_some(x + y * self.width)
+    }
+
+    /// Coordinates of a position in the flat storage
+    pub fn coords_of(&self, idx: usize) -> (usize, usize) {
+        (idx % self.width, idx / self.width)
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<&T> {
+        self.index_of(x, y).map(|idx| &self.cells[idx])
+    }
+
+    pub fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut T> {
+        self.index_of(x, y).map(|idx| &mut self.cells[idx])
+    }
+
+    /// Every cell, row by row
+    pub fn iter(&self) -> std::slice::Iter<'_, T> {
+        self.cells.iter()
+    }
+
+    pub fn iter_mut(&mut self) -> std::slice::IterMut<'_, T> {
+        self.cells.iter_mut()
+    }
+
+    /// Every cell with its coordinates, row by row
+    pub fn iter_coords(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        self.cells
+            .iter()
+            .enumerate()
+            .map(|(idx, cell)| (self.coords_of(idx), cell))
+    }
+
+    /// Every row, top to bottom
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // chunks_exact panics on 0, but an empty grid has no rows either way
+        self.cells.chunks_exact(self.width.max(1))
+    }
+
+    pub fn row(&self, y: usize) -> Option<&[T]> {
+        (y < self.height).then(|| &self.cells[y * self.width..(y + 1) * self.width])
+    }
+
+    /// The cells in column `x`, top to bottom. Empty if `x` is outside the grid.
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        let cells = if x < self.width {
+            &self.cells[x..]
+        } else {
+            &[]
+        };
+        cells.iter().step_by(self.width.max(1))
+    }
+
+    /// Every column, left to right
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    fn offset_neighbors<'a>(
+        &'a self,
+        x: usize,
+        y: usize,
+        offsets: &'a [(isize, isize)],
+    ) -> impl Iterator<Item = (usize, usize)> + 'a {
+        offsets.iter().filter_map(move |&(dx, dy)| {
+            let x = x.checked_add_signed(dx)?;
+            let y = y.checked_add_signed(dy)?;
+            self.index_of(x, y).map(|_| (x, y))
+        })
+    }
+
+    /// Coordinates of the up to 4 cells sharing an edge with `(x, y)`
+    pub fn neighbors4(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.offset_neighbors(x, y, &OFFSETS_4)
+    }
+
+    /// Coordinates of the up to 8 cells sharing an edge or a corner with `(x, y)`
+    pub fn neighbors8(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.offset_neighbors(x, y, &OFFSETS_8)
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (x, y): (usize, usize)) -> &Self::Output {
+        let idx = self
+            .index_of(x, y)
+            .unwrap_or_else(|| panic!("({}, {}) is outside the grid", x, y));
+        &self.cells[idx]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut Self::Output {
+        let idx = self
+            .index_of(x, y)
+            .unwrap_or_else(|| panic!("({}, {}) is outside the grid", x, y));
+        &mut self.cells[idx]
+    }
+}
+
+/// Prints each row on its own line with no separators, like the puzzle inputs
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseGridError {
+    /// A character that isn't a decimal digit, at a 1-indexed line and column
+    NotADigit {
+        line: usize,
+        column: usize,
+        ch: char,
+    },
+    /// A line whose length doesn't match the first line's
+    Ragged {
+        line: usize,
+        expected: usize,
+        found: usize,
+    },
+}
+
+impl Display for ParseGridError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NotADigit { line, column, ch } => {
+                write!(
+                    f,
+                    "line {} column {}: {:?} is not a digit",
+                    line, column, ch
+                )
+            }
+            Self::Ragged {
+                line,
+                expected,
+                found,
+            } => write!(
+                f,
+                "line {}: expected {} cells like the first line, found {}",
+                line, expected, found
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ParseGridError {}
+
+impl From<ParseGridError> for AocError {
+    fn from(err: ParseGridError) -> Self {
+        match err {
+            ParseGridError::NotADigit { line, column, ch } => {
+                AocError::parse(line, column, format!("expected a digit, found {:?}", ch))
+            }
+            ParseGridError::Ragged {
+                line,
+                expected,
+                found,
+            } => AocError::parse(
+                line,
+                // Point just past the first line's width, or at the end of a short line
+                expected.min(found) + 1,
+                format!(
+                    "expected {} digits like the first line, found {}",
+                    expected, found
+                ),
+            ),
+        }
+    }
+}
+
+impl<T: From<u8>> Grid<T> {
+    /// Parses lines of digits one at a time, one cell per digit, the same as [`FromStr`] does
+    /// with a whole block. Handy when the lines come from a reader instead of a string.
+    pub fn from_lines<S: AsRef<str>>(
+        lines: impl IntoIterator<Item = S>,
+    ) -> Result<Self, ParseGridError> {
+        let mut cells = Vec::new();
+        let mut width = None;
+        let mut height = 0;
+        for (line_idx, line) in lines.into_iter().enumerate() {
+            let before = cells.len();
+            for (column, ch) in line.as_ref().chars().enumerate() {
+                let digit = ch.to_digit(10).ok_or(ParseGridError::NotADigit {
+                    line: line_idx + 1,
+                    column: column + 1,
+                    ch,
+                })?;
+                cells.push(T::from(digit as u8));
+            }
+            let found = cells.len() - before;
+            let expected = *width.get_or_insert(found);
+            if found != expected {
+                return Err(ParseGridError::Ragged {
+                    line: line_idx + 1,
+                    expected,
+                    found,
+                });
+            }
+            height += 1;
+        }
+        Ok(Self {
+            cells,
+            width: width.unwrap_or(0),
+            height,
+        })
+    }
+}
+
+/// Parses a block of digits like `"123\n456"`, one cell per digit
+impl<T: From<u8>> FromStr for Grid<T> {
+    type Err = ParseGridError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_lines(s.lines())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn sample() -> Grid<u8> {
+        "123\n456".parse().expect("sample must parse")
+    }
+
+    #[test]
+    fn test_parse() {
+        let grid = sample();
+        assert_eq!((grid.width(), grid.height(), grid.len()), (3, 2, 6));
+        assert_eq!(grid, Grid::new(3, 2, vec![1, 2, 3, 4, 5, 6]).unwrap());
+        assert_eq!(grid.to_string(), "123\n456");
+        assert_eq!(
+            "12\n345".parse::<Grid<u8>>(),
+            Err(ParseGridError::Ragged {
+                line: 2,
+                expected: 2,
+                found: 3
+            })
+        );
+        assert_eq!(
+            "12\n3x".parse::<Grid<u8>>(),
+            Err(ParseGridError::NotADigit {
+                line: 2,
+                column: 2,
+                ch: 'x'
+            })
+        );
+        assert!("".parse::<Grid<u32>>().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_from_lines() {
+        let lines = vec![String::from("123"), String::from("456")];
+        assert_eq!(Grid::from_lines(lines), Ok(sample()));
+        assert_eq!(
+            Grid::<u8>::from_lines(["12", "3x"]),
+            "12\n3x".parse::<Grid<u8>>()
+        );
+        assert!(Grid::<u8>::from_lines(Vec::<&str>::new())
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_access() {
+        let mut grid = sample();
+        assert_eq!(grid.get(2, 1), Some(&6));
+        assert_eq!(grid.get(3, 0), None);
+        assert_eq!(grid.get(0, 2), None);
+        assert_eq!(grid[(1, 0)], 2);
+        grid[(1, 0)] = 9;
+        assert_eq!(grid.get(1, 0), Some(&9));
+        assert_eq!(grid.index_of(2, 1), Some(5));
+        assert_eq!(grid.coords_of(5), (2, 1));
+        assert_eq!(Grid::from_fn(3, 2, |x, y| (x + 3 * y + 1) as u8), sample());
+        assert_eq!(Grid::new(2, 2, vec![1, 2, 3]), None);
+    }
+
+    #[test]
+    fn test_rows_and_columns() {
+        let grid = sample();
+        assert_eq!(
+            grid.rows().collect::<Vec<_>>(),
+            vec![&[1, 2, 3], &[4, 5, 6]]
+        );
+        assert_eq!(grid.row(1), Some(&[4, 5, 6][..]));
+        assert_eq!(grid.row(2), None);
+        assert_eq!(grid.column(1).copied().collect::<Vec<_>>(), vec![2, 5]);
+        assert_eq!(grid.column(3).count(), 0);
+        let columns: Vec<Vec<u8>> = grid.columns().map(|c| c.copied().collect()).collect();
+        assert_eq!(columns, vec![vec![1, 4], vec![2, 5], vec![3, 6]]);
+        assert_eq!(
+            grid.iter_coords().nth(4),
+            Some(((1, 1), &5)),
+            "iter_coords goes row by row"
+        );
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let grid: Grid<u8> = "123\n456\n789".parse().unwrap();
+        let mut corner: Vec<_> = grid.neighbors4(0, 0).collect();
+        corner.sort();
+        assert_eq!(corner, vec![(0, 1), (1, 0)]);
+        assert_eq!(grid.neighbors4(1, 1).count(), 4);
+        assert_eq!(grid.neighbors8(1, 1).count(), 8);
+        assert_eq!(grid.neighbors8(2, 2).count(), 3);
+        // edges don't wrap around onto the next row
+        assert!(grid.neighbors8(2, 0).all(|(x, _)| x >= 1));
+        assert!(grid.neighbors8(0, 1).all(|(x, _)| x <= 1));
+    }
+}
@@ -0,0 +1,184 @@
+//! Repository chores, run with `cargo xtask <command>`
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::ExitCode,
+};
+
+use clap::{Parser, Subcommand};
+
+const CARGO_TOML: &str = include_str!("../templates/Cargo.toml.tmpl");
+const LIB_RS: &str = include_str!("../templates/lib.rs");
+const MAIN_RS: &str = include_str!("../templates/main.rs");
+
+#[derive(Parser)]
+#[command(about = "Repository chores")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Create a dayN crate from the template and add it to the workspace
+    NewDay {
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+    },
+}
+
+fn workspace_root() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .expect("xtask lives inside the workspace")
+        .to_path_buf()
+}
+
+fn render(template: &str, day: u8) -> String {
+    template
+        .replace("{{name}}", &format!("day{}", day))
+        .replace("{{day}}", &day.to_string())
+}
+
+/// Adds `dayN` to the workspace `members`, after the days before it
+fn add_member(manifest: &str, day: u8) -> Result<String, String> {
+    let name = format!("day{}", day);
+    let entry = format!("\"{}\",", name);
+    let mut lines: Vec<&str> = manifest.lines().collect();
+    let start = lines
+        .iter()
+        .position(|line| line.trim() == "members = [")
+        .ok_or("the workspace Cargo.toml has no `members = [` list")?;
+    let end = start
+        + lines[start..]
+            .iter()
+            .position(|line| line.trim() == "]")
+            .ok_or("the workspace `members` list isn't closed")?;
+    if lines[start..end].iter().any(|line| line.trim() == entry) {
+        return Err(format!("{} is already a workspace member", name));
+    }
+    let day_of = |line: &str| -> Option<u8> {
+        line.trim()
+            .strip_prefix("\"day")?
+            .strip_suffix("\",")?
+            .parse()
+            .ok()
+    };
+    let earlier_days = lines[start + 1..end]
+        .iter()
+        .rposition(|line| day_of(line).is_some_and(|other| other < day));
+    let later_days = lines[start + 1..end]
+        .iter()
+        .position(|line| day_of(line).is_some());
+    let at = match (earlier_days, later_days) {
+        (Some(idx), _) => start + 1 + idx + 1,
+        (None, Some(idx)) => start + 1 + idx,
+        (None, None) => end,
+    };
+    let line = format!("    {}", entry);
+    lines.insert(at, &line);
+    Ok(lines.join("\n") + "\n")
+}
+
+/// Writes the new crate under `root` and registers it in `root`'s Cargo.toml
+fn new_day(root: &Path, day: u8) -> Result<PathBuf, String> {
+    let dir = root.join(format!("day{}", day));
+    if dir.exists() {
+        return Err(format!("{} already exists", dir.display()));
+    }
+    let manifest_path = root.join("Cargo.toml");
+    let manifest = fs::read_to_string(&manifest_path)
+        .map_err(|e| format!("couldn't read {}: {}", manifest_path.display(), e))?;
+    let manifest = add_member(&manifest, day)?;
+
+    let src = dir.join("src");
+    fs::create_dir_all(&src).map_err(|e| format!("couldn't create {}: {}", src.display(), e))?;
+    for (path, contents) in [
+        (dir.join("Cargo.toml"), render(CARGO_TOML, day)),
+        (src.join("lib.rs"), render(LIB_RS, day)),
+        (src.join("main.rs"), render(MAIN_RS, day)),
+        (src.join("input.txt"), String::new()),
+        (src.join("test_input.txt"), String::new()),
+    ] {
+        fs::write(&path, contents)
+            .map_err(|e| format!("couldn't write {}: {}", path.display(), e))?;
+    }
+    fs::write(&manifest_path, manifest)
+        .map_err(|e| format!("couldn't write {}: {}", manifest_path.display(), e))?;
+    Ok(dir)
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let result = match cli.command {
+        Command::NewDay { day } => new_day(&workspace_root(), day).map(|dir| {
+            println!("created {}", dir.display());
+            println!(
+                "paste your input into src/input.txt and the example into src/test_input.txt, \
+                 then add day{0} to aoc's dependencies with `use day{0} as _;` in \
+                 aoc/src/main.rs",
+                day
+            );
+        }),
+    };
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::CommandFactory;
+
+    use super::*;
+
+    const MANIFEST: &str = "\
+[workspace]
+
+members = [
+    \"aoc\",
+    \"day1\",
+    \"day2\",
+    \"day10\",
+    \"grid2d\",
+]
+";
+
+    #[test]
+    fn adds_members_in_day_order() {
+        let added = add_member(MANIFEST, 3).unwrap();
+        assert!(added.contains("    \"day2\",\n    \"day3\",\n    \"day10\",\n"));
+        let added = add_member(MANIFEST, 19).unwrap();
+        assert!(added.contains("    \"day10\",\n    \"day19\",\n    \"grid2d\",\n"));
+        assert!(add_member(MANIFEST, 2).is_err());
+        assert!(add_member("[workspace]\n", 2).is_err());
+    }
+
+    #[test]
+    fn fills_in_the_templates() {
+        let main = render(MAIN_RS, 19);
+        assert!(main.contains("use day19::*;"));
+        assert!(render(CARGO_TOML, 19).contains("name = \"day19\""));
+        assert!(!render(LIB_RS, 19).contains("{{"));
+    }
+
+    #[test]
+    fn matches_the_workspace_template() {
+        // The templates should stay in step with how the existing days look
+        let root = workspace_root();
+        let day14 = fs::read_to_string(root.join("day14/src/main.rs")).unwrap();
+        assert_eq!(render(MAIN_RS, 14), day14);
+        let manifest = fs::read_to_string(root.join("Cargo.toml")).unwrap();
+        assert!(add_member(&manifest, 14).is_err());
+    }
+
+    #[test]
+    fn cli_parses() {
+        Cli::command().debug_assert();
+    }
+}
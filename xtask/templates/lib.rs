@@ -0,0 +1,65 @@
+use std::str::FromStr;
+
+use aoc_common::answer::Answer;
+
+pub const INPUT: &str = include_str!("input.txt");
+
+#[derive(Debug, Clone)]
+pub struct Input {
+    lines: Vec<String>,
+}
+
+impl FromStr for Input {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self {
+            lines: s.lines().map(String::from).collect(),
+        })
+    }
+}
+
+pub fn solve_part1(input: Input) -> u64 {
+    todo!("solve part 1 from {} lines", input.lines.len())
+}
+
+pub fn solve_part2(input: Input) -> u64 {
+    todo!("solve part 2 from {} lines", input.lines.len())
+}
+
+/// Day {{day}} part 1
+pub fn part1(input: &str) -> Result<Answer, String> {
+    let input = input.parse::<Input>()?;
+    Ok(solve_part1(input).into())
+}
+
+/// Day {{day}} part 2
+pub fn part2(input: &str) -> Result<Answer, String> {
+    let input = input.parse::<Input>()?;
+    Ok(solve_part2(input).into())
+}
+
+aoc_common::register_day!({{day}});
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = include_str!("test_input.txt");
+
+    #[test]
+    fn solve_part1() {
+        let input = INPUT.parse::<Input>().expect("Input must parse");
+        let part1 = super::solve_part1(input);
+        let expected = 0;
+        assert_eq!(part1, expected);
+    }
+
+    #[test]
+    fn solve_part2() {
+        let input = INPUT.parse::<Input>().expect("Input must parse");
+        let part2 = super::solve_part2(input);
+        let expected = 0;
+        assert_eq!(part2, expected);
+    }
+}
@@ -0,0 +1,17 @@
+use aoc_common::{args::Args, timing::PeakAlloc};
+use {{name}}::*;
+
+#[global_allocator]
+static ALLOC: PeakAlloc = PeakAlloc;
+
+fn main() {
+    let args = Args::from_env();
+    let raw = args.read_input(INPUT);
+    let mut timer = args.timer();
+    let input = timer.measure("parse", || raw.parse::<Input>().expect("Input must parse"));
+    let part1 = timer.measure("part1", || solve_part1(input.clone()));
+    println!("part1: {part1}");
+    let part2 = timer.measure("part2", || solve_part2(input));
+    println!("part2: {part2}");
+    timer.report();
+}